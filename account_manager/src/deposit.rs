@@ -0,0 +1,191 @@
+use bls::{get_withdrawal_credentials, Keypair, PublicKey, Signature};
+use serde_derive::Serialize;
+use ssz::Encode;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use tree_hash::TreeHash;
+use types::{ChainSpec, DepositData, EthSpec, Fork, Hash256};
+
+/// The encoding used when writing `DepositData`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OutputFormat {
+    /// SSZ bytes, written as a 0x-prefixed hex string.
+    Ssz,
+    /// JSON, including the `tree_hash_root` of the deposit data.
+    Json,
+}
+
+/// The `DepositData` and the root that must be supplied to the deposit contract along with it.
+#[derive(Serialize)]
+struct DepositOutput<'a> {
+    deposit_data: &'a DepositData,
+    deposit_data_root: Hash256,
+}
+
+/// Produce `DepositData` for `amount` Gwei, with `keypair` as the validator key and
+/// `withdrawal_pubkey` as the withdrawal key.
+///
+/// The proof-of-possession is signed with `Domain::Deposit` at the genesis fork, the domain with
+/// which deposits are verified at genesis.
+pub fn build_deposit_data<E: EthSpec>(
+    keypair: &Keypair,
+    withdrawal_pubkey: &PublicKey,
+    amount: u64,
+    spec: &ChainSpec,
+) -> DepositData {
+    let withdrawal_credentials = Hash256::from_slice(
+        &get_withdrawal_credentials(withdrawal_pubkey, spec.bls_withdrawal_prefix_byte)[..],
+    );
+
+    let mut deposit_data = DepositData {
+        pubkey: keypair.pk.clone(),
+        withdrawal_credentials,
+        amount,
+        signature: Signature::empty_signature(),
+    };
+
+    let epoch = spec.genesis_slot.epoch(E::slots_per_epoch());
+    deposit_data.signature =
+        deposit_data.create_signature(&keypair.sk, epoch, &Fork::genesis(epoch), spec);
+
+    deposit_data
+}
+
+/// Returns the root of `deposit_data`, as required by the deposit contract.
+pub fn deposit_data_root(deposit_data: &DepositData) -> Hash256 {
+    Hash256::from_slice(&deposit_data.tree_hash_root())
+}
+
+/// Encode `deposit_data` in `format`.
+pub fn encode_deposit_data(
+    deposit_data: &DepositData,
+    format: OutputFormat,
+) -> Result<String, String> {
+    match format {
+        OutputFormat::Ssz => Ok(format!("0x{}", hex::encode(deposit_data.as_ssz_bytes()))),
+        OutputFormat::Json => serde_json::to_string_pretty(&DepositOutput {
+            deposit_data,
+            deposit_data_root: deposit_data_root(deposit_data),
+        })
+        .map_err(|e| format!("Unable to serialize deposit data: {:?}", e)),
+    }
+}
+
+/// Write the encoded `deposit_data` to `path`, or to stdout if `path` is `None`.
+pub fn write_deposit_data(
+    deposit_data: &DepositData,
+    format: OutputFormat,
+    path: Option<PathBuf>,
+) -> Result<(), String> {
+    let encoded = encode_deposit_data(deposit_data, format)?;
+
+    match path {
+        Some(path) => {
+            let mut file =
+                File::create(&path).map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+            writeln!(file, "{}", encoded)
+                .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+        }
+        None => {
+            println!("{}", encoded);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::SecretKey;
+    use tree_hash::SignedRoot;
+    use types::{Domain, Epoch, MinimalEthSpec};
+
+    /// Returns the keypair with the secret key `i`.
+    fn keypair(i: u8) -> Keypair {
+        let mut bytes = [0; 48];
+        bytes[47] = i;
+        let sk = SecretKey::from_bytes(&bytes).unwrap();
+        let pk = PublicKey::from_secret_key(&sk);
+
+        Keypair { sk, pk }
+    }
+
+    fn deposit_data() -> DepositData {
+        build_deposit_data::<MinimalEthSpec>(
+            &keypair(1),
+            &keypair(2).pk,
+            32_000_000_000,
+            &ChainSpec::minimal(),
+        )
+    }
+
+    // The expected values are from an independent implementation of spec v0.6.3.
+    const PUBKEY: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const WITHDRAWAL_CREDENTIALS: &str =
+        "00cf45213dd7b4716864d378f3c6d861467987e4d94b7f79a1f814a697e38637";
+    const SIGNATURE: &str = "b1ccaa010c4ea7135a22ca137765c3d709157a2cba00c1e50c3ec05d966abe14d722c7ab1a25618beb31b11e1aa9624005fefa6e986337b3f551cc2b4bad5dc7eb42d5e76d114f7486eac1a6f493fa75d660ab33f6a35987cb671b32fba0b64c";
+    const DEPOSIT_DATA_ROOT: &str =
+        "82492e665b9fe2179b07220e005f5e5fd8ec75c34683898512f8b9f571b25491";
+
+    #[test]
+    fn known_answer() {
+        let deposit_data = deposit_data();
+
+        assert_eq!(deposit_data.pubkey.as_hex_string(), format!("0x{}", PUBKEY));
+        assert_eq!(
+            hex::encode(deposit_data.withdrawal_credentials.as_bytes()),
+            WITHDRAWAL_CREDENTIALS
+        );
+        assert_eq!(deposit_data.amount, 32_000_000_000);
+        assert_eq!(hex::encode(deposit_data.signature.as_bytes()), SIGNATURE);
+        assert_eq!(
+            hex::encode(deposit_data_root(&deposit_data).as_bytes()),
+            DEPOSIT_DATA_ROOT
+        );
+    }
+
+    #[test]
+    fn signature_is_valid_proof_of_possession() {
+        let spec = ChainSpec::minimal();
+        let deposit_data = deposit_data();
+
+        let epoch = Epoch::new(0);
+        let domain = spec.get_domain(epoch, Domain::Deposit, &Fork::genesis(epoch));
+
+        assert!(deposit_data
+            .signature
+            .verify(&deposit_data.signed_root(), domain, &keypair(1).pk));
+        assert!(!deposit_data.signature.verify(
+            &deposit_data.signed_root(),
+            domain,
+            &keypair(2).pk
+        ));
+    }
+
+    #[test]
+    fn encode_ssz() {
+        let encoded = encode_deposit_data(&deposit_data(), OutputFormat::Ssz).unwrap();
+
+        let amount = hex::encode(32_000_000_000_u64.to_le_bytes());
+        assert_eq!(
+            encoded,
+            format!(
+                "0x{}{}{}{}",
+                PUBKEY, WITHDRAWAL_CREDENTIALS, amount, SIGNATURE
+            )
+        );
+    }
+
+    #[test]
+    fn encode_json() {
+        let encoded = encode_deposit_data(&deposit_data(), OutputFormat::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&encoded).unwrap();
+
+        assert_eq!(
+            json["deposit_data_root"],
+            format!("0x{}", DEPOSIT_DATA_ROOT).as_str()
+        );
+        assert_eq!(json["deposit_data"]["amount"], 32_000_000_000_u64);
+    }
+}
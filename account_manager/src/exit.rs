@@ -0,0 +1,116 @@
+use bls::{Keypair, Signature};
+use eth2_config::Eth2Config;
+use grpcio::{ChannelBuilder, EnvBuilder};
+use protos::services::{
+    Empty, PublishVoluntaryExitRequest, Validators, VoluntaryExit as VoluntaryExitProto,
+};
+use protos::services_grpc::{
+    BeaconNodeServiceClient, ValidatorServiceClient, VoluntaryExitServiceClient,
+};
+use slog::info;
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use ssz::{ssz_encode, Encode};
+use std::sync::Arc;
+use tree_hash::SignedRoot;
+use types::{Domain, Epoch, EthSpec, Fork, Slot, VoluntaryExit};
+
+/// Sign a `VoluntaryExit` for `keypair` at the Beacon Node's current epoch and submit it to the
+/// Beacon Node at `server`, which will add it to its operation pool and publish it to the network.
+pub fn exit_validator<E: EthSpec>(
+    keypair: &Keypair,
+    server: &str,
+    eth2_config: &Eth2Config,
+    log: &slog::Logger,
+) -> Result<(), String> {
+    let spec = &eth2_config.spec;
+    let env = Arc::new(EnvBuilder::new().build());
+
+    let node_info = {
+        let ch = ChannelBuilder::new(env.clone()).connect(server);
+        BeaconNodeServiceClient::new(ch)
+            .info(&Empty::new())
+            .map_err(|e| format!("Could not connect to beacon node: {:?}", e))?
+    };
+
+    if spec.chain_id != node_info.get_chain_id() as u8 {
+        return Err(format!(
+            "Beacon node has the wrong chain id. Expected chain id: {}, node's chain id: {}",
+            spec.chain_id,
+            node_info.get_chain_id()
+        ));
+    }
+
+    let proto_fork = node_info.get_fork();
+    let mut previous_version: [u8; 4] = [0; 4];
+    let mut current_version: [u8; 4] = [0; 4];
+    previous_version.copy_from_slice(&proto_fork.get_previous_version()[..4]);
+    current_version.copy_from_slice(&proto_fork.get_current_version()[..4]);
+    let fork = Fork {
+        previous_version,
+        current_version,
+        epoch: Epoch::from(proto_fork.get_epoch()),
+    };
+
+    let slot_clock = SystemTimeSlotClock::new(
+        Slot::from(node_info.get_genesis_slot()),
+        node_info.get_genesis_time(),
+        spec.seconds_per_slot,
+    );
+    let epoch = slot_clock
+        .present_slot()
+        .map_err(|e| format!("Unable to read system time: {:?}", e))?
+        .ok_or_else(|| "Genesis has not yet occurred".to_string())?
+        .epoch(E::slots_per_epoch());
+
+    let validator_index = {
+        let ch = ChannelBuilder::new(env.clone()).connect(server);
+        let mut req = Validators::new();
+        req.set_public_keys(vec![ssz_encode(&keypair.pk)]);
+
+        let resp = ValidatorServiceClient::new(ch)
+            .get_validator_indices(&req)
+            .map_err(|e| format!("Failed to get validator index: {:?}", e))?;
+
+        match resp.get_validator_indices().first() {
+            Some(index) if index.has_index() => index.get_index(),
+            _ => return Err("Validator is not known to the beacon node".to_string()),
+        }
+    };
+
+    let mut exit = VoluntaryExit {
+        epoch,
+        validator_index,
+        signature: Signature::empty_signature(),
+    };
+    let message = exit.signed_root();
+    let domain = spec.get_domain(exit.epoch, Domain::VoluntaryExit, &fork);
+    exit.signature = Signature::new(&message, domain, &keypair.sk);
+
+    info!(
+        log,
+        "Submitting voluntary exit";
+        "validator_index" => validator_index,
+        "epoch" => exit.epoch.as_u64(),
+    );
+
+    let mut exit_proto = VoluntaryExitProto::new();
+    exit_proto.set_ssz(exit.as_ssz_bytes());
+    let mut req = PublishVoluntaryExitRequest::new();
+    req.set_voluntary_exit(exit_proto);
+
+    let resp = {
+        let ch = ChannelBuilder::new(env.clone()).connect(server);
+        VoluntaryExitServiceClient::new(ch)
+            .publish_voluntary_exit(&req)
+            .map_err(|e| format!("Failed to publish voluntary exit: {:?}", e))?
+    };
+
+    if resp.get_success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Beacon node rejected voluntary exit: {}",
+            String::from_utf8_lossy(resp.get_msg())
+        ))
+    }
+}
@@ -0,0 +1,430 @@
+use bls::{Keypair, PublicKey};
+use grpcio::{CallOption, ChannelBuilder, EnvBuilder};
+use protos::services::Validators;
+use protos::services_grpc::ValidatorServiceClient;
+use slog::{error, info, warn};
+use ssz::ssz_encode;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use validator_client::{
+    read_keypair_file, Config as ValidatorClientConfig, KeyError, DEFAULT_PRIVATE_KEY_FILENAME,
+};
+
+/// How long to wait for the beacon node before listing keys without their on-chain status.
+const BEACON_NODE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The on-chain state of a validator, as reported by a beacon node.
+#[derive(Debug, PartialEq, Clone)]
+pub enum OnChainStatus {
+    /// The validator is not in the validator registry.
+    Unknown,
+    Known {
+        index: u64,
+        balance: u64,
+        status: String,
+    },
+}
+
+/// Log every validator directory in the data directory, including any whose key failed to load.
+///
+/// If `server` is reachable, the on-chain index and status of each valid key is included.
+pub fn list(config: &ValidatorClientConfig, server: Option<&str>, log: &slog::Logger) {
+    let validator_dirs = match config.read_validator_dirs() {
+        Ok(dirs) => dirs,
+        Err(e) => {
+            error!(log, "Unable to read data directory"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
+
+    let keypairs: Vec<Keypair> = validator_dirs
+        .iter()
+        .filter_map(|dir| dir.key.as_ref().ok().cloned())
+        .collect();
+    let statuses = server.and_then(|server| match request_statuses(server, &keypairs) {
+        Ok(statuses) => Some(statuses),
+        Err(e) => {
+            warn!(log, "Beacon node unavailable, on-chain status unknown"; "error" => e);
+            None
+        }
+    });
+
+    // `statuses` is in the same order as `keypairs`, i.e. the order of the valid directories.
+    let mut valid = 0;
+    for dir in &validator_dirs {
+        let name = dir.path.to_string_lossy().to_string();
+        match &dir.key {
+            Ok(keypair) => {
+                match statuses.as_ref().map(|statuses| &statuses[valid]) {
+                    Some(OnChainStatus::Known {
+                        index,
+                        balance,
+                        status,
+                    }) => info!(
+                        log,
+                        "Validator key";
+                        "path" => name,
+                        "pubkey" => keypair.pk.as_hex_string(),
+                        "index" => index,
+                        "balance" => balance,
+                        "status" => status,
+                    ),
+                    Some(OnChainStatus::Unknown) => info!(
+                        log,
+                        "Validator key";
+                        "path" => name,
+                        "pubkey" => keypair.pk.as_hex_string(),
+                        "status" => "not in registry",
+                    ),
+                    None => info!(
+                        log,
+                        "Validator key";
+                        "path" => name,
+                        "pubkey" => keypair.pk.as_hex_string(),
+                    ),
+                }
+                valid += 1;
+            }
+            Err(e) => warn!(
+                log,
+                "Invalid validator directory";
+                "path" => name,
+                "error" => format!("{:?}", e),
+            ),
+        }
+    }
+
+    // Include keys stored under the wrong directory name, as these are often copies of a key that
+    // is also stored correctly.
+    let all_keypairs: Vec<Keypair> = validator_dirs
+        .iter()
+        .filter_map(|dir| match &dir.key {
+            Ok(keypair) => Some(keypair.clone()),
+            Err(KeyError::MismatchedDirectory { .. }) => read_keypair_file(&dir.key_path()).ok(),
+            Err(_) => None,
+        })
+        .collect();
+    report_duplicates(&all_keypairs, log);
+
+    info!(
+        log,
+        "Listed validator keys";
+        "valid" => valid,
+        "invalid" => validator_dirs.len() - valid,
+    );
+}
+
+/// Import keys into the data directory from `source`, which may be a single private key file or
+/// another validator data directory.
+///
+/// Invalid keys and keys that are already present are reported and skipped.
+pub fn import(config: &ValidatorClientConfig, source: &Path, log: &slog::Logger) {
+    let keypairs: Vec<Keypair> = if source.is_dir() {
+        let source_config = ValidatorClientConfig {
+            data_dir: source.to_path_buf(),
+            ..config.clone()
+        };
+
+        match source_config.read_validator_dirs() {
+            Ok(dirs) => dirs
+                .into_iter()
+                .filter_map(|dir| match dir.key {
+                    Ok(keypair) => Some(keypair),
+                    // The directory name is only a hint; a valid key is imported regardless.
+                    Err(KeyError::MismatchedDirectory { .. }) => {
+                        read_keypair_file(&dir.key_path()).ok()
+                    }
+                    Err(e) => {
+                        warn!(
+                            log,
+                            "Skipping invalid key";
+                            "path" => dir.path.to_string_lossy().to_string(),
+                            "error" => format!("{:?}", e),
+                        );
+                        None
+                    }
+                })
+                .collect(),
+            Err(e) => {
+                error!(log, "Unable to read source directory"; "error" => format!("{:?}", e));
+                return;
+            }
+        }
+    } else {
+        match read_keypair_file(source) {
+            Ok(keypair) => vec![keypair],
+            Err(e) => {
+                error!(log, "Unable to read key file"; "error" => format!("{:?}", e));
+                return;
+            }
+        }
+    };
+
+    report_duplicates(&keypairs, log);
+
+    let mut imported: Vec<PublicKey> = vec![];
+    for keypair in keypairs {
+        if imported.contains(&keypair.pk) {
+            continue;
+        }
+
+        if save_if_absent(config, &keypair, log) {
+            imported.push(keypair.pk);
+        }
+    }
+
+    info!(log, "Imported validator keys"; "count" => imported.len());
+}
+
+/// Copy the valid keys in the data directory (or only the key named `identifier`) into the data
+/// directory at `destination`.
+pub fn export(
+    config: &ValidatorClientConfig,
+    destination: &Path,
+    identifier: Option<&str>,
+    log: &slog::Logger,
+) {
+    let keypairs: Vec<Keypair> = match config.read_validator_dirs() {
+        Ok(dirs) => dirs
+            .into_iter()
+            .filter_map(|dir| dir.key.ok())
+            .filter(|keypair| identifier.map_or(true, |id| keypair.identifier() == id))
+            .collect(),
+        Err(e) => {
+            error!(log, "Unable to read data directory"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
+
+    if keypairs.is_empty() {
+        error!(log, "No valid keys to export");
+        return;
+    }
+
+    let destination_config = ValidatorClientConfig {
+        data_dir: destination.to_path_buf(),
+        ..config.clone()
+    };
+
+    let exported = keypairs
+        .iter()
+        .filter(|keypair| save_if_absent(&destination_config, keypair, log))
+        .count();
+
+    info!(
+        log,
+        "Exported validator keys";
+        "count" => exported,
+        "destination" => destination.to_string_lossy().to_string(),
+    );
+}
+
+/// Save `keypair` into the data directory of `config`, unless a key is already stored in its
+/// directory. Returns `true` if the key was saved.
+fn save_if_absent(config: &ValidatorClientConfig, keypair: &Keypair, log: &slog::Logger) -> bool {
+    let existing_path = config.data_dir.join(keypair.identifier());
+
+    if existing_path.exists() {
+        match read_keypair_file(&existing_path.join(DEFAULT_PRIVATE_KEY_FILENAME)) {
+            Ok(ref existing) if existing.pk == keypair.pk => warn!(
+                log,
+                "Key already present";
+                "pubkey" => keypair.pk.as_hex_string(),
+            ),
+            _ => error!(
+                log,
+                "A different key is stored in this key's directory, not overwriting";
+                "path" => existing_path.to_string_lossy().to_string(),
+                "pubkey" => keypair.pk.as_hex_string(),
+            ),
+        }
+        return false;
+    }
+
+    match config.save_key(keypair) {
+        Ok(path) => {
+            info!(log, "Saved key"; "path" => path.to_string_lossy().to_string());
+            true
+        }
+        Err(e) => {
+            error!(log, "Unable to save key"; "error" => format!("{:?}", e));
+            false
+        }
+    }
+}
+
+/// Log each public key that appears more than once in `keypairs`.
+fn report_duplicates(keypairs: &[Keypair], log: &slog::Logger) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for keypair in keypairs {
+        *counts.entry(keypair.pk.as_hex_string()).or_insert(0) += 1;
+    }
+
+    for (pubkey, count) in counts.into_iter().filter(|(_, count)| *count > 1) {
+        warn!(log, "Duplicate validator key"; "pubkey" => pubkey, "count" => count);
+    }
+}
+
+/// Request the on-chain status of each keypair from the beacon node at `server`.
+fn request_statuses(server: &str, keypairs: &[Keypair]) -> Result<Vec<OnChainStatus>, String> {
+    let env = Arc::new(EnvBuilder::new().build());
+    let ch = ChannelBuilder::new(env).connect(server);
+    let client = ValidatorServiceClient::new(ch);
+
+    let mut req = Validators::new();
+    req.set_public_keys(keypairs.iter().map(|k| ssz_encode(&k.pk)).collect());
+
+    let resp = client
+        .get_validator_statuses_opt(&req, CallOption::default().timeout(BEACON_NODE_TIMEOUT))
+        .map_err(|e| format!("{:?}", e))?;
+
+    if resp.get_statuses().len() != keypairs.len() {
+        return Err("Beacon node returned the wrong number of statuses".to_string());
+    }
+
+    Ok(resp
+        .get_statuses()
+        .iter()
+        .map(|status| {
+            if status.get_known() {
+                OnChainStatus::Known {
+                    index: status.get_index(),
+                    balance: status.get_balance(),
+                    status: status.get_status().to_string(),
+                }
+            } else {
+                OnChainStatus::Unknown
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn null_logger() -> slog::Logger {
+        slog::Logger::root(slog::Discard, slog::o!())
+    }
+
+    fn config_in(dir: &Path) -> ValidatorClientConfig {
+        ValidatorClientConfig {
+            data_dir: dir.to_path_buf(),
+            ..ValidatorClientConfig::default()
+        }
+    }
+
+    /// Returns the public keys of the valid keys in the data directory of `config`.
+    fn valid_pubkeys(config: &ValidatorClientConfig) -> Vec<PublicKey> {
+        config
+            .read_validator_dirs()
+            .unwrap()
+            .into_iter()
+            .filter_map(|dir| dir.key.ok())
+            .map(|keypair| keypair.pk)
+            .collect()
+    }
+
+    fn sorted(mut pubkeys: Vec<PublicKey>) -> Vec<PublicKey> {
+        pubkeys.sort_by_key(PublicKey::as_hex_string);
+        pubkeys
+    }
+
+    #[test]
+    fn export_then_import() {
+        let log = null_logger();
+        let source = tempfile::tempdir().unwrap();
+        let exported = tempfile::tempdir().unwrap();
+        let imported = tempfile::tempdir().unwrap();
+
+        let source_config = config_in(source.path());
+        let keypairs: Vec<Keypair> = (0..3).map(|_| Keypair::random()).collect();
+        for keypair in &keypairs {
+            source_config.save_key(keypair).unwrap();
+        }
+        let pubkeys = sorted(keypairs.iter().map(|k| k.pk.clone()).collect());
+
+        export(&source_config, exported.path(), None, &log);
+        assert_eq!(sorted(valid_pubkeys(&config_in(exported.path()))), pubkeys);
+
+        let imported_config = config_in(imported.path());
+        import(&imported_config, exported.path(), &log);
+        assert_eq!(sorted(valid_pubkeys(&imported_config)), pubkeys);
+
+        // Importing again leaves the directory unchanged.
+        import(&imported_config, exported.path(), &log);
+        assert_eq!(sorted(valid_pubkeys(&imported_config)), pubkeys);
+    }
+
+    #[test]
+    fn export_single_key() {
+        let log = null_logger();
+        let source = tempfile::tempdir().unwrap();
+        let destination = tempfile::tempdir().unwrap();
+
+        let source_config = config_in(source.path());
+        let keypairs: Vec<Keypair> = (0..2).map(|_| Keypair::random()).collect();
+        for keypair in &keypairs {
+            source_config.save_key(keypair).unwrap();
+        }
+
+        let identifier = keypairs[1].identifier();
+        export(&source_config, destination.path(), Some(&identifier), &log);
+
+        assert_eq!(
+            valid_pubkeys(&config_in(destination.path())),
+            vec![keypairs[1].pk.clone()]
+        );
+    }
+
+    #[test]
+    fn import_key_file() {
+        let log = null_logger();
+        let source = tempfile::tempdir().unwrap();
+        let destination = tempfile::tempdir().unwrap();
+
+        let keypair = Keypair::random();
+        let key_path = config_in(source.path()).save_key(&keypair).unwrap();
+
+        let config = config_in(destination.path());
+        import(&config, &key_path, &log);
+
+        assert_eq!(valid_pubkeys(&config), vec![keypair.pk.clone()]);
+        assert_eq!(
+            read_keypair_file(
+                &destination
+                    .path()
+                    .join(keypair.identifier())
+                    .join(DEFAULT_PRIVATE_KEY_FILENAME)
+            )
+            .map(|key| key.pk),
+            Ok(keypair.pk)
+        );
+    }
+
+    #[test]
+    fn import_does_not_overwrite() {
+        let log = null_logger();
+        let source = tempfile::tempdir().unwrap();
+        let destination = tempfile::tempdir().unwrap();
+
+        let keypair = Keypair::random();
+        let key_path = config_in(source.path()).save_key(&keypair).unwrap();
+
+        // Store a different key in the directory named after `keypair`.
+        let config = config_in(destination.path());
+        let other_path = config.save_key(&Keypair::random()).unwrap();
+        fs::rename(
+            other_path.parent().unwrap(),
+            destination.path().join(keypair.identifier()),
+        )
+        .unwrap();
+
+        import(&config, &key_path, &log);
+
+        assert!(valid_pubkeys(&config).is_empty());
+    }
+}
@@ -1,9 +1,15 @@
-use bls::Keypair;
+mod deposit;
+mod exit;
+mod keys;
+mod mnemonic;
+
+use bls::{Keypair, PublicKey};
 use clap::{App, Arg, SubCommand};
-use eth2_config::get_data_dir;
-use slog::{crit, debug, info, o, Drain};
+use eth2_config::{get_data_dir, Eth2Config};
+use slog::{crit, debug, info, o, warn, Drain};
 use std::path::PathBuf;
 use types::test_utils::generate_deterministic_keypair;
+use types::{MainnetEthSpec, MinimalEthSpec};
 use validator_client::Config as ValidatorClientConfig;
 
 pub const DEFAULT_DATA_DIR: &str = ".lighthouse-account-manager";
@@ -59,6 +65,219 @@ fn main() {
                         .default_value("1"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("new_mnemonic")
+                .about("Generates a new BIP-39 mnemonic and derives validator keys from it")
+                .version("0.0.1")
+                .author("Sigma Prime <contact@sigmaprime.io>")
+                .arg(
+                    Arg::with_name("validator count")
+                        .long("validator_count")
+                        .short("n")
+                        .value_name("validator_count")
+                        .help("The number of validator keys to derive, starting at account 0.")
+                        .takes_value(true)
+                        .default_value("1"),
+                )
+                .arg(
+                    Arg::with_name("password")
+                        .long("password")
+                        .value_name("PASSWORD")
+                        .help("An optional BIP-39 password, required again for recovery.")
+                        .takes_value(true)
+                        .default_value(""),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("recover")
+                .about("Recovers validator keys from a BIP-39 mnemonic")
+                .version("0.0.1")
+                .author("Sigma Prime <contact@sigmaprime.io>")
+                .arg(
+                    Arg::with_name("mnemonic")
+                        .long("mnemonic")
+                        .short("m")
+                        .value_name("PHRASE")
+                        .help("The mnemonic phrase, quoted.")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("first index")
+                        .long("first_index")
+                        .short("i")
+                        .value_name("index")
+                        .help("The first account to derive a validator key for.")
+                        .takes_value(true)
+                        .default_value("0"),
+                )
+                .arg(
+                    Arg::with_name("validator count")
+                        .long("validator_count")
+                        .short("n")
+                        .value_name("validator_count")
+                        .help("The number of validator keys to derive.")
+                        .takes_value(true)
+                        .default_value("1"),
+                )
+                .arg(
+                    Arg::with_name("password")
+                        .long("password")
+                        .value_name("PASSWORD")
+                        .help("The BIP-39 password supplied when the mnemonic was generated.")
+                        .takes_value(true)
+                        .default_value(""),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("Lists the validator keys in the data directory")
+                .version("0.0.1")
+                .author("Sigma Prime <contact@sigmaprime.io>")
+                .arg(
+                    Arg::with_name("server")
+                        .long("server")
+                        .value_name("server")
+                        .help("Address of a BeaconNode, used to show each validator's on-chain status.")
+                        .takes_value(true)
+                        .default_value("localhost:5051"),
+                )
+                .arg(
+                    Arg::with_name("offline")
+                        .long("offline")
+                        .help("Do not contact a BeaconNode.")
+                        .takes_value(false),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Imports validator keys from a private key file or another data directory")
+                .version("0.0.1")
+                .author("Sigma Prime <contact@sigmaprime.io>")
+                .arg(
+                    Arg::with_name("source")
+                        .long("source")
+                        .value_name("PATH")
+                        .help("A private key file, or a validator data directory.")
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Copies validator keys into another data directory")
+                .version("0.0.1")
+                .author("Sigma Prime <contact@sigmaprime.io>")
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .value_name("DIR")
+                        .help("The data directory to copy keys into.")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("validator")
+                        .long("validator")
+                        .short("v")
+                        .value_name("IDENTIFIER")
+                        .help("Only export the key with this identifier.")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("exit")
+                .about("Signs a voluntary exit for a validator and submits it to a beacon node")
+                .version("0.0.1")
+                .author("Sigma Prime <contact@sigmaprime.io>")
+                .arg(
+                    Arg::with_name("validator")
+                        .long("validator")
+                        .short("v")
+                        .value_name("IDENTIFIER")
+                        .help("The identifier of the validator to exit (the name of its key directory).")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("server")
+                        .long("server")
+                        .value_name("server")
+                        .help("Address to connect to BeaconNode.")
+                        .takes_value(true)
+                        .default_value("localhost:5051"),
+                )
+                .arg(
+                    Arg::with_name("spec-constants")
+                        .long("spec-constants")
+                        .short("s")
+                        .value_name("TITLE")
+                        .help("The specification constants the beacon node is running with.")
+                        .takes_value(true)
+                        .possible_values(&["mainnet", "minimal"])
+                        .default_value("minimal"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("deposit")
+                .about("Produces the signed DepositData required to fund a validator")
+                .version("0.0.1")
+                .author("Sigma Prime <contact@sigmaprime.io>")
+                .arg(
+                    Arg::with_name("validator")
+                        .long("validator")
+                        .short("v")
+                        .value_name("IDENTIFIER")
+                        .help("The identifier of the validator to deposit for (the name of its key directory).")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("withdrawal-pubkey")
+                        .long("withdrawal-pubkey")
+                        .short("w")
+                        .value_name("PUBKEY")
+                        .help("The 0x-prefixed, hex-encoded BLS public key which may withdraw the deposit.")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .long("amount")
+                        .short("a")
+                        .value_name("GWEI")
+                        .help("The deposit amount in Gwei. Defaults to the spec's max effective balance.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .short("f")
+                        .value_name("FORMAT")
+                        .help("The encoding of the deposit data.")
+                        .takes_value(true)
+                        .possible_values(&["json", "ssz"])
+                        .default_value("json"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .short("o")
+                        .value_name("FILE")
+                        .help("File to write the deposit data to. Defaults to stdout.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("spec-constants")
+                        .long("spec-constants")
+                        .short("s")
+                        .value_name("TITLE")
+                        .help("The specification constants to sign the deposit with.")
+                        .takes_value(true)
+                        .possible_values(&["mainnet", "minimal"])
+                        .default_value("minimal"),
+                ),
+        )
         .get_matches();
 
     let data_dir = match get_data_dir(&matches, PathBuf::from(DEFAULT_DATA_DIR)) {
@@ -107,6 +326,31 @@ fn main() {
                 }
             }
         }
+        ("new_mnemonic", Some(m)) => new_mnemonic(m, &client_config, &log),
+        ("recover", Some(m)) => recover(m, &client_config, &log),
+        ("list", Some(m)) => {
+            let server = if m.is_present("offline") {
+                None
+            } else {
+                m.value_of("server")
+            };
+            keys::list(&client_config, server, &log)
+        }
+        ("import", Some(m)) => {
+            let source = m.value_of("source").expect("Source is required");
+            keys::import(&client_config, &PathBuf::from(source), &log)
+        }
+        ("export", Some(m)) => {
+            let destination = m.value_of("destination").expect("Destination is required");
+            keys::export(
+                &client_config,
+                &PathBuf::from(destination),
+                m.value_of("validator"),
+                &log,
+            )
+        }
+        ("exit", Some(m)) => exit_validator(m, &client_config, &log),
+        ("deposit", Some(m)) => deposit(m, &client_config, &log),
         _ => panic!(
             "The account manager must be run with a subcommand. See help for more information."
         ),
@@ -139,6 +383,191 @@ fn generate_deterministic(
     )
 }
 
+fn new_mnemonic(matches: &clap::ArgMatches, config: &ValidatorClientConfig, log: &slog::Logger) {
+    let count: u32 = matches
+        .value_of("validator count")
+        .expect("Validator count has a default")
+        .parse()
+        .expect("Invalid validator count");
+    let password = matches.value_of("password").unwrap_or("");
+
+    let mnemonic = eth2_key_derivation::generate_mnemonic();
+
+    warn!(
+        log,
+        "Write down the following mnemonic and store it securely. It is the only way to recover \
+         the generated keys."
+    );
+    println!("{}", mnemonic.phrase());
+
+    save_derived_keys(&mnemonic, password, 0, count, config, log)
+}
+
+fn recover(matches: &clap::ArgMatches, config: &ValidatorClientConfig, log: &slog::Logger) {
+    let phrase = matches.value_of("mnemonic").expect("Mnemonic is required");
+    let first_index: u32 = matches
+        .value_of("first index")
+        .expect("First index has a default")
+        .parse()
+        .expect("Invalid first index");
+    let count: u32 = matches
+        .value_of("validator count")
+        .expect("Validator count has a default")
+        .parse()
+        .expect("Invalid validator count");
+    let password = matches.value_of("password").unwrap_or("");
+
+    let mnemonic = match eth2_key_derivation::mnemonic_from_phrase(phrase) {
+        Ok(mnemonic) => mnemonic,
+        Err(e) => {
+            crit!(log, "Invalid mnemonic"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
+
+    save_derived_keys(&mnemonic, password, first_index, count, config, log)
+}
+
+fn save_derived_keys(
+    mnemonic: &eth2_key_derivation::Mnemonic,
+    password: &str,
+    first_index: u32,
+    count: u32,
+    config: &ValidatorClientConfig,
+    log: &slog::Logger,
+) {
+    match mnemonic::derive_signing_keypairs(mnemonic, password, first_index, count) {
+        Ok(keypairs) => keypairs
+            .iter()
+            .for_each(|keypair| save_key(keypair, config, log)),
+        Err(e) => crit!(log, "Failed to derive keys"; "error" => e),
+    }
+}
+
+fn exit_validator(matches: &clap::ArgMatches, config: &ValidatorClientConfig, log: &slog::Logger) {
+    let identifier = matches
+        .value_of("validator")
+        .expect("Validator identifier is required");
+    let server = matches.value_of("server").expect("Server has a default");
+    let eth2_config = eth2_config_from_args(matches);
+
+    let keypair = match fetch_keypair(identifier, config, log) {
+        Some(keypair) => keypair,
+        None => {
+            crit!(log, "No key found for validator"; "validator" => identifier);
+            return;
+        }
+    };
+
+    let result = match eth2_config.spec_constants.as_str() {
+        "mainnet" => exit::exit_validator::<MainnetEthSpec>(&keypair, server, &eth2_config, log),
+        _ => exit::exit_validator::<MinimalEthSpec>(&keypair, server, &eth2_config, log),
+    };
+
+    match result {
+        Ok(()) => info!(log, "Voluntary exit published"; "validator" => identifier),
+        Err(e) => crit!(log, "Voluntary exit failed"; "validator" => identifier, "error" => e),
+    }
+}
+
+fn deposit(matches: &clap::ArgMatches, config: &ValidatorClientConfig, log: &slog::Logger) {
+    let identifier = matches
+        .value_of("validator")
+        .expect("Validator identifier is required");
+    let eth2_config = eth2_config_from_args(matches);
+
+    let withdrawal_pubkey = matches
+        .value_of("withdrawal-pubkey")
+        .expect("Withdrawal pubkey is required");
+    let withdrawal_pubkey = match parse_pubkey(withdrawal_pubkey) {
+        Ok(pubkey) => pubkey,
+        Err(e) => {
+            crit!(log, "Invalid withdrawal pubkey"; "error" => e);
+            return;
+        }
+    };
+
+    let amount = match matches.value_of("amount") {
+        Some(amount) => match amount.parse::<u64>() {
+            Ok(amount) => amount,
+            Err(_) => {
+                crit!(log, "Invalid deposit amount"; "amount" => amount);
+                return;
+            }
+        },
+        None => eth2_config.spec.max_effective_balance,
+    };
+    let format = match matches.value_of("format") {
+        Some("ssz") => deposit::OutputFormat::Ssz,
+        _ => deposit::OutputFormat::Json,
+    };
+    let output = matches.value_of("output").map(PathBuf::from);
+
+    let keypair = match fetch_keypair(identifier, config, log) {
+        Some(keypair) => keypair,
+        None => {
+            crit!(log, "No key found for validator"; "validator" => identifier);
+            return;
+        }
+    };
+
+    let deposit_data = match eth2_config.spec_constants.as_str() {
+        "mainnet" => deposit::build_deposit_data::<MainnetEthSpec>(
+            &keypair,
+            &withdrawal_pubkey,
+            amount,
+            &eth2_config.spec,
+        ),
+        _ => deposit::build_deposit_data::<MinimalEthSpec>(
+            &keypair,
+            &withdrawal_pubkey,
+            amount,
+            &eth2_config.spec,
+        ),
+    };
+
+    match deposit::write_deposit_data(&deposit_data, format, output) {
+        Ok(()) => info!(
+            log,
+            "Deposit data generated";
+            "validator" => identifier,
+            "amount" => amount,
+            "deposit_data_root" => format!("{:?}", deposit::deposit_data_root(&deposit_data)),
+        ),
+        Err(e) => crit!(log, "Failed to write deposit data"; "error" => e),
+    }
+}
+
+/// Parse a 0x-prefixed, hex-encoded, compressed BLS public key.
+fn parse_pubkey(string: &str) -> Result<PublicKey, String> {
+    if !string.starts_with("0x") {
+        return Err("Public key must be 0x-prefixed".to_string());
+    }
+    let bytes = hex::decode(&string[2..]).map_err(|e| format!("Invalid hex: {:?}", e))?;
+
+    PublicKey::from_bytes(&bytes).map_err(|e| format!("Invalid public key: {:?}", e))
+}
+
+/// Returns the `Eth2Config` named by the `spec-constants` argument.
+fn eth2_config_from_args(matches: &clap::ArgMatches) -> Eth2Config {
+    match matches.value_of("spec-constants") {
+        Some("mainnet") => Eth2Config::mainnet(),
+        _ => Eth2Config::minimal(),
+    }
+}
+
+/// Load the keypair stored in the key directory named `identifier`.
+fn fetch_keypair(
+    identifier: &str,
+    config: &ValidatorClientConfig,
+    log: &slog::Logger,
+) -> Option<Keypair> {
+    config
+        .fetch_keys(log)?
+        .into_iter()
+        .find(|keypair| keypair.identifier() == identifier)
+}
+
 fn save_key(keypair: &Keypair, config: &ValidatorClientConfig, log: &slog::Logger) {
     let key_path: PathBuf = config
         .save_key(&keypair)
@@ -0,0 +1,26 @@
+use bls::Keypair;
+use eth2_key_derivation::{seed_from_mnemonic, DerivedKey, KeyPath, KeyType, Mnemonic};
+
+/// Derive the EIP-2334 signing keypairs for accounts `first_index..first_index + count` from
+/// `mnemonic`.
+pub fn derive_signing_keypairs(
+    mnemonic: &Mnemonic,
+    password: &str,
+    first_index: u32,
+    count: u32,
+) -> Result<Vec<Keypair>, String> {
+    let end_index = first_index
+        .checked_add(count)
+        .ok_or_else(|| format!("Account {} + {} is out of range", first_index, count))?;
+
+    let seed = seed_from_mnemonic(mnemonic, password);
+
+    (first_index..end_index)
+        .map(|account| {
+            let path = KeyPath::validator(account, KeyType::Signing);
+            DerivedKey::from_path(seed.as_bytes(), &path)
+                .and_then(|key| key.keypair())
+                .map_err(|e| format!("Unable to derive key at {}: {:?}", path, e))
+        })
+        .collect()
+}
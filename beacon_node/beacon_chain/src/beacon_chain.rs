@@ -3,25 +3,37 @@ use crate::errors::{BeaconChainError as Error, BlockProductionError};
 use crate::iter::{BlockIterator, BlockRootsIterator};
 use crate::metrics::Metrics;
 use crate::persisted_beacon_chain::{PersistedBeaconChain, BEACON_CHAIN_DB_KEY};
+use crate::rewards_report::RewardsReport;
+use crate::state_cache::StateCache;
+use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use fork_choice::{ForkChoice, ForkChoiceError};
 use log::{debug, trace};
 use operation_pool::DepositInsertStatus;
 use operation_pool::OperationPool;
-use parking_lot::{RwLock, RwLockReadGuard};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use slot_clock::SlotClock;
+use state_processing::common::get_attesting_indices_unsorted;
 use state_processing::per_block_processing::errors::{
     AttestationValidationError, AttesterSlashingValidationError, DepositValidationError,
     ExitValidationError, ProposerSlashingValidationError, TransferValidationError,
 };
 use state_processing::{
-    per_block_processing, per_block_processing_without_verifying_block_signature,
-    per_slot_processing, BlockProcessingError,
+    per_block_processing_with_pubkey_cache, per_block_processing_without_verifying_block_signature,
+    per_slot_processing, per_slot_processing_with_rewards_report, BlockProcessingError,
 };
+use std::collections::HashSet;
 use std::sync::Arc;
 use store::{Error as DBError, Store};
 use tree_hash::TreeHash;
 use types::*;
 
+/// The number of recent post-block states held in memory, see `BeaconChain::get_state`.
+pub const STATE_CACHE_SIZE: usize = 8;
+
+/// The number of states advanced through skip slots held in memory, see
+/// `BeaconChain::skip_slot_cache`.
+pub const SKIP_SLOT_CACHE_SIZE: usize = 4;
+
 #[derive(Debug, PartialEq)]
 pub enum BlockProcessingOutcome {
     /// Block was valid and imported into the block graph.
@@ -48,7 +60,7 @@ pub enum BlockProcessingOutcome {
 pub trait BeaconChainTypes {
     type Store: store::Store;
     type SlotClock: slot_clock::SlotClock;
-    type ForkChoice: fork_choice::ForkChoice<Self::Store>;
+    type ForkChoice: fork_choice::ForkChoice<Self::Store, Self::EthSpec>;
     type EthSpec: types::EthSpec;
 }
 
@@ -74,6 +86,24 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     /// A state-machine that is updated with information from the network and chooses a canonical
     /// head block.
     pub fork_choice: RwLock<T::ForkChoice>,
+    /// The public key of every validator known to the chain, shared by all states and used when
+    /// verifying block signatures.
+    validator_pubkey_cache: RwLock<ValidatorPubkeyCache>,
+    /// Recently imported states, with their caches built. Avoids reading (and rebuilding the caches
+    /// of) the parent state from the database when importing a block.
+    state_cache: Mutex<StateCache<Hash256, T::EthSpec>>,
+    /// States which have been advanced from the post-state of some block (identified by its root)
+    /// through skip slots to the given slot. Ensures that the skip slots (possibly including an
+    /// epoch transition) are processed once, regardless of how many blocks or requests build upon
+    /// them.
+    skip_slot_cache: Mutex<StateCache<(Hash256, Slot), T::EthSpec>>,
+    /// If true, a `RewardsReport` is stored for each epoch transition. Disabled by default.
+    pub rewards_reports: bool,
+    /// The epoch and key of each `RewardsReport` stored by this instance which has not yet been
+    /// pruned.
+    ///
+    /// Not persisted; reports stored prior to a restart are never pruned.
+    stored_rewards_reports: Mutex<Vec<(Epoch, Hash256)>>,
     /// Stores metrics about this `BeaconChain`.
     pub metrics: Metrics,
 }
@@ -88,7 +118,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         spec: ChainSpec,
         fork_choice: T::ForkChoice,
     ) -> Result<Self, Error> {
-        let state_root = genesis_state.canonical_root();
+        // Build the tree hash cache before the state is cloned into the canonical head, so that
+        // future state roots descending from genesis are computed incrementally.
+        let state_root = genesis_state.update_tree_hash_cache()?;
         store.put(&state_root, &genesis_state)?;
 
         let genesis_block_root = genesis_block.block_header().canonical_root();
@@ -107,6 +139,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         genesis_state.build_all_caches(&spec)?;
 
+        let validator_pubkey_cache = RwLock::new(ValidatorPubkeyCache::new(&genesis_state)?);
+
+        let mut state_cache = StateCache::new(STATE_CACHE_SIZE);
+        state_cache.insert(state_root, genesis_state.clone());
+
         Ok(Self {
             spec,
             store,
@@ -116,6 +153,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             canonical_head,
             genesis_block_root,
             fork_choice: RwLock::new(fork_choice),
+            validator_pubkey_cache,
+            state_cache: Mutex::new(state_cache),
+            skip_slot_cache: Mutex::new(StateCache::new(SKIP_SLOT_CACHE_SIZE)),
+            rewards_reports: false,
+            stored_rewards_reports: Mutex::new(vec![]),
             metrics: Metrics::new()?,
         })
     }
@@ -126,12 +168,22 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         spec: ChainSpec,
     ) -> Result<Option<BeaconChain<T>>, Error> {
         let key = Hash256::from_slice(&BEACON_CHAIN_DB_KEY.as_bytes());
-        let p: PersistedBeaconChain<T> = match store.get(&key) {
+        let mut p: PersistedBeaconChain<T> = match store.get(&key) {
             Err(e) => return Err(e.into()),
             Ok(None) => return Ok(None),
             Ok(Some(p)) => p,
         };
 
+        // Caches are not persisted. Rebuild the caches of the head state so that the first block
+        // imported atop it does not rebuild them (e.g., hash the entire state).
+        p.canonical_head.beacon_state.build_all_caches(&spec)?;
+
+        let mut state_cache = StateCache::new(STATE_CACHE_SIZE);
+        state_cache.insert(
+            p.canonical_head.beacon_state_root,
+            p.canonical_head.beacon_state.clone(),
+        );
+
         let slot_clock = T::SlotClock::new(
             spec.genesis_slot,
             p.state.genesis_time,
@@ -140,6 +192,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let fork_choice = T::ForkChoice::new(store.clone());
 
+        // The pubkey cache is not persisted, it is rebuilt from the registry of the head state.
+        let validator_pubkey_cache = RwLock::new(ValidatorPubkeyCache::new(&p.state)?);
+
         Ok(Some(BeaconChain {
             spec,
             store,
@@ -149,6 +204,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             state: RwLock::new(p.state),
             fork_choice: RwLock::new(fork_choice),
             genesis_block_root: p.genesis_block_root,
+            validator_pubkey_cache,
+            state_cache: Mutex::new(state_cache),
+            skip_slot_cache: Mutex::new(StateCache::new(SKIP_SLOT_CACHE_SIZE)),
+            rewards_reports: false,
+            stored_rewards_reports: Mutex::new(vec![]),
             metrics: Metrics::new()?,
         }))
     }
@@ -225,15 +285,37 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(self.store.get(block_root)?)
     }
 
+    /// Returns the state with the given root, reading from the in-memory state cache if possible
+    /// and otherwise from `self.store`.
+    ///
+    /// States from the cache have their caches built, states from the database do not. States read
+    /// from the database are not added to the cache.
+    pub fn get_state(
+        &self,
+        state_root: &Hash256,
+    ) -> Result<Option<BeaconState<T::EthSpec>>, Error> {
+        if let Some(state) = self.state_cache.lock().get_cloned(state_root) {
+            return Ok(Some(state));
+        }
+
+        Ok(self.store.get(state_root)?)
+    }
+
     /// Update the canonical head to `new_head`.
     fn update_canonical_head(&self, new_head: CheckPoint<T::EthSpec>) -> Result<(), Error> {
+        let old_finalized_epoch = self.head().beacon_state.finalized_epoch;
+        let new_finalized_epoch = new_head.beacon_state.finalized_epoch;
+
         // Update the checkpoint that stores the head of the chain at the time it received the
         // block.
         *self.canonical_head.write() = new_head;
 
         // Update the always-at-the-present-slot state we keep around for performance gains.
         *self.state.write() = {
-            let mut state = self.canonical_head.read().beacon_state.clone();
+            let (mut state, head_block_root) = {
+                let head = self.canonical_head.read();
+                (head.beacon_state.clone(), head.beacon_block_root)
+            };
 
             let present_slot = match self.slot_clock.present_slot() {
                 Ok(Some(slot)) => slot,
@@ -241,15 +323,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             };
 
             // If required, transition the new state to the present slot.
-            for _ in state.slot.as_u64()..present_slot.as_u64() {
-                per_slot_processing(&mut state, &self.spec)?;
-            }
+            self.advance_through_skip_slots(&mut state, head_block_root, present_slot)?;
 
             state.build_all_caches(&self.spec)?;
 
             state
         };
 
+        if new_finalized_epoch > old_finalized_epoch {
+            self.prune_rewards_reports(new_finalized_epoch)?;
+        }
+
         // Save `self` to `self.store`.
         self.persist()?;
 
@@ -289,11 +373,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let mut state = self.state.write();
 
         // If required, transition the new state to the present slot.
-        for _ in state.slot.as_u64()..present_slot.as_u64() {
-            // Ensure the next epoch state caches are built in case of an epoch transition.
-            state.build_committee_cache(RelativeEpoch::Next, spec)?;
-
-            per_slot_processing(&mut *state, spec)?;
+        if state.slot < present_slot {
+            let head_block_root = latest_block_root(&mut *state, spec)?;
+            self.advance_through_skip_slots(&mut *state, head_block_root, present_slot)?;
         }
 
         state.build_all_caches(spec)?;
@@ -301,6 +383,111 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Advances `self.state` to the present slot, if the slot clock is ahead of it.
+    ///
+    /// Allows a block or attestation to be produced before `self.catchup_state` has been called
+    /// for the present slot (e.g., by a proposer following some skip slots).
+    fn catchup_state_if_behind(&self) -> Result<(), Error> {
+        match self.read_slot_clock() {
+            Some(present_slot) if present_slot > self.state.read().slot => self.catchup_state(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Transitions `state` to `slot` with skip slots (i.e., `per_slot_processing`), using
+    /// `self.skip_slot_cache` to avoid repeating a transition that has already been performed.
+    ///
+    /// `state` must be the post-state of the block with root `block_root`, possibly already
+    /// advanced through some skip slots. Does nothing if `state` is not prior to `slot`.
+    fn advance_through_skip_slots(
+        &self,
+        state: &mut BeaconState<T::EthSpec>,
+        block_root: Hash256,
+        slot: Slot,
+    ) -> Result<(), Error> {
+        if state.slot >= slot {
+            return Ok(());
+        }
+
+        let key = (block_root, slot);
+
+        let cached = self.skip_slot_cache.lock().get_cloned(&key);
+        if let Some(cached) = cached {
+            *state = cached;
+            return Ok(());
+        }
+
+        for _ in state.slot.as_u64()..slot.as_u64() {
+            // Ensure the next epoch state caches are built in case of an epoch transition.
+            state.build_committee_cache(RelativeEpoch::Next, &self.spec)?;
+
+            if !self.rewards_reports {
+                per_slot_processing(state, &self.spec)?;
+            } else if let Some(validators) =
+                per_slot_processing_with_rewards_report(state, &self.spec)?
+            {
+                // Store the report against the root of the state which was processed, so that it
+                // may be found via the `latest_state_roots` of any descendant.
+                let processed_slot = state.slot - 1;
+                let epoch = processed_slot.epoch(T::EthSpec::slots_per_epoch());
+                let state_root = *state.get_state_root(processed_slot)?;
+
+                self.store
+                    .put(&state_root, &RewardsReport { epoch, validators })?;
+                self.stored_rewards_reports.lock().push((epoch, state_root));
+            }
+        }
+
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+
+        self.skip_slot_cache.lock().insert(key, state.clone());
+
+        Ok(())
+    }
+
+    /// Returns the rewards and penalties applied to each validator during the canonical transition
+    /// out of `epoch`.
+    ///
+    /// Returns `None` if the transition has not yet occurred, is too old to be found in the
+    /// `latest_state_roots` of the present state, was not processed by this node or occurred whilst
+    /// `self.rewards_reports` was disabled.
+    pub fn get_rewards_report(&self, epoch: Epoch) -> Result<Option<RewardsReport>, Error> {
+        let slot = epoch.end_slot(T::EthSpec::slots_per_epoch());
+
+        let state_root = match self.state.read().get_state_root(slot) {
+            Ok(root) => *root,
+            Err(BeaconStateError::SlotOutOfBounds) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(self.store.get(&state_root)?)
+    }
+
+    /// Deletes each stored `RewardsReport` prior to `finalized_epoch` which can no longer be
+    /// returned by `Self::get_rewards_report`, either because it is from a fork which has been
+    /// abandoned by finalization or because it is too old to be found in the `latest_state_roots`
+    /// of the present state.
+    fn prune_rewards_reports(&self, finalized_epoch: Epoch) -> Result<(), Error> {
+        let state = self.state.read();
+        let mut stored = self.stored_rewards_reports.lock();
+
+        let (pruned, retained): (Vec<_>, Vec<_>) =
+            stored.drain(..).partition(|(epoch, state_root)| {
+                *epoch < finalized_epoch
+                    && state
+                        .get_state_root(epoch.end_slot(T::EthSpec::slots_per_epoch()))
+                        .ok()
+                        != Some(state_root)
+            });
+        *stored = retained;
+
+        for (_, state_root) in pruned {
+            self.store.delete::<RewardsReport>(&state_root)?;
+        }
+
+        Ok(())
+    }
+
     /// Build all of the caches on the current state.
     ///
     /// Ideally this shouldn't be required, however we leave it here for testing.
@@ -312,20 +499,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
     /// Returns the validator index (if any) for the given public key.
     ///
-    /// Information is retrieved from the present `beacon_state.validator_registry`.
+    /// Information is retrieved from the pubkey cache of the present `beacon_state`, which is kept
+    /// up-to-date whenever the head is updated.
     pub fn validator_index(&self, pubkey: &PublicKey) -> Option<usize> {
-        for (i, validator) in self
-            .head()
-            .beacon_state
-            .validator_registry
-            .iter()
-            .enumerate()
-        {
-            if validator.pubkey == *pubkey {
-                return Some(i);
-            }
-        }
-        None
+        self.state.read().get_validator_index(pubkey).ok()?
+    }
+
+    /// Returns a read-lock guarded cache of the public key of every validator known to the chain.
+    pub fn validator_pubkey_cache(&self) -> RwLockReadGuard<ValidatorPubkeyCache> {
+        self.validator_pubkey_cache.read()
     }
 
     /// Reads the slot clock, returns `None` if the slot is unavailable.
@@ -371,9 +553,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// Information is read from the present `beacon_state` shuffling, so only information from the
     /// present and prior epoch is available.
     pub fn block_proposer(&self, slot: Slot) -> Result<usize, BeaconStateError> {
-        self.state
-            .write()
-            .build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+        self.ensure_current_committee_cache()?;
 
         let index = self.state.read().get_beacon_proposer_index(
             slot,
@@ -396,6 +576,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             "BeaconChain::validator_attestion_slot_and_shard: validator_index: {}",
             validator_index
         );
+        self.ensure_current_committee_cache()?;
+
         if let Some(attestation_duty) = self
             .state
             .read()
@@ -407,6 +589,92 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Builds the current epoch committee cache of `self.state`, if it is not already built.
+    ///
+    /// The cache is built whenever the head is updated, so a write-lock is rarely required.
+    fn ensure_current_committee_cache(&self) -> Result<(), BeaconStateError> {
+        if !self
+            .state
+            .read()
+            .committee_cache_is_initialized(RelativeEpoch::Current)
+        {
+            self.state
+                .write()
+                .build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` for each of `validator_indices` that is known to have attested or produced a
+    /// block during `epoch`.
+    ///
+    /// Activity is determined from the attestations included in recent blocks, the attestations
+    /// waiting in the `op_pool` and the proposers of recent blocks. Only the previous and current
+    /// epochs of the present state are observable, other epochs will report no activity.
+    pub fn validator_liveness(
+        &self,
+        epoch: Epoch,
+        validator_indices: &[usize],
+    ) -> Result<Vec<bool>, Error> {
+        let state = self.state.read();
+
+        let relative_epoch = match RelativeEpoch::from_epoch(state.current_epoch(), epoch) {
+            Ok(RelativeEpoch::Next) | Err(_) => return Ok(vec![false; validator_indices.len()]),
+            Ok(relative_epoch) => relative_epoch,
+        };
+
+        let mut live_validators = HashSet::new();
+
+        // Attestations that have been included in a block.
+        for attestation in state.get_matching_source_attestations(epoch)? {
+            live_validators.extend(get_attesting_indices_unsorted(
+                &*state,
+                &attestation.data,
+                &attestation.aggregation_bitfield,
+            )?);
+        }
+
+        // Attestations that are yet to be included in a block. These have only been checked for
+        // time-independent validity, so any that do not fit the current committees are ignored.
+        for attestation in self.op_pool.get_attestations_for_epoch(epoch) {
+            if let Ok(indices) = get_attesting_indices_unsorted(
+                &*state,
+                &attestation.data,
+                &attestation.aggregation_bitfield,
+            ) {
+                live_validators.extend(indices);
+            }
+        }
+
+        // The proposers of any blocks in the epoch.
+        for slot in epoch.slot_iter(T::EthSpec::slots_per_epoch()) {
+            // The genesis block does not have a proposer.
+            let has_block = if slot > state.slot || slot == self.spec.genesis_slot {
+                false
+            } else if slot == state.latest_block_header.slot {
+                true
+            } else if slot == state.slot {
+                false
+            } else {
+                state.get_block_root(slot)? != state.get_block_root(slot - 1)?
+            };
+
+            if has_block {
+                live_validators.insert(state.get_beacon_proposer_index(
+                    slot,
+                    relative_epoch,
+                    &self.spec,
+                )?);
+            }
+        }
+
+        Ok(validator_indices
+            .iter()
+            .map(|i| live_validators.contains(i))
+            .collect())
+    }
+
     /// Produce an `AttestationData` that is valid for the present `slot` and given `shard`.
     pub fn produce_attestation_data(&self, shard: u64) -> Result<AttestationData, Error> {
         let slots_per_epoch = T::EthSpec::slots_per_epoch();
@@ -414,6 +682,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.metrics.attestation_production_requests.inc();
         let timer = self.metrics.attestation_production_times.start_timer();
 
+        self.catchup_state_if_behind()?;
+
         let state = self.state.read();
 
         let current_epoch_start_slot = self
@@ -573,28 +843,66 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         };
 
-        // Load the parent blocks state from the database, returning an error if it is not found.
-        // It is an error because if know the parent block we should also know the parent state.
-        let parent_state_root = parent_block.state_root;
-        let parent_state = self
-            .store
-            .get(&parent_state_root)?
-            .ok_or_else(|| Error::DBInconsistent(format!("Missing state {}", parent_state_root)))?;
-
-        // TODO: check the block proposer signature BEFORE doing a state transition. This will
-        // significantly lower exposure surface to DoS attacks.
-
-        // Transition the parent state to the block slot.
-        let mut state: BeaconState<T::EthSpec> = parent_state;
-        for _ in state.slot.as_u64()..block.slot.as_u64() {
-            per_slot_processing(&mut state, &self.spec)?;
-        }
+        // Load the parent blocks state, returning an error if it is not found. It is an error
+        // because if know the parent block we should also know the parent state.
+        //
+        // Recent states are held in memory. Unlike a state read from the database, these retain
+        // their tree hash cache, so the state roots computed during the state transition are
+        // updated incrementally rather than computed from scratch.
+        //
+        // If another block with the same parent and slot has been processed, the parent state has
+        // already been transitioned to this slot and need not be loaded.
+        let skip_slot_state = self
+            .skip_slot_cache
+            .lock()
+            .get_cloned(&(parent_block_root, block.slot));
+        let mut state: BeaconState<T::EthSpec> = match skip_slot_state {
+            Some(state) => state,
+            None => {
+                let parent_state_root = parent_block.state_root;
+                let mut state = self.get_state(&parent_state_root)?.ok_or_else(|| {
+                    Error::DBInconsistent(format!("Missing state {}", parent_state_root))
+                })?;
+
+                // TODO: check the block proposer signature BEFORE doing a state transition. This
+                // will significantly lower exposure surface to DoS attacks.
+
+                // Transition the parent state to the block slot.
+                self.advance_through_skip_slots(&mut state, parent_block_root, block.slot)?;
+
+                state
+            }
+        };
 
         state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
 
+        // Ensure every validator in the parent state is in the pubkey cache. This is usually a
+        // no-op, as the validators of each imported state are added below.
+        self.validator_pubkey_cache
+            .write()
+            .import_new_pubkeys(&state)?;
+
         // Apply the received block to its parent state (which has been transitioned into this
         // slot).
-        match per_block_processing(&mut state, &block, &self.spec) {
+        let result = {
+            let validator_count = state.validator_registry.len();
+            let pubkey_cache = self.validator_pubkey_cache.read();
+
+            per_block_processing_with_pubkey_cache(
+                &mut state,
+                &block,
+                |i| {
+                    if i < validator_count {
+                        pubkey_cache.get(i)
+                    } else {
+                        None
+                    }
+                },
+                &self.spec,
+            )
+        };
+
+        match result {
             Err(BlockProcessingError::BeaconStateError(e)) => {
                 return Err(Error::BeaconStateError(e))
             }
@@ -602,7 +910,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             _ => {}
         }
 
-        let state_root = state.canonical_root();
+        // Add any validators created by deposits in the block.
+        self.validator_pubkey_cache
+            .write()
+            .import_new_pubkeys(&state)?;
+
+        let state_root = state.update_tree_hash_cache()?;
 
         if block.state_root != state_root {
             return Ok(BlockProcessingOutcome::StateRootMismatch);
@@ -611,6 +924,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Store the block and state.
         self.store.put(&block_root, &block)?;
         self.store.put(&state_root, &state)?;
+        self.state_cache.lock().insert(state_root, state);
 
         // Register the new block with the fork choice service.
         self.fork_choice
@@ -627,6 +941,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.metrics
             .operations_per_block_attestation
             .observe(block.body.attestations.len() as f64);
+
         timer.observe_duration();
 
         Ok(BlockProcessingOutcome::Processed)
@@ -640,6 +955,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         &self,
         randao_reveal: Signature,
     ) -> Result<(BeaconBlock, BeaconState<T::EthSpec>), BlockProductionError> {
+        self.catchup_state_if_behind()?;
+
         debug!("Producing block at slot {}...", self.state.read().slot);
         self.metrics.block_production_requests.inc();
         let timer = self.metrics.block_production_times.start_timer();
@@ -676,16 +993,22 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 },
                 // TODO: badass Lighthouse graffiti
                 graffiti: [0; 32],
-                proposer_slashings,
-                attester_slashings,
-                attestations: self
-                    .op_pool
-                    .get_attestations(&*self.state.read(), &self.spec),
-                deposits: self.op_pool.get_deposits(&*self.state.read(), &self.spec),
-                voluntary_exits: self
-                    .op_pool
-                    .get_voluntary_exits(&*self.state.read(), &self.spec),
-                transfers: self.op_pool.get_transfers(&*self.state.read(), &self.spec),
+                proposer_slashings: VariableList::new(proposer_slashings)?,
+                attester_slashings: VariableList::new(attester_slashings)?,
+                attestations: VariableList::new(
+                    self.op_pool
+                        .get_attestations(&*self.state.read(), &self.spec),
+                )?,
+                deposits: VariableList::new(
+                    self.op_pool.get_deposits(&*self.state.read(), &self.spec),
+                )?,
+                voluntary_exits: VariableList::new(
+                    self.op_pool
+                        .get_voluntary_exits(&*self.state.read(), &self.spec),
+                )?,
+                transfers: VariableList::new(
+                    self.op_pool.get_transfers(&*self.state.read(), &self.spec),
+                )?,
             },
         };
 
@@ -696,10 +1019,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         per_block_processing_without_verifying_block_signature(&mut state, &block, &self.spec)?;
 
-        let state_root = state.canonical_root();
+        let state_root = state.update_tree_hash_cache()?;
 
         block.state_root = state_root;
 
+        // The produced block is typically signed and imported shortly afterwards, at which point
+        // its state may be read with `Self::get_state` (e.g., by fork choice or the HTTP API).
+        self.state_cache.lock().insert(state_root, state.clone());
+
         self.metrics.block_production_successes.inc();
         timer.observe_duration();
 
@@ -722,11 +1049,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         };
 
-        // Determine the root of the block that is the head of the chain.
-        let beacon_block_root = self
-            .fork_choice
-            .write()
-            .find_head(&justified_root, &self.spec)?;
+        // Determine the root of the block that is the head of the chain, reading the justified
+        // state through the state cache.
+        let beacon_block_root = self.fork_choice.write().find_head(
+            &justified_root,
+            |state_root| {
+                self.get_state(state_root)
+                    .map_err(|e| ForkChoiceError::StorageError(format!("{:?}", e)))
+            },
+            &self.spec,
+        )?;
 
         // End fork choice metrics timer.
         timer.observe_duration();
@@ -742,8 +1074,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
             let beacon_state_root = beacon_block.state_root;
             let beacon_state: BeaconState<T::EthSpec> = self
-                .store
-                .get(&beacon_state_root)?
+                .get_state(&beacon_state_root)?
                 .ok_or_else(|| Error::MissingBeaconState(beacon_state_root))?;
 
             // If we switched to a new chain (instead of building atop the present chain).
@@ -816,6 +1147,23 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     }
 }
 
+/// Returns the root of the most recent block applied to `state`.
+///
+/// The state root of the latest block header is only filled in at the following slot, so it is
+/// computed (incrementally, using the tree hash cache) if `state` is the post-state of that block.
+fn latest_block_root<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<Hash256, BeaconStateError> {
+    let mut header = state.latest_block_header.clone();
+
+    if header.state_root == spec.zero_hash {
+        header.state_root = state.update_tree_hash_cache()?;
+    }
+
+    Ok(header.canonical_root())
+}
+
 impl From<DBError> for Error {
     fn from(e: DBError) -> Error {
         Error::DBError(e)
@@ -833,3 +1181,160 @@ impl From<BeaconStateError> for Error {
         Error::BeaconStateError(e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fork_choice::OptimizedLMDGhost;
+    use slot_clock::TestingSlotClock;
+    use store::MemoryStore;
+    use types::test_utils::TestingBeaconStateBuilder;
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 16;
+    const SKIP_SLOTS: u64 = 3;
+
+    struct TestTypes;
+
+    impl BeaconChainTypes for TestTypes {
+        type Store = MemoryStore;
+        type SlotClock = TestingSlotClock;
+        type ForkChoice = OptimizedLMDGhost<MemoryStore, E>;
+        type EthSpec = E;
+    }
+
+    fn get_chain() -> BeaconChain<TestTypes> {
+        let spec = E::default_spec();
+        let store = Arc::new(MemoryStore::open());
+
+        let (genesis_state, _keypairs) =
+            TestingBeaconStateBuilder::from_deterministic_keypairs(VALIDATOR_COUNT, &spec).build();
+        let mut genesis_block = BeaconBlock::empty(&spec);
+        genesis_block.state_root = Hash256::from_slice(&genesis_state.tree_hash_root());
+
+        let slot_clock = TestingSlotClock::new(
+            spec.genesis_slot,
+            genesis_state.genesis_time,
+            spec.seconds_per_slot,
+        );
+        let fork_choice = OptimizedLMDGhost::new(store.clone());
+
+        BeaconChain::from_genesis(
+            store,
+            slot_clock,
+            genesis_state,
+            genesis_block,
+            spec,
+            fork_choice,
+        )
+        .expect("should build chain")
+    }
+
+    /// Returns the head block root and a state at the head, with its caches built.
+    fn head(chain: &BeaconChain<TestTypes>) -> (Hash256, BeaconState<E>) {
+        (chain.head().beacon_block_root, chain.state.read().clone())
+    }
+
+    fn cached_root(chain: &BeaconChain<TestTypes>, key: (Hash256, Slot)) -> Option<Hash256> {
+        chain
+            .skip_slot_cache
+            .lock()
+            .get_cloned(&key)
+            .map(|state| state.canonical_root())
+    }
+
+    #[test]
+    fn skip_slot_cache_miss() {
+        let chain = get_chain();
+        let (block_root, pre_state) = head(&chain);
+        let slot = pre_state.slot + SKIP_SLOTS;
+
+        assert_eq!(cached_root(&chain, (block_root, slot)), None);
+
+        let mut state = pre_state.clone();
+        chain
+            .advance_through_skip_slots(&mut state, block_root, slot)
+            .unwrap();
+
+        let mut expected = pre_state.clone();
+        for _ in 0..SKIP_SLOTS {
+            per_slot_processing(&mut expected, &chain.spec).unwrap();
+        }
+
+        assert_eq!(state.slot, slot);
+        assert_eq!(state.canonical_root(), expected.canonical_root());
+        assert_eq!(
+            cached_root(&chain, (block_root, slot)),
+            Some(expected.canonical_root())
+        );
+
+        // Only the requested slot is cached.
+        assert_eq!(cached_root(&chain, (block_root, slot - 1)), None);
+    }
+
+    #[test]
+    fn skip_slot_cache_hit() {
+        let chain = get_chain();
+        let (block_root, pre_state) = head(&chain);
+        let slot = pre_state.slot + SKIP_SLOTS;
+
+        let mut state = pre_state.clone();
+        chain
+            .advance_through_skip_slots(&mut state, block_root, slot)
+            .unwrap();
+
+        // Mark the cached state, so that reading it can be distinguished from repeating the
+        // transition.
+        let mut marked = state.clone();
+        marked.balances[0] = 42;
+        chain
+            .skip_slot_cache
+            .lock()
+            .insert((block_root, slot), marked.clone());
+
+        let mut state = pre_state.clone();
+        chain
+            .advance_through_skip_slots(&mut state, block_root, slot)
+            .unwrap();
+
+        assert_eq!(state.canonical_root(), marked.canonical_root());
+    }
+
+    #[test]
+    fn skip_slot_cache_fork() {
+        let chain = get_chain();
+        let (block_root, pre_state) = head(&chain);
+        let slot = pre_state.slot + SKIP_SLOTS;
+
+        // A competing block at the same slot as the head, with a different post-state.
+        let fork_root = Hash256::from_low_u64_be(42);
+        let mut fork_pre_state = pre_state.clone();
+        fork_pre_state.balances[0] += 1;
+
+        let mut state = pre_state.clone();
+        chain
+            .advance_through_skip_slots(&mut state, block_root, slot)
+            .unwrap();
+
+        // The state cached for the head must not be used for the fork.
+        let mut fork_state = fork_pre_state.clone();
+        chain
+            .advance_through_skip_slots(&mut fork_state, fork_root, slot)
+            .unwrap();
+
+        assert_eq!(fork_state.slot, slot);
+        assert_eq!(fork_state.balances[0], state.balances[0] + 1);
+        assert_ne!(fork_state.canonical_root(), state.canonical_root());
+
+        // Both branches remain cached.
+        assert_eq!(
+            cached_root(&chain, (block_root, slot)),
+            Some(state.canonical_root())
+        );
+        assert_eq!(
+            cached_root(&chain, (fork_root, slot)),
+            Some(fork_state.canonical_root())
+        );
+    }
+}
@@ -25,6 +25,8 @@ pub enum BeaconChainError {
     ForkChoiceError(ForkChoiceError),
     MissingBeaconBlock(Hash256),
     MissingBeaconState(Hash256),
+    /// A state disagrees with the chain-wide pubkey cache about the key at the given index.
+    ValidatorPubkeyCacheInconsistent(usize),
     SlotProcessingError(SlotProcessingError),
     MetricsError(String),
 }
@@ -42,7 +44,12 @@ pub enum BlockProductionError {
     UnableToGetBlockRootFromState,
     BlockProcessingError(BlockProcessingError),
     BeaconStateError(BeaconStateError),
+    BeaconChainError(BeaconChainError),
+    /// The operation pool returned more operations than fit in a block.
+    VariableListError(VariableListError),
 }
 
 easy_from_to!(BlockProcessingError, BlockProductionError);
 easy_from_to!(BeaconStateError, BlockProductionError);
+easy_from_to!(BeaconChainError, BlockProductionError);
+easy_from_to!(VariableListError, BlockProductionError);
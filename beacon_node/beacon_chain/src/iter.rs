@@ -107,10 +107,10 @@ mod test {
 
         let mut hashes = (0..).into_iter().map(|i| Hash256::from(i));
 
-        for root in &mut state_a.latest_block_roots[..] {
+        for root in state_a.latest_block_roots.iter_mut() {
             *root = hashes.next().unwrap()
         }
-        for root in &mut state_b.latest_block_roots[..] {
+        for root in state_b.latest_block_roots.iter_mut() {
             *root = hashes.next().unwrap()
         }
 
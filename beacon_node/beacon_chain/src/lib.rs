@@ -4,10 +4,15 @@ mod errors;
 pub mod iter;
 mod metrics;
 mod persisted_beacon_chain;
+mod rewards_report;
+mod state_cache;
+mod validator_pubkey_cache;
 
 pub use self::beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessingOutcome};
 pub use self::checkpoint::CheckPoint;
 pub use self::errors::{BeaconChainError, BlockProductionError};
+pub use self::rewards_report::RewardsReport;
+pub use self::validator_pubkey_cache::ValidatorPubkeyCache;
 pub use fork_choice;
 pub use parking_lot;
 pub use slot_clock;
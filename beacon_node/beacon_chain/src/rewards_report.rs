@@ -0,0 +1,32 @@
+use serde_derive::Serialize;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use state_processing::RewardsBreakdown;
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::Epoch;
+
+/// The rewards and penalties applied to each validator during the transition out of `epoch`.
+///
+/// Stored against the root of the state at the last slot of `epoch` (i.e., the state upon which
+/// per-epoch processing was run).
+#[derive(Debug, Clone, PartialEq, Serialize, Encode, Decode)]
+pub struct RewardsReport {
+    /// The epoch which was processed.
+    pub epoch: Epoch,
+    /// The breakdown for each validator, indexed by validator index.
+    pub validators: Vec<RewardsBreakdown>,
+}
+
+impl StoreItem for RewardsReport {
+    fn db_column() -> DBColumn {
+        DBColumn::RewardsReport
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &mut [u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use types::{BeaconState, EthSpec};
+
+/// A least-recently-used cache of `BeaconState`s.
+///
+/// The key is typically the state root, however any value which uniquely identifies a state may
+/// be used (e.g., a block root and a slot for a state advanced through skip slots).
+///
+/// States are stored with their caches (committee, tree hash, etc.) intact. A state taken from
+/// here is ready for `per_slot_processing` without first rebuilding those caches, unlike a state
+/// read from the database.
+///
+/// The cache is expected to be small (a handful of states), so eviction simply scans for the
+/// least recently used entry.
+pub struct StateCache<K, E: EthSpec> {
+    capacity: usize,
+    /// Incremented each time a state is inserted or read, used to track recency.
+    clock: u64,
+    states: HashMap<K, (u64, BeaconState<E>)>,
+}
+
+impl<K: Hash + Eq + Copy, E: EthSpec> StateCache<K, E> {
+    /// Create an empty cache which holds at most `capacity` states.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            clock: 0,
+            states: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns a clone of the state with the given key, if it is in the cache.
+    pub fn get_cloned(&mut self, key: &K) -> Option<BeaconState<E>> {
+        self.clock += 1;
+        let clock = self.clock;
+
+        self.states.get_mut(key).map(|(last_used, state)| {
+            *last_used = clock;
+            state.clone()
+        })
+    }
+
+    /// Insert `state`, evicting the least recently used state if the cache is full.
+    pub fn insert(&mut self, key: K, state: BeaconState<E>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if !self.states.contains_key(&key) && self.states.len() >= self.capacity {
+            let oldest = self
+                .states
+                .iter()
+                .min_by_key(|(_, (last_used, _))| *last_used)
+                .map(|(key, _)| *key);
+
+            if let Some(key) = oldest {
+                self.states.remove(&key);
+            }
+        }
+
+        self.clock += 1;
+        self.states.insert(key, (self.clock, state));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::{test_utils::TestingBeaconStateBuilder, Keypair, MinimalEthSpec, Slot};
+
+    type E = MinimalEthSpec;
+
+    /// Returns a state which is distinguishable from other states by its `slot`.
+    fn get_state(slot: u64) -> BeaconState<E> {
+        let builder = TestingBeaconStateBuilder::from_single_keypair(
+            0,
+            &Keypair::random(),
+            &E::default_spec(),
+        );
+        let (mut state, _keypairs) = builder.build();
+        state.slot = Slot::new(slot);
+        state
+    }
+
+    fn get_slot(cache: &mut StateCache<u64, E>, key: u64) -> Option<Slot> {
+        cache.get_cloned(&key).map(|state| state.slot)
+    }
+
+    #[test]
+    fn hit_and_miss() {
+        let mut cache = StateCache::new(2);
+
+        assert_eq!(get_slot(&mut cache, 1), None);
+
+        cache.insert(1, get_state(1));
+
+        assert_eq!(get_slot(&mut cache, 1), Some(Slot::new(1)));
+        assert_eq!(get_slot(&mut cache, 2), None);
+    }
+
+    #[test]
+    fn evicts_least_recently_inserted() {
+        let mut cache = StateCache::new(2);
+
+        cache.insert(1, get_state(1));
+        cache.insert(2, get_state(2));
+        cache.insert(3, get_state(3));
+
+        assert_eq!(get_slot(&mut cache, 1), None);
+        assert_eq!(get_slot(&mut cache, 2), Some(Slot::new(2)));
+        assert_eq!(get_slot(&mut cache, 3), Some(Slot::new(3)));
+    }
+
+    #[test]
+    fn evicts_least_recently_read() {
+        let mut cache = StateCache::new(2);
+
+        cache.insert(1, get_state(1));
+        cache.insert(2, get_state(2));
+
+        // Reading `1` makes `2` the least recently used.
+        assert_eq!(get_slot(&mut cache, 1), Some(Slot::new(1)));
+
+        cache.insert(3, get_state(3));
+
+        assert_eq!(get_slot(&mut cache, 1), Some(Slot::new(1)));
+        assert_eq!(get_slot(&mut cache, 2), None);
+        assert_eq!(get_slot(&mut cache, 3), Some(Slot::new(3)));
+    }
+
+    #[test]
+    fn replacing_a_key_does_not_evict() {
+        let mut cache = StateCache::new(2);
+
+        cache.insert(1, get_state(1));
+        cache.insert(2, get_state(2));
+        cache.insert(2, get_state(42));
+
+        assert_eq!(get_slot(&mut cache, 1), Some(Slot::new(1)));
+        assert_eq!(get_slot(&mut cache, 2), Some(Slot::new(42)));
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut cache = StateCache::new(0);
+
+        cache.insert(1, get_state(1));
+
+        assert_eq!(get_slot(&mut cache, 1), None);
+    }
+}
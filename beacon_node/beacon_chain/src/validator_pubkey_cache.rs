@@ -0,0 +1,145 @@
+use crate::errors::BeaconChainError as Error;
+use types::{BeaconState, EthSpec, PublicKey};
+
+/// A chain-wide, append-only list of the public key of each validator, by index.
+///
+/// Validators are never removed from the registry and each is assigned the next index as its
+/// deposit is processed, so all states which descend from genesis agree upon the key at any index
+/// they both contain. This allows a single cache to serve every state in the chain, instead of
+/// each `BeaconState` clone carrying (and rebuilding) its own.
+///
+/// A state may contain fewer validators than the cache, so callers must bound lookups by the
+/// length of the relevant validator registry.
+///
+/// Only the index to key direction is stored. Lookups by key should use the `pubkey_cache` of a
+/// `BeaconState` (see `BeaconState::get_validator_index`), rather than duplicating it here.
+#[derive(Debug, Default)]
+pub struct ValidatorPubkeyCache {
+    pubkeys: Vec<PublicKey>,
+}
+
+impl ValidatorPubkeyCache {
+    /// Create a cache containing the validator registry of `state`.
+    pub fn new<E: EthSpec>(state: &BeaconState<E>) -> Result<Self, Error> {
+        let mut cache = Self::default();
+        cache.import_new_pubkeys(state)?;
+        Ok(cache)
+    }
+
+    /// Add any validators in `state` which are not yet known to the cache.
+    ///
+    /// Returns an error if `state` disagrees with the cache about the key at the last index they
+    /// both contain, which indicates that `state` is not from the same chain.
+    pub fn import_new_pubkeys<E: EthSpec>(&mut self, state: &BeaconState<E>) -> Result<(), Error> {
+        let known = std::cmp::min(self.pubkeys.len(), state.validator_registry.len());
+        if known > 0 && state.validator_registry[known - 1].pubkey != self.pubkeys[known - 1] {
+            return Err(Error::ValidatorPubkeyCacheInconsistent(known - 1));
+        }
+
+        for validator in state.validator_registry.iter().skip(self.pubkeys.len()) {
+            self.pubkeys.push(validator.pubkey.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the public key of the validator at `index`.
+    pub fn get(&self, index: usize) -> Option<&PublicKey> {
+        self.pubkeys.get(index)
+    }
+
+    /// Returns the number of validators in the cache.
+    pub fn len(&self) -> usize {
+        self.pubkeys.len()
+    }
+
+    /// Returns `true` if the cache contains no validators.
+    pub fn is_empty(&self) -> bool {
+        self.pubkeys.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::{test_utils::TestingBeaconStateBuilder, Keypair, MinimalEthSpec, Validator};
+
+    type E = MinimalEthSpec;
+
+    fn get_state(validator_count: usize) -> BeaconState<E> {
+        let builder = TestingBeaconStateBuilder::from_deterministic_keypairs(
+            validator_count,
+            &E::default_spec(),
+        );
+        let (state, _keypairs) = builder.build();
+        state
+    }
+
+    fn assert_matches_registry(cache: &ValidatorPubkeyCache, state: &BeaconState<E>) {
+        for (i, validator) in state.validator_registry.iter().enumerate() {
+            assert_eq!(cache.get(i), Some(&validator.pubkey), "index {}", i);
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let state = get_state(8);
+        let cache = ValidatorPubkeyCache::new(&state).unwrap();
+
+        assert_eq!(cache.len(), 8);
+        assert_matches_registry(&cache, &state);
+        assert_eq!(cache.get(8), None);
+    }
+
+    #[test]
+    fn grows_with_new_validators() {
+        let mut state = get_state(4);
+        let mut cache = ValidatorPubkeyCache::new(&state).unwrap();
+
+        // Importing the same state again is a no-op.
+        cache.import_new_pubkeys(&state).unwrap();
+        assert_eq!(cache.len(), 4);
+
+        for _ in 0..2 {
+            state
+                .validator_registry
+                .push(Validator {
+                    pubkey: Keypair::random().pk,
+                    ..Validator::default()
+                })
+                .unwrap();
+        }
+        cache.import_new_pubkeys(&state).unwrap();
+
+        assert_eq!(cache.len(), 6);
+        assert_matches_registry(&cache, &state);
+    }
+
+    #[test]
+    fn ignores_shorter_registries() {
+        let state = get_state(8);
+        let mut cache = ValidatorPubkeyCache::new(&state).unwrap();
+
+        // A state with a prefix of the registry (e.g., an ancestor) does not shrink the cache.
+        let mut ancestor = state.clone();
+        ancestor.validator_registry = state.validator_registry[0..4].to_vec().into();
+        cache.import_new_pubkeys(&ancestor).unwrap();
+
+        assert_eq!(cache.len(), 8);
+        assert_matches_registry(&cache, &state);
+    }
+
+    #[test]
+    fn rejects_inconsistent_state() {
+        let state = get_state(4);
+        let mut cache = ValidatorPubkeyCache::new(&state).unwrap();
+
+        let mut other = state.clone();
+        other.validator_registry[3].pubkey = Keypair::random().pk;
+
+        assert_eq!(
+            cache.import_new_pubkeys(&other),
+            Err(Error::ValidatorPubkeyCacheInconsistent(3))
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::client_config::GenesisState;
+use crate::interop_genesis::interop_genesis_state;
 use beacon_chain::{
     fork_choice::OptimizedLMDGhost, slot_clock::SystemTimeSlotClock, store::Store, BeaconChain,
     BeaconChainTypes,
@@ -8,7 +10,9 @@ use slot_clock::SlotClock;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use tree_hash::TreeHash;
-use types::{test_utils::TestingBeaconStateBuilder, BeaconBlock, ChainSpec, EthSpec, Hash256};
+use types::{
+    test_utils::TestingBeaconStateBuilder, BeaconBlock, BeaconState, ChainSpec, EthSpec, Hash256,
+};
 
 /// The number initial validators when starting the `Minimal`.
 const TESTNET_VALIDATOR_COUNT: usize = 16;
@@ -17,10 +21,16 @@ const TESTNET_VALIDATOR_COUNT: usize = 16;
 pub trait InitialiseBeaconChain<T: BeaconChainTypes> {
     fn initialise_beacon_chain(
         store: Arc<T::Store>,
+        genesis_state: &GenesisState,
         spec: ChainSpec,
         log: Logger,
-    ) -> BeaconChain<T> {
-        maybe_load_from_store_for_testnet::<_, T::Store, T::EthSpec>(store, spec, log)
+    ) -> Result<BeaconChain<T>, String> {
+        maybe_load_from_store_for_testnet::<_, T::Store, T::EthSpec>(
+            store,
+            genesis_state,
+            spec,
+            log,
+        )
     }
 }
 
@@ -39,14 +49,17 @@ impl<S: Store, E: EthSpec + Clone> BeaconChainTypes for ClientType<S, E> {
 impl<T: Store, E: EthSpec, X: BeaconChainTypes> InitialiseBeaconChain<X> for ClientType<T, E> {}
 
 /// Loads a `BeaconChain` from `store`, if it exists. Otherwise, create a new chain from genesis.
+///
+/// Returns an error if a new chain is required and it cannot be created.
 fn maybe_load_from_store_for_testnet<T, U: Store, V: EthSpec>(
     store: Arc<U>,
+    genesis_state: &GenesisState,
     spec: ChainSpec,
     log: Logger,
-) -> BeaconChain<T>
+) -> Result<BeaconChain<T>, String>
 where
     T: BeaconChainTypes<Store = U>,
-    T::ForkChoice: ForkChoice<U>,
+    T::ForkChoice: ForkChoice<U, T::EthSpec>,
 {
     if let Ok(Some(beacon_chain)) = BeaconChain::from_store(store.clone(), spec.clone()) {
         info!(
@@ -56,14 +69,28 @@ where
             "best_slot" => beacon_chain.best_slot(),
         );
 
-        beacon_chain
+        Ok(beacon_chain)
     } else {
         info!(log, "Initializing new BeaconChain from genesis");
-        let state_builder = TestingBeaconStateBuilder::from_default_keypairs_file_if_exists(
-            TESTNET_VALIDATOR_COUNT,
-            &spec,
-        );
-        let (genesis_state, _keypairs) = state_builder.build();
+        let genesis_state: BeaconState<V> = match genesis_state {
+            GenesisState::Testnet => {
+                let state_builder = TestingBeaconStateBuilder::from_default_keypairs_file_if_exists(
+                    TESTNET_VALIDATOR_COUNT,
+                    &spec,
+                );
+                let (genesis_state, _keypairs) = state_builder.build();
+                genesis_state
+            }
+            GenesisState::Interop { validator_count } => {
+                info!(
+                    log,
+                    "Building interop genesis state";
+                    "validator_count" => validator_count,
+                    "genesis_time" => spec.genesis_time,
+                );
+                interop_genesis_state(*validator_count, spec.genesis_time, &spec)?
+            }
+        };
 
         let mut genesis_block = BeaconBlock::empty(&spec);
         genesis_block.state_root = Hash256::from_slice(&genesis_state.tree_hash_root());
@@ -78,7 +105,6 @@ where
         let fork_choice = T::ForkChoice::new(store.clone());
 
         // Genesis chain
-        //TODO: Handle error correctly
         BeaconChain::from_genesis(
             store,
             slot_clock,
@@ -87,6 +113,6 @@ where
             spec,
             fork_choice,
         )
-        .expect("Terminate if beacon chain generation fails")
+        .map_err(|e| format!("Unable to create BeaconChain from genesis: {:?}", e))
     }
 }
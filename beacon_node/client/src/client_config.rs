@@ -14,6 +14,28 @@ pub struct ClientConfig {
     pub network: network::NetworkConfig,
     pub rpc: rpc::RPCConfig,
     pub http: HttpServerConfig,
+    /// How to build the genesis state, if the chain is not already in the database.
+    #[serde(skip)]
+    pub genesis_state: GenesisState,
+    /// If true, store a `RewardsReport` for each epoch transition.
+    #[serde(default)]
+    pub rewards_reports: bool,
+}
+
+/// Defines how the genesis `BeaconState` is built.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenesisState {
+    /// Use the testnet validators from the keypairs file, if it exists.
+    Testnet,
+    /// Use `validator_count` validators with the keypairs and deposits from the interop
+    /// specification.
+    Interop { validator_count: usize },
+}
+
+impl Default for GenesisState {
+    fn default() -> Self {
+        GenesisState::Testnet
+    }
 }
 
 impl Default for ClientConfig {
@@ -27,6 +49,8 @@ impl Default for ClientConfig {
             network: NetworkConfig::new(vec![]),
             rpc: rpc::RPCConfig::default(),
             http: HttpServerConfig::default(),
+            genesis_state: GenesisState::default(),
+            rewards_reports: false,
         }
     }
 }
@@ -58,6 +82,19 @@ impl ClientConfig {
             self.db_type = dir.to_string();
         }
 
+        if args.is_present("interop") {
+            let validator_count = args
+                .value_of("validator-count")
+                .ok_or("--interop requires --validator-count")?
+                .parse()
+                .map_err(|_| "Invalid validator-count")?;
+            self.genesis_state = GenesisState::Interop { validator_count };
+        }
+
+        if args.is_present("rewards-reports") {
+            self.rewards_reports = true;
+        }
+
         self.network.apply_cli_args(args)?;
         self.rpc.apply_cli_args(args)?;
         self.http.apply_cli_args(args)?;
@@ -0,0 +1,190 @@
+use hashing::hash;
+use state_processing::get_genesis_beacon_state;
+use tree_hash::TreeHash;
+use types::test_utils::TestingDepositBuilder;
+use types::{BeaconState, ChainSpec, Deposit, Eth1Data, EthSpec, Fork, Hash256, Keypair};
+
+/// The eth1 block hash used in the genesis `Eth1Data`, as per the interop specification.
+const INTEROP_ETH1_BLOCK_HASH: [u8; 32] = [0x42; 32];
+
+/// Builds a genesis `BeaconState` with `validator_count` validators using the keypairs and
+/// deposits defined by the interop specification.
+///
+/// Each validator makes a single deposit of `spec.max_effective_balance`, so they are all active
+/// at genesis.
+pub fn interop_genesis_state<T: EthSpec>(
+    validator_count: usize,
+    genesis_time: u64,
+    spec: &ChainSpec,
+) -> Result<BeaconState<T>, String> {
+    let keypairs = eth2_interop_keypairs::keypairs(0..validator_count);
+    let mut deposits = interop_deposits::<T>(&keypairs, spec);
+
+    let leaves: Vec<Hash256> = deposits
+        .iter()
+        .map(|deposit| Hash256::from_slice(&deposit.data.tree_hash_root()))
+        .collect();
+    let tree = DepositTree::new(leaves, spec.deposit_contract_tree_depth as usize);
+
+    for deposit in &mut deposits {
+        deposit.proof = tree.proof(deposit.index as usize).into();
+    }
+
+    let eth1_data = Eth1Data {
+        deposit_root: tree.root(),
+        deposit_count: deposits.len() as u64,
+        block_hash: Hash256::from_slice(&INTEROP_ETH1_BLOCK_HASH),
+    };
+
+    get_genesis_beacon_state(&deposits, genesis_time, eth1_data, spec)
+        .map_err(|e| format!("Unable to build interop genesis state: {:?}", e))
+}
+
+/// Returns a signed deposit of `spec.max_effective_balance` for each of `keypairs`, without a
+/// Merkle proof.
+fn interop_deposits<T: EthSpec>(keypairs: &[Keypair], spec: &ChainSpec) -> Vec<Deposit> {
+    let fork = Fork::genesis(T::genesis_epoch());
+
+    keypairs
+        .iter()
+        .enumerate()
+        .map(|(i, keypair)| {
+            let mut builder =
+                TestingDepositBuilder::new(keypair.pk.clone(), spec.max_effective_balance);
+            builder.set_index(i as u64);
+            builder.sign(keypair, T::genesis_epoch(), &fork, spec);
+            builder.build()
+        })
+        .collect()
+}
+
+/// A fixed-depth Merkle tree of deposit data roots, where absent leaves are zero.
+struct DepositTree {
+    /// Each layer of the tree, leaves first. Absent nodes are omitted.
+    layers: Vec<Vec<Hash256>>,
+    /// The root of a sub-tree of zero leaves, at each height.
+    zero_hashes: Vec<Hash256>,
+}
+
+impl DepositTree {
+    fn new(leaves: Vec<Hash256>, depth: usize) -> Self {
+        let mut zero_hashes = vec![Hash256::zero()];
+        for i in 0..depth {
+            zero_hashes.push(hash_concat(&zero_hashes[i], &zero_hashes[i]));
+        }
+
+        let mut layers = vec![leaves];
+        for height in 0..depth {
+            let layer = layers[height]
+                .chunks(2)
+                .map(|pair| hash_concat(&pair[0], pair.get(1).unwrap_or(&zero_hashes[height])))
+                .collect();
+            layers.push(layer);
+        }
+
+        Self {
+            layers,
+            zero_hashes,
+        }
+    }
+
+    fn root(&self) -> Hash256 {
+        let depth = self.layers.len() - 1;
+        self.layers[depth]
+            .first()
+            .cloned()
+            .unwrap_or(self.zero_hashes[depth])
+    }
+
+    /// Returns the branch for the leaf at `index`, bottom-up.
+    fn proof(&self, index: usize) -> Vec<Hash256> {
+        let depth = self.layers.len() - 1;
+
+        (0..depth)
+            .map(|height| {
+                let sibling = (index >> height) ^ 1;
+                self.layers[height]
+                    .get(sibling)
+                    .cloned()
+                    .unwrap_or(self.zero_hashes[height])
+            })
+            .collect()
+    }
+}
+
+fn hash_concat(a: &Hash256, b: &Hash256) -> Hash256 {
+    let mut preimage = a.as_bytes().to_vec();
+    preimage.extend_from_slice(b.as_bytes());
+    Hash256::from_slice(&hash(&preimage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MinimalEthSpec;
+
+    fn leaf(i: u8) -> Hash256 {
+        Hash256::from_slice(&[i; 32])
+    }
+
+    #[test]
+    fn deposit_tree_root() {
+        let zero = Hash256::zero();
+
+        // A tree of depth 2 with three leaves.
+        let tree = DepositTree::new(vec![leaf(1), leaf(2), leaf(3)], 2);
+        let expected = hash_concat(
+            &hash_concat(&leaf(1), &leaf(2)),
+            &hash_concat(&leaf(3), &zero),
+        );
+        assert_eq!(tree.root(), expected);
+
+        // An empty tree has the root of a tree of zero leaves.
+        let tree = DepositTree::new(vec![], 2);
+        let zero_subtree = hash_concat(&zero, &zero);
+        assert_eq!(tree.root(), hash_concat(&zero_subtree, &zero_subtree));
+    }
+
+    #[test]
+    fn deposit_tree_proofs() {
+        let depth = 4;
+        let leaves: Vec<Hash256> = (1..=5).map(leaf).collect();
+        let tree = DepositTree::new(leaves.clone(), depth);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index);
+            assert_eq!(proof.len(), depth);
+
+            // Recompute the root from the leaf and its branch.
+            let root = proof
+                .iter()
+                .enumerate()
+                .fold(*leaf, |node, (height, sibling)| {
+                    if (index >> height) & 1 == 0 {
+                        hash_concat(&node, sibling)
+                    } else {
+                        hash_concat(sibling, &node)
+                    }
+                });
+            assert_eq!(root, tree.root(), "invalid proof for leaf {}", index);
+        }
+    }
+
+    #[test]
+    fn interop_genesis_state_has_active_validators() {
+        let spec = MinimalEthSpec::default_spec();
+        let validator_count = 4;
+        let genesis_time = 42;
+
+        let state: BeaconState<MinimalEthSpec> =
+            interop_genesis_state(validator_count, genesis_time, &spec).unwrap();
+
+        assert_eq!(state.genesis_time, genesis_time);
+        assert_eq!(state.validator_registry.len(), validator_count);
+        assert_eq!(state.deposit_index, validator_count as u64);
+        assert!(state
+            .validator_registry
+            .iter()
+            .all(|validator| validator.is_active_at(MinimalEthSpec::genesis_epoch())));
+    }
+}
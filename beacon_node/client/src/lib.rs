@@ -3,6 +3,7 @@ extern crate slog;
 mod beacon_chain_types;
 mod client_config;
 pub mod error;
+mod interop_genesis;
 pub mod notifier;
 
 use beacon_chain::BeaconChain;
@@ -21,7 +22,7 @@ use tokio::timer::Interval;
 pub use beacon_chain::BeaconChainTypes;
 pub use beacon_chain_types::ClientType;
 pub use beacon_chain_types::InitialiseBeaconChain;
-pub use client_config::ClientConfig;
+pub use client_config::{ClientConfig, GenesisState};
 pub use eth2_config::Eth2Config;
 
 /// Main beacon node client service. This provides the connection and initialisation of the clients
@@ -62,11 +63,14 @@ where
         let seconds_per_slot = eth2_config.spec.seconds_per_slot;
 
         // Load a `BeaconChain` from the store, or create a new one if it does not exist.
-        let beacon_chain = Arc::new(T::initialise_beacon_chain(
+        let mut beacon_chain = T::initialise_beacon_chain(
             store,
+            &client_config.genesis_state,
             eth2_config.spec.clone(),
             log.clone(),
-        ));
+        )?;
+        beacon_chain.rewards_reports = client_config.rewards_reports;
+        let beacon_chain = Arc::new(beacon_chain);
         // Registry all beacon chain metrics with the global registry.
         beacon_chain
             .metrics
@@ -119,6 +123,7 @@ where
                 &client_config.rpc,
                 executor,
                 network_send.clone(),
+                network.topics(),
                 beacon_chain.clone(),
                 &log,
             ))
@@ -0,0 +1,16 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate eth2_libp2p;
+extern crate ssz;
+
+use eth2_libp2p::PubsubMessage;
+use ssz::{Decode, Encode};
+
+// Fuzz decoding of gossip messages received from peers.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = PubsubMessage::from_ssz_bytes(data) {
+        // Anything which decodes must survive a round-trip.
+        let encoded = message.as_ssz_bytes();
+        assert_eq!(PubsubMessage::from_ssz_bytes(&encoded), Ok(message));
+    }
+});
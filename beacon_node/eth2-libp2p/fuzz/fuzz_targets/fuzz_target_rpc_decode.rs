@@ -0,0 +1,10 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate eth2_libp2p;
+
+use eth2_libp2p::rpc::protocol::decode;
+
+// Fuzz decoding of RPC packets received from peers.
+fuzz_target!(|data: &[u8]| {
+    let _result = decode(data.to_vec());
+});
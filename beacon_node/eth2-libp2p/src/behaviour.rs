@@ -13,8 +13,9 @@ use libp2p::{
     NetworkBehaviour, PeerId,
 };
 use slog::{debug, o, trace, warn};
-use ssz::{ssz_encode, Decode, DecodeError, Encode};
-use types::{Attestation, BeaconBlock};
+use ssz::{ssz_encode, Decode};
+use ssz_derive::{Decode, Encode};
+use types::{Attestation, BeaconBlock, VoluntaryExit};
 use types::{Topic, TopicHash};
 
 /// Builds the network behaviour for the libp2p Swarm.
@@ -188,70 +189,19 @@ pub enum BehaviourEvent {
 }
 
 /// Messages that are passed to and from the pubsub (Gossipsub) behaviour.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Encoded as an SSZ union, where the selector is the index of the variant.
+///
+/// The variant order is part of the wire format: do not reorder the variants and only append new
+/// ones.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
 pub enum PubsubMessage {
     /// Gossipsub message providing notification of a new block.
     Block(BeaconBlock),
     /// Gossipsub message providing notification of a new attestation.
     Attestation(Attestation),
-}
-
-//TODO: Correctly encode/decode enums. Prefixing with integer for now.
-impl Encode for PubsubMessage {
-    fn is_ssz_fixed_len() -> bool {
-        false
-    }
-
-    fn ssz_append(&self, buf: &mut Vec<u8>) {
-        let offset = <u32 as Encode>::ssz_fixed_len() + <Vec<u8> as Encode>::ssz_fixed_len();
-
-        let mut encoder = ssz::SszEncoder::container(buf, offset);
-
-        match self {
-            PubsubMessage::Block(block_gossip) => {
-                encoder.append(&0_u32);
-
-                // Encode the gossip as a Vec<u8>;
-                encoder.append(&block_gossip.as_ssz_bytes());
-            }
-            PubsubMessage::Attestation(attestation_gossip) => {
-                encoder.append(&1_u32);
-
-                // Encode the gossip as a Vec<u8>;
-                encoder.append(&attestation_gossip.as_ssz_bytes());
-            }
-        }
-
-        encoder.finalize();
-    }
-}
-
-impl Decode for PubsubMessage {
-    fn is_ssz_fixed_len() -> bool {
-        false
-    }
-
-    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
-        let mut builder = ssz::SszDecoderBuilder::new(&bytes);
-
-        builder.register_type::<u32>()?;
-        builder.register_type::<Vec<u8>>()?;
-
-        let mut decoder = builder.build()?;
-
-        let id: u32 = decoder.decode_next()?;
-        let body: Vec<u8> = decoder.decode_next()?;
-
-        match id {
-            0 => Ok(PubsubMessage::Block(BeaconBlock::from_ssz_bytes(&body)?)),
-            1 => Ok(PubsubMessage::Attestation(Attestation::from_ssz_bytes(
-                &body,
-            )?)),
-            _ => Err(DecodeError::BytesInvalid(
-                "Invalid PubsubMessage id".to_string(),
-            )),
-        }
-    }
+    /// Gossipsub message providing notification of a new voluntary exit.
+    VoluntaryExit(VoluntaryExit),
 }
 
 #[cfg(test)]
@@ -269,4 +219,19 @@ mod test {
 
         assert_eq!(original, decoded);
     }
+
+    #[test]
+    fn ssz_encoding_voluntary_exit() {
+        let original = PubsubMessage::VoluntaryExit(VoluntaryExit {
+            epoch: Epoch::new(1),
+            validator_index: 42,
+            signature: Signature::empty_signature(),
+        });
+
+        let encoded = ssz_encode(&original);
+
+        let decoded = PubsubMessage::from_ssz_bytes(&encoded).unwrap();
+
+        assert_eq!(original, decoded);
+    }
 }
@@ -52,7 +52,11 @@ impl Into<u16> for RPCMethod {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Encoded as an SSZ union, where the selector is the index of the variant.
+///
+/// The variant order is part of the wire format: do not reorder the variants and only append new
+/// ones.
+#[derive(Encode, Decode, Debug, Clone)]
 pub enum RPCRequest {
     Hello(HelloMessage),
     Goodbye(GoodbyeReason),
@@ -76,7 +80,11 @@ impl RPCRequest {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Encoded as an SSZ union, where the selector is the index of the variant.
+///
+/// The variant order is part of the wire format: do not reorder the variants and only append new
+/// ones.
+#[derive(Encode, Decode, Debug, Clone)]
 pub enum RPCResponse {
     Hello(HelloMessage),
     BeaconBlockRoots(BeaconBlockRootsResponse),
@@ -3,7 +3,7 @@
 /// This is purpose built for Ethereum 2.0 serenity and the protocol listens on
 /// `/eth/serenity/rpc/1.0.0`
 pub mod methods;
-mod protocol;
+pub mod protocol;
 
 use futures::prelude::*;
 use libp2p::core::protocols_handler::{OneShotHandler, ProtocolsHandler};
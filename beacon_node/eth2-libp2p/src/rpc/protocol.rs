@@ -1,6 +1,6 @@
 use super::methods::*;
 use libp2p::core::{upgrade, InboundUpgrade, OutboundUpgrade, UpgradeInfo};
-use ssz::{impl_decode_via_from, impl_encode_via_from, ssz_encode, Decode, Encode};
+use ssz::{impl_decode_via_from, impl_encode_via_from, Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::hash::{Hash, Hasher};
 use std::io;
@@ -116,77 +116,66 @@ where
     }
 }
 
-/// A helper structed used to obtain SSZ serialization for RPC messages.
-#[derive(Encode, Decode, Default)]
-struct SszContainer {
-    /// Note: the `is_request` field is not included in the spec.
-    ///
-    /// We are unable to determine a request from a response unless we add some flag to the
-    /// packet. Here we have added a bool (encoded as 1 byte) which is set to `1` if the
-    /// message is a request.
-    is_request: bool,
-    id: u64,
-    other: u16,
-    bytes: Vec<u8>,
+/// A helper used to obtain SSZ serialization for RPC messages.
+///
+/// Note: distinguishing requests from responses is not included in the spec.
+///
+/// We are unable to determine a request from a response unless we add some flag to the packet.
+/// Here the message is wrapped in an SSZ union, where the selector is `0` for a request and `1`
+/// for a response.
+///
+/// The variant order is part of the wire format: do not reorder the variants.
+#[derive(Encode, Decode)]
+enum SszContainer {
+    Request(SszRequest),
+    Response(SszResponse),
 }
 
-fn decode(packet: Vec<u8>) -> Result<RPCEvent, DecodeError> {
-    let msg = SszContainer::from_ssz_bytes(&packet)?;
+#[derive(Encode, Decode)]
+struct SszRequest {
+    id: RequestId,
+    body: RPCRequest,
+}
 
-    if msg.is_request {
-        let body = match RPCMethod::from(msg.other) {
-            RPCMethod::Hello => RPCRequest::Hello(HelloMessage::from_ssz_bytes(&msg.bytes)?),
-            RPCMethod::Goodbye => RPCRequest::Goodbye(GoodbyeReason::from_ssz_bytes(&msg.bytes)?),
-            RPCMethod::BeaconBlockRoots => {
-                RPCRequest::BeaconBlockRoots(BeaconBlockRootsRequest::from_ssz_bytes(&msg.bytes)?)
-            }
-            RPCMethod::BeaconBlockHeaders => RPCRequest::BeaconBlockHeaders(
-                BeaconBlockHeadersRequest::from_ssz_bytes(&msg.bytes)?,
-            ),
-            RPCMethod::BeaconBlockBodies => {
-                RPCRequest::BeaconBlockBodies(BeaconBlockBodiesRequest::from_ssz_bytes(&msg.bytes)?)
-            }
-            RPCMethod::BeaconChainState => {
-                RPCRequest::BeaconChainState(BeaconChainStateRequest::from_ssz_bytes(&msg.bytes)?)
-            }
-            RPCMethod::Unknown => return Err(DecodeError::UnknownRPCMethod),
-        };
+#[derive(Encode, Decode)]
+struct SszResponse {
+    id: RequestId,
+    result: RPCResponse,
+}
 
-        Ok(RPCEvent::Request {
-            id: RequestId::from(msg.id),
-            method_id: msg.other,
-            body,
-        })
-    }
-    // we have received a response
-    else {
-        let result = match RPCMethod::from(msg.other) {
-            RPCMethod::Hello => RPCResponse::Hello(HelloMessage::from_ssz_bytes(&msg.bytes)?),
-            RPCMethod::BeaconBlockRoots => {
-                RPCResponse::BeaconBlockRoots(BeaconBlockRootsResponse::from_ssz_bytes(&msg.bytes)?)
-            }
-            RPCMethod::BeaconBlockHeaders => RPCResponse::BeaconBlockHeaders(
-                BeaconBlockHeadersResponse::from_ssz_bytes(&msg.bytes)?,
-            ),
-            RPCMethod::BeaconBlockBodies => RPCResponse::BeaconBlockBodies(
-                BeaconBlockBodiesResponse::from_ssz_bytes(&msg.bytes)?,
-            ),
-            RPCMethod::BeaconChainState => {
-                RPCResponse::BeaconChainState(BeaconChainStateResponse::from_ssz_bytes(&msg.bytes)?)
+impl From<RPCEvent> for SszContainer {
+    fn from(event: RPCEvent) -> Self {
+        match event {
+            RPCEvent::Request { id, body, .. } => SszContainer::Request(SszRequest { id, body }),
+            RPCEvent::Response { id, result, .. } => {
+                SszContainer::Response(SszResponse { id, result })
             }
-            // We should never receive a goodbye response; it is invalid.
-            RPCMethod::Goodbye => return Err(DecodeError::UnknownRPCMethod),
-            RPCMethod::Unknown => return Err(DecodeError::UnknownRPCMethod),
-        };
-
-        Ok(RPCEvent::Response {
-            id: RequestId::from(msg.id),
-            method_id: msg.other,
-            result,
-        })
+        }
     }
 }
 
+impl From<SszContainer> for RPCEvent {
+    fn from(container: SszContainer) -> Self {
+        match container {
+            SszContainer::Request(SszRequest { id, body }) => RPCEvent::Request {
+                id,
+                method_id: body.method_id(),
+                body,
+            },
+            SszContainer::Response(SszResponse { id, result }) => RPCEvent::Response {
+                id,
+                method_id: result.method_id(),
+                result,
+            },
+        }
+    }
+}
+
+/// Decodes an `RPCEvent` from the SSZ bytes of a packet received from a peer.
+pub fn decode(packet: Vec<u8>) -> Result<RPCEvent, DecodeError> {
+    Ok(SszContainer::from_ssz_bytes(&packet)?.into())
+}
+
 impl<TSocket> OutboundUpgrade<TSocket> for RPCEvent
 where
     TSocket: AsyncWrite,
@@ -197,62 +186,15 @@ where
 
     #[inline]
     fn upgrade_outbound(self, socket: upgrade::Negotiated<TSocket>, _: Self::Info) -> Self::Future {
-        let bytes = ssz_encode(&self);
+        let bytes = SszContainer::from(self).as_ssz_bytes();
         upgrade::write_one(socket, bytes)
     }
 }
 
-impl Encode for RPCEvent {
-    fn is_ssz_fixed_len() -> bool {
-        false
-    }
-
-    fn ssz_append(&self, buf: &mut Vec<u8>) {
-        let container = match self {
-            RPCEvent::Request {
-                id,
-                method_id,
-                body,
-            } => SszContainer {
-                is_request: true,
-                id: (*id).into(),
-                other: *method_id,
-                bytes: match body {
-                    RPCRequest::Hello(body) => body.as_ssz_bytes(),
-                    RPCRequest::Goodbye(body) => body.as_ssz_bytes(),
-                    RPCRequest::BeaconBlockRoots(body) => body.as_ssz_bytes(),
-                    RPCRequest::BeaconBlockHeaders(body) => body.as_ssz_bytes(),
-                    RPCRequest::BeaconBlockBodies(body) => body.as_ssz_bytes(),
-                    RPCRequest::BeaconChainState(body) => body.as_ssz_bytes(),
-                },
-            },
-            RPCEvent::Response {
-                id,
-                method_id,
-                result,
-            } => SszContainer {
-                is_request: false,
-                id: (*id).into(),
-                other: *method_id,
-                bytes: match result {
-                    RPCResponse::Hello(response) => response.as_ssz_bytes(),
-                    RPCResponse::BeaconBlockRoots(response) => response.as_ssz_bytes(),
-                    RPCResponse::BeaconBlockHeaders(response) => response.as_ssz_bytes(),
-                    RPCResponse::BeaconBlockBodies(response) => response.as_ssz_bytes(),
-                    RPCResponse::BeaconChainState(response) => response.as_ssz_bytes(),
-                },
-            },
-        };
-
-        container.ssz_append(buf)
-    }
-}
-
 #[derive(Debug)]
 pub enum DecodeError {
     ReadError(upgrade::ReadOneError),
     SSZDecodeError(ssz::DecodeError),
-    UnknownRPCMethod,
 }
 
 impl From<upgrade::ReadOneError> for DecodeError {
@@ -268,3 +210,137 @@ impl From<ssz::DecodeError> for DecodeError {
         DecodeError::SSZDecodeError(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlock, Epoch, EthSpec, Hash256, MainnetEthSpec, Slot};
+
+    fn hello() -> HelloMessage {
+        HelloMessage {
+            network_id: 2,
+            latest_finalized_root: Hash256::from_low_u64_be(1),
+            latest_finalized_epoch: Epoch::new(3),
+            best_root: Hash256::from_low_u64_be(4),
+            best_slot: Slot::new(5),
+        }
+    }
+
+    /// Encodes `event` as it is sent to a peer, decodes it and checks the result re-encodes to the
+    /// same bytes.
+    fn round_trip(event: RPCEvent) -> Vec<u8> {
+        let bytes = SszContainer::from(event.clone()).as_ssz_bytes();
+        let decoded = decode(bytes.clone()).expect("should decode");
+
+        match (&event, &decoded) {
+            (
+                RPCEvent::Request { id, method_id, .. },
+                RPCEvent::Request {
+                    id: decoded_id,
+                    method_id: decoded_method_id,
+                    ..
+                },
+            )
+            | (
+                RPCEvent::Response { id, method_id, .. },
+                RPCEvent::Response {
+                    id: decoded_id,
+                    method_id: decoded_method_id,
+                    ..
+                },
+            ) => {
+                assert_eq!(id, decoded_id);
+                assert_eq!(method_id, decoded_method_id);
+            }
+            _ => panic!("decoded {:?} from {:?}", decoded, event),
+        }
+
+        assert_eq!(SszContainer::from(decoded).as_ssz_bytes(), bytes);
+
+        bytes
+    }
+
+    fn request(body: RPCRequest) -> RPCEvent {
+        RPCEvent::Request {
+            id: RequestId::from(42),
+            method_id: body.method_id(),
+            body,
+        }
+    }
+
+    fn response(result: RPCResponse) -> RPCEvent {
+        RPCEvent::Response {
+            id: RequestId::from(42),
+            method_id: result.method_id(),
+            result,
+        }
+    }
+
+    #[test]
+    fn request_round_trip() {
+        let requests = vec![
+            RPCRequest::Hello(hello()),
+            RPCRequest::Goodbye(GoodbyeReason::Fault),
+            RPCRequest::BeaconBlockRoots(BeaconBlockRootsRequest {
+                start_slot: Slot::new(1),
+                count: 10,
+            }),
+            RPCRequest::BeaconBlockHeaders(BeaconBlockHeadersRequest {
+                start_root: Hash256::from_low_u64_be(1),
+                start_slot: Slot::new(1),
+                max_headers: 10,
+                skip_slots: 0,
+            }),
+            RPCRequest::BeaconBlockBodies(BeaconBlockBodiesRequest {
+                block_roots: vec![Hash256::from_low_u64_be(1), Hash256::from_low_u64_be(2)],
+            }),
+            RPCRequest::BeaconChainState(BeaconChainStateRequest { hashes: vec![] }),
+        ];
+
+        for (selector, body) in requests.into_iter().enumerate() {
+            let bytes = round_trip(request(body));
+
+            // A request container, holding a fixed-length id, the offset of the body and then the
+            // body, which is itself a union.
+            assert_eq!(bytes[0], 0);
+            assert_eq!(bytes[1 + 8 + 4], selector as u8);
+        }
+    }
+
+    #[test]
+    fn response_round_trip() {
+        let block = BeaconBlock::empty(&MainnetEthSpec::default_spec());
+
+        let responses = vec![
+            RPCResponse::Hello(hello()),
+            RPCResponse::BeaconBlockRoots(BeaconBlockRootsResponse {
+                roots: vec![BlockRootSlot {
+                    block_root: Hash256::from_low_u64_be(1),
+                    slot: Slot::new(1),
+                }],
+            }),
+            RPCResponse::BeaconBlockHeaders(BeaconBlockHeadersResponse {
+                headers: vec![block.block_header()],
+            }),
+            RPCResponse::BeaconBlockBodies(BeaconBlockBodiesResponse {
+                block_bodies: vec![block.body.clone()],
+            }),
+            RPCResponse::BeaconChainState(BeaconChainStateResponse { values: true }),
+        ];
+
+        for (selector, result) in responses.into_iter().enumerate() {
+            let bytes = round_trip(response(result));
+
+            assert_eq!(bytes[0], 1);
+            assert_eq!(bytes[1 + 8 + 4], selector as u8);
+        }
+    }
+
+    #[test]
+    fn invalid_selector() {
+        let mut bytes = SszContainer::from(request(RPCRequest::Hello(hello()))).as_ssz_bytes();
+        bytes[0] = 2;
+
+        assert!(decode(bytes).is_err());
+    }
+}
@@ -9,7 +9,10 @@ use iron::{
 use persistent::Read;
 use router::Router;
 use serde_json::json;
+use ssz::Decode;
 use std::sync::Arc;
+use store::Store;
+use types::{BeaconStateView, Epoch, Hash256, Validator};
 
 /// Yields a handler for the HTTP API.
 pub fn build_handler<T: BeaconChainTypes + 'static>(
@@ -18,6 +21,8 @@ pub fn build_handler<T: BeaconChainTypes + 'static>(
     let mut router = Router::new();
 
     router.get("/node/fork", handle_fork::<T>, "fork");
+    router.get("/beacon/rewards/:epoch", handle_rewards::<T>, "rewards");
+    router.get("/beacon/state/:root", handle_state::<T>, "state");
 
     let mut chain = Chain::new(router);
 
@@ -69,3 +74,93 @@ fn handle_fork<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<R
 
     Ok(Response::with((Status::Ok, response.to_string())))
 }
+
+/// Returns the `RewardsReport` for the canonical transition out of the given epoch, see
+/// `BeaconChain::get_rewards_report`.
+fn handle_rewards<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let epoch = match req
+        .extensions
+        .get::<Router>()
+        .and_then(|params| params.find("epoch"))
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+    {
+        Some(epoch) => Epoch::new(epoch),
+        None => return Ok(Response::with((Status::BadRequest, "Invalid epoch"))),
+    };
+
+    match beacon_chain.get_rewards_report(epoch) {
+        Ok(Some(report)) => Ok(Response::with((Status::Ok, json!(report).to_string()))),
+        Ok(None) => Ok(Response::with((Status::NotFound, "No report for epoch"))),
+        Err(e) => Ok(Response::with((
+            Status::InternalServerError,
+            format!("Unable to read report: {:?}", e),
+        ))),
+    }
+}
+
+/// Returns a summary of the state with the given root, see `BeaconChain::get_state`.
+///
+/// Recent states are read from the in-memory state cache, older states from the database.
+fn handle_state<T: BeaconChainTypes + 'static>(req: &mut Request) -> IronResult<Response> {
+    let beacon_chain = req
+        .get::<Read<BeaconChainKey<T>>>()
+        .map_err(map_persistent_err_to_500)?;
+
+    let state_root = match req
+        .extensions
+        .get::<Router>()
+        .and_then(|params| params.find("root"))
+        .and_then(|root| root.trim_start_matches("0x").parse::<Hash256>().ok())
+    {
+        Some(root) => root,
+        None => return Ok(Response::with((Status::BadRequest, "Invalid state root"))),
+    };
+
+    // Read the summary through a view of the stored bytes, rather than decoding the entire state.
+    // States which are only in the state cache (i.e., not yet stored) are cloned from the cache.
+    let summary = match beacon_chain.store.read_state_view(
+        &state_root,
+        |view: &BeaconStateView<T::EthSpec>| {
+            let validator_count =
+                view.validator_registry_bytes()?.len() / <Validator as Decode>::ssz_fixed_len();
+
+            Ok(json!({
+                "slot": view.slot()?,
+                "fork": view.fork()?,
+                "validator_count": validator_count,
+                "current_justified_epoch": view.current_justified_epoch()?,
+                "finalized_epoch": view.finalized_epoch()?,
+            }))
+        },
+    ) {
+        Ok(Some(summary)) => Ok(Some(summary)),
+        Ok(None) => beacon_chain
+            .get_state(&state_root)
+            .map(|state| {
+                state.map(|state| {
+                    json!({
+                        "slot": state.slot,
+                        "fork": state.fork,
+                        "validator_count": state.validator_registry.len(),
+                        "current_justified_epoch": state.current_justified_epoch,
+                        "finalized_epoch": state.finalized_epoch,
+                    })
+                })
+            })
+            .map_err(|e| format!("{:?}", e)),
+        Err(e) => Err(format!("{:?}", e)),
+    };
+
+    match summary {
+        Ok(Some(response)) => Ok(Response::with((Status::Ok, response.to_string()))),
+        Ok(None) => Ok(Response::with((Status::NotFound, "Unknown state root"))),
+        Err(e) => Ok(Response::with((
+            Status::InternalServerError,
+            format!("Unable to read state: {}", e),
+        ))),
+    }
+}
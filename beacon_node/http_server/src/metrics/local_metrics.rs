@@ -75,8 +75,6 @@ impl LocalMetrics {
 
     /// Update the metrics in `self` to the latest values.
     pub fn update<T: BeaconChainTypes>(&self, beacon_chain: &BeaconChain<T>, db_path: &PathBuf) {
-        let state = &beacon_chain.head().beacon_state;
-
         let present_slot = beacon_chain
             .slot_clock
             .present_slot()
@@ -86,17 +84,24 @@ impl LocalMetrics {
         self.present_epoch
             .set(present_slot.epoch(T::EthSpec::slots_per_epoch()).as_u64() as i64);
 
-        self.best_slot.set(state.slot.as_u64() as i64);
-        self.validator_count
-            .set(state.validator_registry.len() as i64);
-        self.justified_epoch
-            .set(state.current_justified_epoch.as_u64() as i64);
-        self.finalized_epoch
-            .set(state.finalized_epoch.as_u64() as i64);
-        if SHOULD_SUM_VALIDATOR_BALANCES {
-            self.validator_balances_sum
-                .set(state.balances.iter().sum::<u64>() as i64);
+        // Read the fields of the head state under the read lock, rather than cloning the state.
+        {
+            let head = beacon_chain.head();
+            let state = &head.beacon_state;
+
+            self.best_slot.set(state.slot.as_u64() as i64);
+            self.validator_count
+                .set(state.validator_registry.len() as i64);
+            self.justified_epoch
+                .set(state.current_justified_epoch.as_u64() as i64);
+            self.finalized_epoch
+                .set(state.finalized_epoch.as_u64() as i64);
+            if SHOULD_SUM_VALIDATOR_BALANCES {
+                self.validator_balances_sum
+                    .set(state.balances.iter().sum::<u64>() as i64);
+            }
         }
+
         let db_size = File::open(db_path)
             .and_then(|f| f.metadata())
             .and_then(|m| Ok(m.len()))
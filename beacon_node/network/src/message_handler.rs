@@ -216,6 +216,10 @@ impl<T: BeaconChainTypes + 'static> MessageHandler<T> {
                 self.sync
                     .on_attestation_gossip(peer_id, message, &mut self.network_context)
             }
+            PubsubMessage::VoluntaryExit(message) => {
+                self.sync
+                    .on_voluntary_exit_gossip(peer_id, message, &mut self.network_context)
+            }
         }
     }
 }
@@ -13,13 +13,15 @@ use slog::{debug, info, o, trace};
 use std::marker::PhantomData;
 use std::sync::Arc;
 use tokio::runtime::TaskExecutor;
-use types::Topic;
+use types::{Topic, TopicBuilder};
 
 /// Service that handles communication between internal services and the eth2_libp2p network service.
 pub struct Service<T: BeaconChainTypes> {
     //libp2p_service: Arc<Mutex<LibP2PService>>,
     _libp2p_exit: oneshot::Sender<()>,
     network_send: crossbeam_channel::Sender<NetworkMessage>,
+    /// The gossipsub topics subscribed to by the libp2p service.
+    topics: Vec<Topic>,
     _phantom: PhantomData<T>, //message_handler: MessageHandler,
                               //message_handler_send: Sender<HandlerMessage>
 }
@@ -42,6 +44,12 @@ impl<T: BeaconChainTypes + 'static> Service<T> {
             message_handler_log,
         )?;
 
+        let topics = config
+            .topics
+            .iter()
+            .map(|topic| TopicBuilder::new(topic.clone()).build())
+            .collect();
+
         // launch libp2p service
         let libp2p_log = log.new(o!("Service" => "Libp2p"));
         let libp2p_service = LibP2PService::new(config.clone(), libp2p_log)?;
@@ -57,12 +65,18 @@ impl<T: BeaconChainTypes + 'static> Service<T> {
         let network_service = Service {
             _libp2p_exit: libp2p_exit,
             network_send: network_send.clone(),
+            topics,
             _phantom: PhantomData,
         };
 
         Ok((Arc::new(network_service), network_send))
     }
 
+    /// Returns the gossipsub topics on which messages from this node should be published.
+    pub fn topics(&self) -> Vec<Topic> {
+        self.topics.clone()
+    }
+
     // TODO: Testing only
     pub fn send_message(&self) {
         self.network_send
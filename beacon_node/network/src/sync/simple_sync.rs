@@ -11,6 +11,7 @@ use std::time::Duration;
 use store::Store;
 use types::{
     Attestation, BeaconBlock, BeaconBlockBody, BeaconBlockHeader, Epoch, EthSpec, Hash256, Slot,
+    VoluntaryExit,
 };
 
 /// The number of slots that we can import blocks ahead of us, before going into full Sync mode.
@@ -570,6 +571,23 @@ impl<T: BeaconChainTypes> SimpleSync<T> {
         }
     }
 
+    /// Process a gossip message declaring a new voluntary exit.
+    ///
+    /// Valid exits are added to the operation pool for inclusion in a future block.
+    pub fn on_voluntary_exit_gossip(
+        &mut self,
+        _peer_id: PeerId,
+        msg: VoluntaryExit,
+        _network: &mut NetworkContext,
+    ) {
+        match self.chain.process_voluntary_exit(msg) {
+            Ok(()) => info!(self.log, "ImportedVoluntaryExit"; "source" => "gossip"),
+            Err(e) => {
+                warn!(self.log, "InvalidVoluntaryExit"; "source" => "gossip", "error" => format!("{:?}", e))
+            }
+        }
+    }
+
     /// Iterate through the `import_queue` and process any complete blocks.
     ///
     /// If a block is successfully processed it is removed from the queue, otherwise it remains in
@@ -3,11 +3,13 @@ mod beacon_block;
 mod beacon_node;
 pub mod config;
 mod validator;
+mod voluntary_exit;
 
 use self::attestation::AttestationServiceInstance;
 use self::beacon_block::BeaconBlockServiceInstance;
 use self::beacon_node::BeaconNodeServiceInstance;
 use self::validator::ValidatorServiceInstance;
+use self::voluntary_exit::VoluntaryExitServiceInstance;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 pub use config::Config as RPCConfig;
 use futures::Future;
@@ -15,16 +17,18 @@ use grpcio::{Environment, ServerBuilder};
 use network::NetworkMessage;
 use protos::services_grpc::{
     create_attestation_service, create_beacon_block_service, create_beacon_node_service,
-    create_validator_service,
+    create_validator_service, create_voluntary_exit_service,
 };
 use slog::{info, o, warn};
 use std::sync::Arc;
 use tokio::runtime::TaskExecutor;
+use types::Topic;
 
 pub fn start_server<T: BeaconChainTypes + Clone + 'static>(
     config: &RPCConfig,
     executor: &TaskExecutor,
     network_chan: crossbeam_channel::Sender<NetworkMessage>,
+    topics: Vec<Topic>,
     beacon_chain: Arc<BeaconChain<T>>,
     log: &slog::Logger,
 ) -> exit_future::Signal {
@@ -61,17 +65,27 @@ pub fn start_server<T: BeaconChainTypes + Clone + 'static>(
     let attestation_service = {
         let instance = AttestationServiceInstance {
             chain: beacon_chain.clone(),
-            network_chan,
+            network_chan: network_chan.clone(),
             log: log.clone(),
         };
         create_attestation_service(instance)
     };
+    let voluntary_exit_service = {
+        let instance = VoluntaryExitServiceInstance {
+            chain: beacon_chain.clone(),
+            network_chan,
+            topics,
+            log: log.clone(),
+        };
+        create_voluntary_exit_service(instance)
+    };
 
     let mut server = ServerBuilder::new(env)
         .register_service(beacon_block_service)
         .register_service(validator_service)
         .register_service(beacon_node_service)
         .register_service(attestation_service)
+        .register_service(voluntary_exit_service)
         .bind(config.listen_address.to_string(), config.port)
         .build()
         .unwrap();
@@ -2,12 +2,16 @@ use beacon_chain::{BeaconChain, BeaconChainTypes};
 use bls::PublicKey;
 use futures::Future;
 use grpcio::{RpcContext, RpcStatus, RpcStatusCode, UnarySink};
-use protos::services::{ActiveValidator, GetDutiesRequest, GetDutiesResponse, ValidatorDuty};
+use protos::services::{
+    ActiveValidator, GetDutiesRequest, GetDutiesResponse, GetValidatorIndicesResponse,
+    GetValidatorStatusesResponse, ValidatorDuty, ValidatorIndex, ValidatorLivenessRequest,
+    ValidatorLivenessResponse, ValidatorStatus, Validators,
+};
 use protos::services_grpc::ValidatorService;
 use slog::{trace, warn};
 use ssz::Decode;
 use std::sync::Arc;
-use types::{Epoch, EthSpec, RelativeEpoch};
+use types::{ChainSpec, Epoch, EthSpec, RelativeEpoch, Validator};
 
 #[derive(Clone)]
 pub struct ValidatorServiceInstance<T: BeaconChainTypes> {
@@ -169,4 +173,208 @@ impl<T: BeaconChainTypes> ValidatorService for ValidatorServiceInstance<T> {
             .map_err(move |e| println!("failed to reply {:?}: {:?}", req, e));
         ctx.spawn(f)
     }
+
+    /// For a list of validator public keys, this function returns whether or not each validator
+    /// has been seen attesting or proposing a block during the requested epoch.
+    ///
+    /// Public keys that are not in the validator registry are reported as not live.
+    fn get_validator_liveness(
+        &mut self,
+        ctx: RpcContext,
+        req: ValidatorLivenessRequest,
+        sink: UnarySink<ValidatorLivenessResponse>,
+    ) {
+        trace!(self.log, "RPC request"; "endpoint" => "GetValidatorLiveness", "epoch" => req.get_epoch());
+
+        let epoch = Epoch::from(req.get_epoch());
+
+        // Resolve the public keys into validator indices, dropping the lock on the state
+        // afterwards.
+        let validator_indices: Result<Vec<Option<usize>>, String> = {
+            let state = self.chain.current_state();
+
+            req.get_validators()
+                .get_public_keys()
+                .iter()
+                .map(|validator_pk| {
+                    let public_key = PublicKey::from_ssz_bytes(validator_pk)
+                        .map_err(|_| "Invalid public_key".to_string())?;
+                    state
+                        .get_validator_index(&public_key)
+                        .map_err(|e| format!("Beacon state error {:?}", e))
+                })
+                .collect()
+        };
+
+        let liveness = validator_indices.and_then(|validator_indices| {
+            let known_indices: Vec<usize> = validator_indices.iter().filter_map(|i| *i).collect();
+
+            let mut known_liveness = self
+                .chain
+                .validator_liveness(epoch, &known_indices)
+                .map_err(|e| format!("Unable to determine liveness: {:?}", e))?
+                .into_iter();
+
+            // Unknown validators cannot have been seen on chain.
+            Ok(validator_indices
+                .iter()
+                .map(|i| i.and_then(|_| known_liveness.next()).unwrap_or(false))
+                .collect::<Vec<bool>>())
+        });
+
+        let liveness = match liveness {
+            Ok(v) => v,
+            Err(e) => {
+                let log_clone = self.log.clone();
+                let f = sink
+                    .fail(RpcStatus::new(RpcStatusCode::FailedPrecondition, Some(e)))
+                    .map_err(move |e| warn!(log_clone, "Failed to reply {:?}: {:?}", req, e));
+                return ctx.spawn(f);
+            }
+        };
+
+        let mut resp = ValidatorLivenessResponse::new();
+        resp.set_is_live(liveness);
+
+        let log_clone = self.log.clone();
+        let f = sink
+            .success(resp)
+            .map_err(move |e| warn!(log_clone, "Failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+
+    /// For a list of validator public keys, this function returns the index of each validator in
+    /// the validator registry, if any.
+    fn get_validator_indices(
+        &mut self,
+        ctx: RpcContext,
+        req: Validators,
+        sink: UnarySink<GetValidatorIndicesResponse>,
+    ) {
+        trace!(self.log, "RPC request"; "endpoint" => "GetValidatorIndices");
+
+        let validator_indices: Result<Vec<ValidatorIndex>, String> = {
+            let state = self.chain.current_state();
+
+            req.get_public_keys()
+                .iter()
+                .map(|validator_pk| {
+                    let public_key = PublicKey::from_ssz_bytes(validator_pk)
+                        .map_err(|_| "Invalid public_key".to_string())?;
+
+                    let mut validator_index = ValidatorIndex::new();
+                    match state.get_validator_index(&public_key) {
+                        Ok(Some(index)) => validator_index.set_index(index as u64),
+                        // index not present in registry
+                        Ok(None) => validator_index.set_none(false),
+                        Err(e) => return Err(format!("Beacon state error {:?}", e)),
+                    };
+
+                    Ok(validator_index)
+                })
+                .collect()
+        };
+
+        let validator_indices = match validator_indices {
+            Ok(v) => v,
+            Err(e) => {
+                let log_clone = self.log.clone();
+                let f = sink
+                    .fail(RpcStatus::new(RpcStatusCode::FailedPrecondition, Some(e)))
+                    .map_err(move |e| warn!(log_clone, "Failed to reply {:?}: {:?}", req, e));
+                return ctx.spawn(f);
+            }
+        };
+
+        let mut resp = GetValidatorIndicesResponse::new();
+        resp.set_validator_indices(validator_indices.into());
+
+        let log_clone = self.log.clone();
+        let f = sink
+            .success(resp)
+            .map_err(move |e| warn!(log_clone, "Failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+
+    /// For a list of validator public keys, returns the registry index, balance and status of
+    /// each validator. Unknown validators are reported with `known == false`.
+    fn get_validator_statuses(
+        &mut self,
+        ctx: RpcContext,
+        req: Validators,
+        sink: UnarySink<GetValidatorStatusesResponse>,
+    ) {
+        trace!(self.log, "RPC request"; "endpoint" => "GetValidatorStatuses");
+
+        let statuses: Result<Vec<ValidatorStatus>, String> = {
+            let spec = &self.chain.spec;
+            let state = self.chain.current_state();
+            let epoch = state.current_epoch();
+
+            req.get_public_keys()
+                .iter()
+                .map(|validator_pk| {
+                    let public_key = PublicKey::from_ssz_bytes(validator_pk)
+                        .map_err(|_| "Invalid public_key".to_string())?;
+
+                    let mut status = ValidatorStatus::new();
+                    match state.get_validator_index(&public_key) {
+                        Ok(Some(index)) => {
+                            status.set_known(true);
+                            status.set_index(index as u64);
+                            status.set_balance(state.balances[index]);
+                            status.set_status(
+                                validator_status(&state.validator_registry[index], epoch, spec)
+                                    .to_string(),
+                            );
+                        }
+                        // index not present in registry
+                        Ok(None) => status.set_known(false),
+                        Err(e) => return Err(format!("Beacon state error {:?}", e)),
+                    };
+
+                    Ok(status)
+                })
+                .collect()
+        };
+
+        let statuses = match statuses {
+            Ok(v) => v,
+            Err(e) => {
+                let log_clone = self.log.clone();
+                let f = sink
+                    .fail(RpcStatus::new(RpcStatusCode::FailedPrecondition, Some(e)))
+                    .map_err(move |e| warn!(log_clone, "Failed to reply {:?}: {:?}", req, e));
+                return ctx.spawn(f);
+            }
+        };
+
+        let mut resp = GetValidatorStatusesResponse::new();
+        resp.set_statuses(statuses.into());
+
+        let log_clone = self.log.clone();
+        let f = sink
+            .success(resp)
+            .map_err(move |e| warn!(log_clone, "Failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+}
+
+/// Returns a short description of the lifecycle stage of `validator` at `epoch`.
+fn validator_status(validator: &Validator, epoch: Epoch, spec: &ChainSpec) -> &'static str {
+    if validator.slashed {
+        "slashed"
+    } else if validator.is_withdrawable_at(epoch) {
+        "withdrawable"
+    } else if validator.is_exited_at(epoch) {
+        "exited"
+    } else if validator.is_active_at(epoch) {
+        if validator.exit_epoch != spec.far_future_epoch {
+            "exiting"
+        } else {
+            "active"
+        }
+    } else {
+        "pending"
+    }
 }
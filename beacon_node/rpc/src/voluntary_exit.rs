@@ -0,0 +1,98 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2_libp2p::PubsubMessage;
+use futures::Future;
+use grpcio::{RpcContext, RpcStatus, RpcStatusCode, UnarySink};
+use network::NetworkMessage;
+use protos::services::{PublishVoluntaryExitRequest, PublishVoluntaryExitResponse};
+use protos::services_grpc::VoluntaryExitService;
+use slog::{error, info, trace, warn};
+use ssz::Decode;
+use std::sync::Arc;
+use types::{Topic, VoluntaryExit};
+
+#[derive(Clone)]
+pub struct VoluntaryExitServiceInstance<T: BeaconChainTypes> {
+    pub chain: Arc<BeaconChain<T>>,
+    pub network_chan: crossbeam_channel::Sender<NetworkMessage>,
+    /// The gossipsub topics on which exits are published, see `network::Service::topics`.
+    pub topics: Vec<Topic>,
+    pub log: slog::Logger,
+}
+
+impl<T: BeaconChainTypes> VoluntaryExitService for VoluntaryExitServiceInstance<T> {
+    /// Accept some signed `VoluntaryExit` from a validator, insert it into the operation pool and
+    /// publish it to the network.
+    fn publish_voluntary_exit(
+        &mut self,
+        ctx: RpcContext,
+        req: PublishVoluntaryExitRequest,
+        sink: UnarySink<PublishVoluntaryExitResponse>,
+    ) {
+        trace!(self.log, "Publishing voluntary exit");
+
+        let mut resp = PublishVoluntaryExitResponse::new();
+        let ssz_serialized_exit = req.get_voluntary_exit().get_ssz();
+
+        let exit = match VoluntaryExit::from_ssz_bytes(ssz_serialized_exit) {
+            Ok(v) => v,
+            Err(_) => {
+                let log_clone = self.log.clone();
+                let f = sink
+                    .fail(RpcStatus::new(
+                        RpcStatusCode::InvalidArgument,
+                        Some("Invalid voluntary exit".to_string()),
+                    ))
+                    .map_err(move |_| warn!(log_clone, "failed to reply {:?}", req));
+                return ctx.spawn(f);
+            }
+        };
+
+        match self.chain.process_voluntary_exit(exit.clone()) {
+            Ok(()) => {
+                // Exit was successfully processed.
+                info!(
+                    self.log,
+                    "PublishVoluntaryExit";
+                    "type" => "valid_voluntary_exit",
+                    "validator_index" => exit.validator_index,
+                );
+
+                let message = PubsubMessage::VoluntaryExit(exit);
+
+                // Publish the exit to the p2p network via gossipsub.
+                self.network_chan
+                    .send(NetworkMessage::Publish {
+                        topics: self.topics.clone(),
+                        message: Box::new(message),
+                    })
+                    .unwrap_or_else(|e| {
+                        error!(
+                            self.log,
+                            "PublishVoluntaryExit";
+                            "type" => "failed to publish to gossipsub",
+                            "error" => format!("{:?}", e)
+                        );
+                    });
+
+                resp.set_success(true);
+            }
+            Err(e) => {
+                // Exit was invalid
+                warn!(
+                    self.log,
+                    "PublishVoluntaryExit";
+                    "type" => "invalid_voluntary_exit",
+                    "error" => format!("{:?}", e),
+                );
+                resp.set_success(false);
+                resp.set_msg(format!("InvalidVoluntaryExit: {:?}", e).as_bytes().to_vec());
+            }
+        };
+
+        let error_log = self.log.clone();
+        let f = sink
+            .success(resp)
+            .map_err(move |e| error!(error_log, "failed to reply {:?}: {:?}", req, e));
+        ctx.spawn(f)
+    }
+}
@@ -110,12 +110,49 @@ fn main() {
                 .possible_values(&["mainnet", "minimal"])
                 .default_value("minimal"),
         )
+        .arg(
+            Arg::with_name("spec-yaml")
+                .long("spec-yaml")
+                .value_name("YAML_FILE")
+                .help("Path to a spec-compliant YAML config which replaces the values of the spec constants.")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("recent-genesis")
                 .long("recent-genesis")
                 .short("r")
                 .help("When present, genesis will be within 30 minutes prior. Only for testing"),
         )
+        .arg(
+            Arg::with_name("genesis-time")
+                .long("genesis-time")
+                .value_name("UNIX_SECONDS")
+                .help("The genesis time, in seconds since the UNIX epoch. Only for testing.")
+                .takes_value(true)
+                .conflicts_with("recent-genesis"),
+        )
+        .arg(
+            Arg::with_name("interop")
+                .long("interop")
+                .help("Build genesis from the interop-standard deterministic keypairs and deposits. Only for testing.")
+                .takes_value(false)
+                .requires("validator-count")
+                .requires("genesis-time"),
+        )
+        .arg(
+            Arg::with_name("validator-count")
+                .long("validator-count")
+                .value_name("COUNT")
+                .help("The number of validators in the interop genesis state.")
+                .takes_value(true)
+                .requires("interop"),
+        )
+        .arg(
+            Arg::with_name("rewards-reports")
+                .long("rewards-reports")
+                .help("Store the rewards and penalties of each validator at each epoch transition, serving them at /beacon/rewards/<epoch> on the HTTP API.")
+                .takes_value(false),
+        )
         .get_matches();
 
     let data_dir = match get_data_dir(&matches, PathBuf::from(DEFAULT_DATA_DIR)) {
@@ -6,18 +6,11 @@ fn get_block_bytes<T: Store>(store: &T, root: Hash256) -> Result<Option<Vec<u8>>
 }
 
 fn read_slot_from_block_bytes(bytes: &[u8]) -> Result<Slot, DecodeError> {
-    let end = std::cmp::min(Slot::ssz_fixed_len(), bytes.len());
-
-    Slot::from_ssz_bytes(&bytes[0..end])
+    BeaconBlockView::new(bytes)?.slot()
 }
 
 fn read_previous_block_root_from_block_bytes(bytes: &[u8]) -> Result<Hash256, DecodeError> {
-    let previous_bytes = Slot::ssz_fixed_len();
-    let slice = bytes
-        .get(previous_bytes..previous_bytes + Hash256::ssz_fixed_len())
-        .ok_or_else(|| DecodeError::BytesInvalid("Not enough bytes.".to_string()))?;
-
-    Hash256::from_ssz_bytes(slice)
+    BeaconBlockView::new(bytes)?.previous_block_root()
 }
 
 pub fn get_block_at_preceeding_slot<T: Store>(
@@ -1,7 +1,7 @@
 use crate::*;
 use ssz::{Decode, Encode};
 
-mod beacon_state;
+pub mod beacon_state;
 
 impl StoreItem for BeaconBlock {
     fn db_column() -> DBColumn {
@@ -1,46 +1,49 @@
 use crate::*;
 use ssz::{Decode, DecodeError, Encode};
-use ssz_derive::{Decode, Encode};
+use ssz_derive::{Decode, DecodeView, Encode};
 use std::convert::TryInto;
 use types::beacon_state::{CommitteeCache, CACHED_EPOCHS};
 
 /// A container for storing `BeaconState` components.
-#[derive(Encode, Decode)]
-struct StorageContainer {
-    state_bytes: Vec<u8>,
-    committee_caches_bytes: Vec<Vec<u8>>,
+///
+/// Decodes the bytes produced by `StorageContainerRef`.
+#[derive(Decode, DecodeView)]
+struct StorageContainer<T: EthSpec> {
+    state: BeaconState<T>,
+    committee_caches: Vec<CommitteeCache>,
 }
 
-impl StorageContainer {
-    /// Create a new instance for storing a `BeaconState`.
-    pub fn new<T: EthSpec>(state: &BeaconState<T>) -> Self {
-        let mut committee_caches_bytes = vec![];
-
-        for cache in state.committee_caches[..].iter() {
-            committee_caches_bytes.push(cache.as_ssz_bytes());
-        }
+/// A container for storing a borrowed `BeaconState` and its committee caches, without cloning
+/// or separately encoding them.
+#[derive(Encode)]
+struct StorageContainerRef<'a, T: EthSpec> {
+    state: &'a BeaconState<T>,
+    committee_caches: Vec<&'a CommitteeCache>,
+}
 
+impl<'a, T: EthSpec> StorageContainerRef<'a, T> {
+    /// Create a new instance for storing a `BeaconState`.
+    pub fn new(state: &'a BeaconState<T>) -> Self {
         Self {
-            state_bytes: state.as_ssz_bytes(),
-            committee_caches_bytes,
+            state,
+            committee_caches: state.committee_caches.iter().collect(),
         }
     }
 }
 
-impl<T: EthSpec> TryInto<BeaconState<T>> for StorageContainer {
+impl<T: EthSpec> TryInto<BeaconState<T>> for StorageContainer<T> {
     type Error = Error;
 
     fn try_into(self) -> Result<BeaconState<T>, Error> {
-        let mut state: BeaconState<T> = BeaconState::from_ssz_bytes(&self.state_bytes)?;
+        let mut state = self.state;
+        let mut committee_caches = self.committee_caches.into_iter();
 
         for i in 0..CACHED_EPOCHS {
-            let bytes = &self.committee_caches_bytes.get(i).ok_or_else(|| {
+            state.committee_caches[i] = committee_caches.next().ok_or_else(|| {
                 Error::SszDecodeError(DecodeError::BytesInvalid(
                     "Insufficient committees for BeaconState".to_string(),
                 ))
             })?;
-
-            state.committee_caches[i] = CommitteeCache::from_ssz_bytes(bytes)?;
         }
 
         Ok(state)
@@ -52,9 +55,17 @@ impl<T: EthSpec> StoreItem for BeaconState<T> {
         DBColumn::BeaconState
     }
 
+    /// Writes the state and its committee caches directly into a buffer of the final length,
+    /// rather than encoding the state into an intermediate buffer.
     fn as_store_bytes(&self) -> Vec<u8> {
-        let container = StorageContainer::new(self);
-        container.as_ssz_bytes()
+        let container = StorageContainerRef::new(self);
+
+        let mut bytes = Vec::with_capacity(container.ssz_bytes_len());
+        container
+            .ssz_write(&mut bytes)
+            .expect("Writing to a Vec cannot fail");
+
+        bytes
     }
 
     fn from_store_bytes(bytes: &mut [u8]) -> Result<Self, Error> {
@@ -62,3 +73,98 @@ impl<T: EthSpec> StoreItem for BeaconState<T> {
         container.try_into()
     }
 }
+
+/// Reads fields of the `BeaconState` stored with `key` through a `BeaconStateView` of its bytes,
+/// without decoding the entire state.
+///
+/// Returns `None` if there is no state stored with `key`.
+pub fn read_state_view<S, E, F, R>(store: &S, key: &Hash256, read: F) -> Result<Option<R>, Error>
+where
+    S: Store,
+    E: EthSpec,
+    F: FnOnce(&BeaconStateView<E>) -> Result<R, DecodeError>,
+{
+    let column = BeaconState::<E>::db_column().into();
+
+    match store.get_bytes(column, key.as_bytes())? {
+        Some(bytes) => {
+            let container = StorageContainerView::<E>::new(&bytes)?;
+            let view = BeaconStateView::new(container.state_bytes()?)?;
+
+            Ok(Some(read(&view)?))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::test_utils::TestingBeaconStateBuilder;
+
+    /// The format used prior to streaming encoding, which must remain readable.
+    #[derive(Encode)]
+    struct LegacyStorageContainer {
+        state_bytes: Vec<u8>,
+        committee_caches_bytes: Vec<Vec<u8>>,
+    }
+
+    fn state() -> BeaconState<MinimalEthSpec> {
+        let spec = MinimalEthSpec::default_spec();
+        let builder = TestingBeaconStateBuilder::from_deterministic_keypairs(8, &spec);
+        let (mut state, _keypairs) = builder.build();
+        // Only the committee caches are stored, so build no others.
+        state
+            .build_committee_cache(RelativeEpoch::Previous, &spec)
+            .unwrap();
+        state
+            .build_committee_cache(RelativeEpoch::Current, &spec)
+            .unwrap();
+        state
+            .build_committee_cache(RelativeEpoch::Next, &spec)
+            .unwrap();
+        state
+    }
+
+    #[test]
+    fn round_trip() {
+        let state = state();
+        let mut bytes = state.as_store_bytes();
+
+        assert_eq!(BeaconState::from_store_bytes(&mut bytes), Ok(state));
+    }
+
+    #[test]
+    fn state_view() {
+        let state = state();
+        let state_root = Hash256::from_slice(&[42; 32]);
+
+        let store = MemoryStore::open();
+        store.put(&state_root, &state).unwrap();
+
+        let read = |view: &BeaconStateView<MinimalEthSpec>| Ok((view.slot()?, view.balances()?));
+        assert_eq!(
+            read_state_view(&store, &state_root, read),
+            Ok(Some((state.slot, state.balances.clone())))
+        );
+
+        let unknown_root = Hash256::from_slice(&[43; 32]);
+        assert_eq!(read_state_view(&store, &unknown_root, read), Ok(None));
+    }
+
+    #[test]
+    fn matches_legacy_format() {
+        let state = state();
+
+        let legacy = LegacyStorageContainer {
+            state_bytes: state.as_ssz_bytes(),
+            committee_caches_bytes: state
+                .committee_caches
+                .iter()
+                .map(Encode::as_ssz_bytes)
+                .collect(),
+        };
+
+        assert_eq!(state.as_store_bytes(), legacy.as_ssz_bytes());
+    }
+}
@@ -58,6 +58,18 @@ pub trait Store: Sync + Send + Sized {
         block_at_slot::get_block_at_preceeding_slot(self, slot, start_block_root)
     }
 
+    /// Reads fields of the state with root `state_root` through a `BeaconStateView` of its stored
+    /// bytes, without decoding the entire state (e.g., the public key of every validator).
+    ///
+    /// Returns `None` if the state is not in `self`.
+    fn read_state_view<E, F, R>(&self, state_root: &Hash256, read: F) -> Result<Option<R>, Error>
+    where
+        E: EthSpec,
+        F: FnOnce(&BeaconStateView<E>) -> Result<R, ssz::DecodeError>,
+    {
+        impls::beacon_state::read_state_view(self, state_root, read)
+    }
+
     /// Retrieve some bytes in `column` with `key`.
     fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
 
@@ -76,6 +88,7 @@ pub enum DBColumn {
     BeaconBlock,
     BeaconState,
     BeaconChain,
+    RewardsReport,
 }
 
 impl<'a> Into<&'a str> for DBColumn {
@@ -85,6 +98,7 @@ impl<'a> Into<&'a str> for DBColumn {
             DBColumn::BeaconBlock => &"blk",
             DBColumn::BeaconState => &"ste",
             DBColumn::BeaconChain => &"bch",
+            DBColumn::RewardsReport => &"rwd",
         }
     }
 }
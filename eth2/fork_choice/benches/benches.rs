@@ -65,7 +65,14 @@ fn find_head(c: &mut Criterion) {
     c.bench(
         &format!("{}_blocks", chain_length),
         Benchmark::new("find_head", move |b| {
-            b.iter(|| fc.find_head(&builder.genesis_root(), &spec).unwrap())
+            b.iter(|| {
+                fc.find_head(
+                    &builder.genesis_root(),
+                    |root| builder.get_state(root),
+                    &spec,
+                )
+                .unwrap()
+            })
         })
         .sample_size(10),
     );
@@ -1,7 +1,7 @@
 use fork_choice::{test_utils::TestingForkChoiceBuilder, ForkChoice, OptimizedLMDGhost};
 use std::sync::Arc;
 use store::{MemoryStore, Store};
-use types::{BeaconBlock, ChainSpec, EthSpec, Hash256, MainnetEthSpec};
+use types::{ChainSpec, EthSpec, MainnetEthSpec};
 
 fn main() {
     let validator_count = 16;
@@ -21,20 +21,25 @@ fn main() {
 
     println!("Running {} times...", repetitions);
     for fc in fork_choosers {
-        do_thing(fc, &builder.chain, builder.genesis_root(), spec);
+        do_thing(fc, &builder, spec);
     }
 }
 
 #[inline(never)]
-fn do_thing<F: ForkChoice<S>, S: Store>(
+fn do_thing<F: ForkChoice<S, MainnetEthSpec>, S: Store>(
     mut fc: F,
-    chain: &[(Hash256, BeaconBlock)],
-    genesis_root: Hash256,
+    builder: &TestingForkChoiceBuilder<S, MainnetEthSpec>,
     spec: &ChainSpec,
 ) {
-    for (root, block) in chain.iter().skip(1) {
+    for (root, block) in builder.chain.iter().skip(1) {
         fc.add_block(block, root, spec).unwrap();
     }
 
-    let _head = fc.find_head(&genesis_root, spec).unwrap();
+    let _head = fc
+        .find_head(
+            &builder.genesis_root(),
+            |root| builder.get_state(root),
+            spec,
+        )
+        .unwrap();
 }
@@ -52,7 +52,7 @@ impl<T: Store, E: EthSpec> BitwiseLMDGhost<T, E> {
     /// weighted votes.
     pub fn get_latest_votes(
         &self,
-        state_root: &Hash256,
+        current_state: &BeaconState<E>,
         block_slot: Slot,
         spec: &ChainSpec,
     ) -> Result<HashMap<Hash256, u64>, ForkChoiceError> {
@@ -61,12 +61,6 @@ impl<T: Store, E: EthSpec> BitwiseLMDGhost<T, E> {
         // FORK_CHOICE_BALANCE_INCREMENT
         // build a hashmap of block_hash to weighted votes
         let mut latest_votes: HashMap<Hash256, u64> = HashMap::new();
-        // gets the current weighted votes
-        let current_state: BeaconState<E> = self
-            .store
-            .get(&state_root)?
-            .ok_or_else(|| ForkChoiceError::MissingBeaconState(*state_root))?;
-
         let active_validator_indices =
             current_state.get_active_validator_indices(block_slot.epoch(E::slots_per_epoch()));
 
@@ -215,7 +209,7 @@ impl<T: Store, E: EthSpec> BitwiseLMDGhost<T, E> {
     }
 }
 
-impl<T: Store, E: EthSpec> ForkChoice<T> for BitwiseLMDGhost<T, E> {
+impl<T: Store, E: EthSpec> ForkChoice<T, E> for BitwiseLMDGhost<T, E> {
     fn new(store: Arc<T>) -> Self {
         BitwiseLMDGhost {
             cache: HashMap::new(),
@@ -311,11 +305,15 @@ impl<T: Store, E: EthSpec> ForkChoice<T> for BitwiseLMDGhost<T, E> {
     }
 
     /// Perform lmd_ghost on the current chain to find the head.
-    fn find_head(
+    fn find_head<F>(
         &mut self,
         justified_block_start: &Hash256,
+        get_state: F,
         spec: &ChainSpec,
-    ) -> Result<Hash256, ForkChoiceError> {
+    ) -> Result<Hash256, ForkChoiceError>
+    where
+        F: Fn(&Hash256) -> Result<Option<BeaconState<E>>, ForkChoiceError>,
+    {
         debug!(
             "Starting optimised fork choice at block: {}",
             justified_block_start
@@ -331,7 +329,10 @@ impl<T: Store, E: EthSpec> ForkChoice<T> for BitwiseLMDGhost<T, E> {
 
         let mut current_head = *justified_block_start;
 
-        let mut latest_votes = self.get_latest_votes(&state_root, block_slot, spec)?;
+        let state = get_state(&state_root)?
+            .ok_or_else(|| ForkChoiceError::MissingBeaconState(state_root))?;
+
+        let mut latest_votes = self.get_latest_votes(&state, block_slot, spec)?;
 
         // remove any votes that don't relate to our current head.
         latest_votes
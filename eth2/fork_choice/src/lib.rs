@@ -24,7 +24,7 @@ pub mod test_utils;
 
 use std::sync::Arc;
 use store::Error as DBError;
-use types::{BeaconBlock, ChainSpec, Hash256};
+use types::{BeaconBlock, BeaconState, ChainSpec, EthSpec, Hash256};
 
 pub use bitwise_lmd_ghost::BitwiseLMDGhost;
 pub use longest_chain::LongestChain;
@@ -34,7 +34,7 @@ pub use slow_lmd_ghost::SlowLMDGhost;
 /// Defines the interface for Fork Choices. Each Fork choice will define their own data structures
 /// which can be built in block processing through the `add_block` and `add_attestation` functions.
 /// The main fork choice algorithm is specified in `find_head
-pub trait ForkChoice<T>: Send + Sync {
+pub trait ForkChoice<T, E: EthSpec>: Send + Sync {
     /// Create a new `ForkChoice` which reads from `store`.
     fn new(store: Arc<T>) -> Self;
 
@@ -55,12 +55,18 @@ pub trait ForkChoice<T>: Send + Sync {
         spec: &ChainSpec,
     ) -> Result<(), ForkChoiceError>;
     /// The fork-choice algorithm to find the current canonical head of the chain.
+    ///
+    /// The justified state (which weights the votes) is read through `get_state`, allowing the
+    /// caller to provide it from memory rather than reading it from the store.
     // TODO: Remove the justified_start_block parameter and make it internal
-    fn find_head(
+    fn find_head<F>(
         &mut self,
         justified_start_block: &Hash256,
+        get_state: F,
         spec: &ChainSpec,
-    ) -> Result<Hash256, ForkChoiceError>;
+    ) -> Result<Hash256, ForkChoiceError>
+    where
+        F: Fn(&Hash256) -> Result<Option<BeaconState<E>>, ForkChoiceError>;
 }
 
 /// Possible fork choice errors that can occur.
@@ -1,7 +1,7 @@
 use crate::{ForkChoice, ForkChoiceError};
 use std::sync::Arc;
 use store::Store;
-use types::{BeaconBlock, ChainSpec, Hash256, Slot};
+use types::{BeaconBlock, BeaconState, ChainSpec, EthSpec, Hash256, Slot};
 
 pub struct LongestChain<T> {
     /// List of head block hashes
@@ -10,7 +10,7 @@ pub struct LongestChain<T> {
     store: Arc<T>,
 }
 
-impl<T: Store> ForkChoice<T> for LongestChain<T> {
+impl<T: Store, E: EthSpec> ForkChoice<T, E> for LongestChain<T> {
     fn new(store: Arc<T>) -> Self {
         LongestChain {
             head_block_hashes: Vec::new(),
@@ -41,7 +41,10 @@ impl<T: Store> ForkChoice<T> for LongestChain<T> {
         Ok(())
     }
 
-    fn find_head(&mut self, _: &Hash256, _: &ChainSpec) -> Result<Hash256, ForkChoiceError> {
+    fn find_head<F>(&mut self, _: &Hash256, _: F, _: &ChainSpec) -> Result<Hash256, ForkChoiceError>
+    where
+        F: Fn(&Hash256) -> Result<Option<BeaconState<E>>, ForkChoiceError>,
+    {
         let mut head_blocks: Vec<(usize, BeaconBlock)> = vec![];
         /*
          * Load all the head_block hashes from the DB as SszBeaconBlocks.
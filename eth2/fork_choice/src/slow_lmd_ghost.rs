@@ -24,7 +24,7 @@ impl<T: Store, E: EthSpec> SlowLMDGhost<T, E> {
     /// weighted votes.
     pub fn get_latest_votes(
         &self,
-        state_root: &Hash256,
+        current_state: &BeaconState<E>,
         block_slot: Slot,
         spec: &ChainSpec,
     ) -> Result<HashMap<Hash256, u64>, ForkChoiceError> {
@@ -33,12 +33,6 @@ impl<T: Store, E: EthSpec> SlowLMDGhost<T, E> {
         // FORK_CHOICE_BALANCE_INCREMENT
         // build a hashmap of block_hash to weighted votes
         let mut latest_votes: HashMap<Hash256, u64> = HashMap::new();
-        // gets the current weighted votes
-        let current_state: BeaconState<E> = self
-            .store
-            .get(state_root)?
-            .ok_or_else(|| ForkChoiceError::MissingBeaconState(*state_root))?;
-
         let active_validator_indices =
             current_state.get_active_validator_indices(block_slot.epoch(E::slots_per_epoch()));
 
@@ -83,7 +77,7 @@ impl<T: Store, E: EthSpec> SlowLMDGhost<T, E> {
     }
 }
 
-impl<T: Store, E: EthSpec> ForkChoice<T> for SlowLMDGhost<T, E> {
+impl<T: Store, E: EthSpec> ForkChoice<T, E> for SlowLMDGhost<T, E> {
     fn new(store: Arc<T>) -> Self {
         SlowLMDGhost {
             latest_attestation_targets: HashMap::new(),
@@ -157,11 +151,15 @@ impl<T: Store, E: EthSpec> ForkChoice<T> for SlowLMDGhost<T, E> {
     }
 
     /// A very inefficient implementation of LMD ghost.
-    fn find_head(
+    fn find_head<F>(
         &mut self,
         justified_block_start: &Hash256,
+        get_state: F,
         spec: &ChainSpec,
-    ) -> Result<Hash256, ForkChoiceError> {
+    ) -> Result<Hash256, ForkChoiceError>
+    where
+        F: Fn(&Hash256) -> Result<Option<BeaconState<E>>, ForkChoiceError>,
+    {
         debug!("Running LMD Ghost Fork-choice rule");
         let start = self
             .store
@@ -170,7 +168,10 @@ impl<T: Store, E: EthSpec> ForkChoice<T> for SlowLMDGhost<T, E> {
 
         let start_state_root = start.state_root;
 
-        let latest_votes = self.get_latest_votes(&start_state_root, start.slot, spec)?;
+        let start_state = get_state(&start_state_root)?
+            .ok_or_else(|| ForkChoiceError::MissingBeaconState(start_state_root))?;
+
+        let latest_votes = self.get_latest_votes(&start_state, start.slot, spec)?;
 
         let mut head_hash = *justified_block_start;
 
@@ -1,4 +1,4 @@
-use crate::ForkChoice;
+use crate::{ForkChoice, ForkChoiceError};
 use std::marker::PhantomData;
 use std::sync::Arc;
 use store::Store;
@@ -30,8 +30,17 @@ impl<S: Store, E: EthSpec> TestingForkChoiceBuilder<S, E> {
         self.chain[0].0
     }
 
+    /// Reads the state with the given root from the `Store`, for use as the `get_state` argument
+    /// of `ForkChoice::find_head`.
+    pub fn get_state(
+        &self,
+        state_root: &Hash256,
+    ) -> Result<Option<BeaconState<E>>, ForkChoiceError> {
+        Ok(self.store.get(state_root)?)
+    }
+
     /// Return a new `ForkChoice` instance with a chain stored in it's `Store`.
-    pub fn build<F: ForkChoice<S>>(&self) -> F {
+    pub fn build<F: ForkChoice<S, E>>(&self) -> F {
         F::new(self.store.clone())
     }
 }
@@ -12,6 +12,7 @@ use std::{fs::File, io::prelude::*, path::PathBuf};
 use types::test_utils::TestingBeaconStateBuilder;
 use types::{
     BeaconBlock, BeaconBlockBody, Eth1Data, EthSpec, Hash256, Keypair, MainnetEthSpec, Slot,
+    VariableList,
 };
 use yaml_rust::yaml;
 
@@ -53,7 +54,7 @@ fn test_longest_chain() {
 }
 
 // run a generic test over given YAML test vectors
-fn test_yaml_vectors<T: ForkChoice<MemoryStore>>(
+fn test_yaml_vectors<T: ForkChoice<MemoryStore, MainnetEthSpec>>(
     yaml_file_path: &str,
     emulated_validators: usize, // the number of validators used to give weights.
 ) {
@@ -74,12 +75,12 @@ fn test_yaml_vectors<T: ForkChoice<MemoryStore>>(
         eth1_data,
         randao_reveal,
         graffiti: [0; 32],
-        proposer_slashings: vec![],
-        attester_slashings: vec![],
-        attestations: vec![],
-        deposits: vec![],
-        voluntary_exits: vec![],
-        transfers: vec![],
+        proposer_slashings: VariableList::empty(),
+        attester_slashings: VariableList::empty(),
+        attestations: VariableList::empty(),
+        deposits: VariableList::empty(),
+        voluntary_exits: VariableList::empty(),
+        transfers: VariableList::empty(),
     };
 
     // process the tests
@@ -162,7 +163,7 @@ fn test_yaml_vectors<T: ForkChoice<MemoryStore>>(
 
         // everything is set up, run the fork choice, using genesis as the head
         let head = fork_choice
-            .find_head(&genesis_hash.unwrap(), &spec)
+            .find_head(&genesis_hash.unwrap(), |root| Ok(store.get(root)?), &spec)
             .unwrap();
 
         // compare the result to the expected test
@@ -199,7 +200,7 @@ fn setup_inital_state<T>(
     num_validators: usize,
 ) -> (T, Arc<MemoryStore>, Hash256)
 where
-    T: ForkChoice<MemoryStore>,
+    T: ForkChoice<MemoryStore, MainnetEthSpec>,
 {
     let store = Arc::new(MemoryStore::open());
 
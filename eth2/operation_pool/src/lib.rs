@@ -12,7 +12,7 @@ use state_processing::per_block_processing::{
     get_slashable_indices_modular, validate_attestation,
     validate_attestation_time_independent_only, verify_attester_slashing, verify_exit,
     verify_exit_time_independent_only, verify_proposer_slashing, verify_transfer,
-    verify_transfer_time_independent_only,
+    verify_transfer_time_independent_only, VerifySignatures,
 };
 use std::collections::{btree_map::Entry, hash_map, BTreeMap, HashMap, HashSet};
 use std::marker::PhantomData;
@@ -169,6 +169,18 @@ impl<T: EthSpec> OperationPool<T> {
         self.attestations.read().values().map(Vec::len).sum()
     }
 
+    /// Get all attestations in the pool that target the given `epoch`, regardless of whether or
+    /// not they are valid for inclusion in a block.
+    pub fn get_attestations_for_epoch(&self, epoch: Epoch) -> Vec<Attestation> {
+        self.attestations
+            .read()
+            .values()
+            .flatten()
+            .filter(|attestation| attestation.data.target_epoch == epoch)
+            .cloned()
+            .collect()
+    }
+
     /// Get a list of attestations for inclusion in a block.
     pub fn get_attestations(&self, state: &BeaconState<T>, spec: &ChainSpec) -> Vec<Attestation> {
         // Attestations for the current fork, which may be from the current or previous epoch.
@@ -282,7 +294,7 @@ impl<T: EthSpec> OperationPool<T> {
     ) -> Result<(), ProposerSlashingValidationError> {
         // TODO: should maybe insert anyway if the proposer is unknown in the validator index,
         // because they could *become* known later
-        verify_proposer_slashing(&slashing, state, spec)?;
+        verify_proposer_slashing(&slashing, state, VerifySignatures::True, spec)?;
         self.proposer_slashings
             .write()
             .insert(slashing.proposer_index, slashing);
@@ -423,7 +435,7 @@ impl<T: EthSpec> OperationPool<T> {
     ) -> Vec<VoluntaryExit> {
         filter_limit_operations(
             self.voluntary_exits.read().values(),
-            |exit| verify_exit(state, exit, spec).is_ok(),
+            |exit| verify_exit(state, exit, VerifySignatures::True, spec).is_ok(),
             spec.max_voluntary_exits,
         )
     }
@@ -459,7 +471,9 @@ impl<T: EthSpec> OperationPool<T> {
         self.transfers
             .read()
             .iter()
-            .filter(|transfer| verify_transfer(state, transfer, spec).is_ok())
+            .filter(|transfer| {
+                verify_transfer(state, transfer, VerifySignatures::True, spec).is_ok()
+            })
             .sorted_by_key(|transfer| std::cmp::Reverse(transfer.fee))
             .take(spec.max_transfers as usize)
             .cloned()
@@ -778,12 +792,15 @@ mod tests {
                     attestation_score(&att1, state)
                 );
 
-                state.current_epoch_attestations.push(PendingAttestation {
-                    aggregation_bitfield: att1.aggregation_bitfield.clone(),
-                    data: att1.data.clone(),
-                    inclusion_delay: 0,
-                    proposer_index: 0,
-                });
+                state
+                    .current_epoch_attestations
+                    .push(PendingAttestation {
+                        aggregation_bitfield: att1.aggregation_bitfield.clone(),
+                        data: att1.data.clone(),
+                        inclusion_delay: 0,
+                        proposer_index: 0,
+                    })
+                    .unwrap();
 
                 assert_eq!(cc.committee.len() - 2, attestation_score(&att2, state));
             }
@@ -5,7 +5,7 @@ use state_processing::{
     per_block_processing::{
         process_attestations, process_attester_slashings, process_deposits, process_eth1_data,
         process_exits, process_proposer_slashings, process_randao, process_transfers,
-        verify_block_signature,
+        verify_block_signature, VerifySignatures,
     },
 };
 use tree_hash::TreeHash;
@@ -48,7 +48,7 @@ pub fn bench_block_processing(
             b.iter_batched(
                 || state.clone(),
                 |mut state| {
-                    process_randao(&mut state, &block, &spec).unwrap();
+                    process_randao(&mut state, &block, VerifySignatures::True, &spec).unwrap();
                     state
                 },
                 criterion::BatchSize::SmallInput,
@@ -83,8 +83,13 @@ pub fn bench_block_processing(
             b.iter_batched(
                 || state.clone(),
                 |mut state| {
-                    process_proposer_slashings(&mut state, &block.body.proposer_slashings, &spec)
-                        .unwrap();
+                    process_proposer_slashings(
+                        &mut state,
+                        &block.body.proposer_slashings,
+                        VerifySignatures::True,
+                        &spec,
+                    )
+                    .unwrap();
                     state
                 },
                 criterion::BatchSize::SmallInput,
@@ -102,8 +107,13 @@ pub fn bench_block_processing(
             b.iter_batched(
                 || state.clone(),
                 |mut state| {
-                    process_attester_slashings(&mut state, &block.body.attester_slashings, &spec)
-                        .unwrap();
+                    process_attester_slashings(
+                        &mut state,
+                        &block.body.attester_slashings,
+                        VerifySignatures::True,
+                        &spec,
+                    )
+                    .unwrap();
                     state
                 },
                 criterion::BatchSize::SmallInput,
@@ -121,7 +131,13 @@ pub fn bench_block_processing(
             b.iter_batched(
                 || state.clone(),
                 |mut state| {
-                    process_attestations(&mut state, &block.body.attestations, &spec).unwrap();
+                    process_attestations(
+                        &mut state,
+                        &block.body.attestations,
+                        VerifySignatures::True,
+                        &spec,
+                    )
+                    .unwrap();
                     state
                 },
                 criterion::BatchSize::SmallInput,
@@ -157,7 +173,13 @@ pub fn bench_block_processing(
             b.iter_batched(
                 || state.clone(),
                 |mut state| {
-                    process_exits(&mut state, &block.body.voluntary_exits, &spec).unwrap();
+                    process_exits(
+                        &mut state,
+                        &block.body.voluntary_exits,
+                        VerifySignatures::True,
+                        &spec,
+                    )
+                    .unwrap();
                     state
                 },
                 criterion::BatchSize::SmallInput,
@@ -175,7 +197,13 @@ pub fn bench_block_processing(
             b.iter_batched(
                 || state.clone(),
                 |mut state| {
-                    process_transfers(&mut state, &block.body.transfers, &spec).unwrap();
+                    process_transfers(
+                        &mut state,
+                        &block.body.transfers,
+                        VerifySignatures::True,
+                        &spec,
+                    )
+                    .unwrap();
                     state
                 },
                 criterion::BatchSize::SmallInput,
@@ -11,6 +11,9 @@ mod block_benching_builder;
 
 pub const VALIDATOR_COUNT: usize = 16_384;
 
+/// The validator count used to measure epoch processing at a realistic mainnet scale.
+pub const LARGE_VALIDATOR_COUNT: usize = 300_000;
+
 // `LOG_LEVEL == "info"` gives handy messages.
 pub const LOG_LEVEL: &str = "info";
 
@@ -92,6 +95,7 @@ pub fn block_processing_reasonable_case(c: &mut Criterion) {
 
 pub fn state_processing(c: &mut Criterion) {
     bench_epoch_processing::bench_epoch_processing_n_validators(c, VALIDATOR_COUNT);
+    bench_epoch_processing::bench_epoch_processing_n_validators(c, LARGE_VALIDATOR_COUNT);
 }
 
 criterion_group!(
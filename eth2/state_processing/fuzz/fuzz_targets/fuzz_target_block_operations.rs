@@ -0,0 +1,165 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+#[macro_use] extern crate lazy_static;
+extern crate arbitrary;
+extern crate state_processing;
+extern crate tree_hash;
+extern crate types;
+
+use arbitrary::{Arbitrary, Unstructured};
+use state_processing::per_block_processing;
+use tree_hash::{SignedRoot, TreeHash};
+use types::test_utils::{TestingBeaconBlockBuilder, TestingBeaconStateBuilder};
+use types::*;
+
+type E = MinimalEthSpec;
+
+const VALIDATOR_COUNT: usize = 64;
+/// The most validators included in a single attester slashing.
+const MAX_SLASHED_ATTESTERS: usize = 16;
+/// The most attestations included by a single `Operation::Attestations`.
+const MAX_ATTESTATIONS_PER_OPERATION: usize = 8;
+
+lazy_static! {
+    static ref SPEC: ChainSpec = E::default_spec();
+    static ref STATE_AND_KEYPAIRS: (BeaconState<E>, Vec<Keypair>) = {
+        let mut builder: TestingBeaconStateBuilder<E> =
+            TestingBeaconStateBuilder::from_deterministic_keypairs(VALIDATOR_COUNT, &SPEC);
+
+        // Start a few epochs after genesis, so that attestations for the previous epoch and
+        // slashings/exits may be valid.
+        builder.teleport_to_slot((E::genesis_epoch() + 4).start_slot(E::slots_per_epoch()) + 1);
+        builder.build_caches(&SPEC).unwrap();
+
+        builder.build()
+    };
+}
+
+/// An operation to include in the fuzzed block.
+///
+/// Validator indices are taken modulo the number of validators and each operation is signed by
+/// the relevant validators, so that most inputs produce well-formed operations which reach the
+/// checks of block processing, unlike arbitrary SSZ bytes (see
+/// `fuzz_target_per_block_processing`).
+///
+/// There is no deposit operation: the state expects no deposits, so any deposit is rejected
+/// before its contents are checked.
+#[derive(Arbitrary, Debug)]
+enum Operation {
+    ProposerSlashing { validator: u64 },
+    AttesterSlashing { validators: Vec<u64> },
+    Attestations { count: u8 },
+    Exit { validator: u64 },
+    Transfer { from: u64, to: u64, amount: u64 },
+}
+
+/// Returns a block for the next slot of the state, containing `operations`.
+///
+/// Operations which would exceed the maximum length of their list in the block are ignored.
+fn build_block(operations: &[Operation]) -> BeaconBlock {
+    let (state, keypairs) = &*STATE_AND_KEYPAIRS;
+    let validator = |index: u64| index % VALIDATOR_COUNT as u64;
+
+    let mut builder = TestingBeaconBlockBuilder::new(&SPEC);
+    builder.set_slot(state.slot);
+    builder.set_previous_block_root(Hash256::from_slice(
+        &state.latest_block_header.signed_root(),
+    ));
+
+    let proposer = state
+        .get_beacon_proposer_index(state.slot, RelativeEpoch::Current, &SPEC)
+        .unwrap();
+    builder.set_randao_reveal::<E>(&keypairs[proposer].sk, &state.fork, &SPEC);
+
+    for operation in operations {
+        let body = &builder.block.body;
+
+        match operation {
+            Operation::ProposerSlashing { validator: index } => {
+                if body.proposer_slashings.len() < MaxProposerSlashings::to_usize() {
+                    let index = validator(*index);
+                    builder.insert_proposer_slashing::<E>(
+                        index,
+                        &keypairs[index as usize].sk,
+                        &state.fork,
+                        &SPEC,
+                    );
+                }
+            }
+            Operation::AttesterSlashing { validators } => {
+                if body.attester_slashings.len() < MaxAttesterSlashings::to_usize() {
+                    let mut indices: Vec<u64> = validators
+                        .iter()
+                        .take(MAX_SLASHED_ATTESTERS)
+                        .map(|index| validator(*index))
+                        .collect();
+                    indices.sort();
+                    indices.dedup();
+
+                    let secret_keys: Vec<&SecretKey> = indices
+                        .iter()
+                        .map(|index| &keypairs[*index as usize].sk)
+                        .collect();
+
+                    builder.insert_attester_slashing(&indices, &secret_keys, &state.fork, &SPEC);
+                }
+            }
+            Operation::Attestations { count } => {
+                let count = std::cmp::min(
+                    1 + *count as usize % MAX_ATTESTATIONS_PER_OPERATION,
+                    MaxAttestations::to_usize() - body.attestations.len(),
+                );
+
+                if count > 0 {
+                    let secret_keys: Vec<&SecretKey> =
+                        keypairs.iter().map(|keypair| &keypair.sk).collect();
+
+                    builder
+                        .insert_attestations(state, &secret_keys, count, &SPEC)
+                        .unwrap();
+                }
+            }
+            Operation::Exit { validator: index } => {
+                if body.voluntary_exits.len() < MaxVoluntaryExits::to_usize() {
+                    let index = validator(*index);
+                    builder.insert_exit(state, index, &keypairs[index as usize].sk, &SPEC);
+                }
+            }
+            Operation::Transfer { from, to, amount } => {
+                if body.transfers.len() < MaxTransfers::to_usize() {
+                    let (from, to) = (validator(*from), validator(*to));
+                    builder.insert_transfer(
+                        state,
+                        from,
+                        to,
+                        *amount,
+                        keypairs[from as usize].clone(),
+                        &SPEC,
+                    );
+                }
+            }
+        }
+    }
+
+    builder.build::<E>(&keypairs[proposer].sk, &state.fork, &SPEC)
+}
+
+// Fuzz per_block_processing() with a block built from arbitrary operations.
+//
+// Errors are expected, however any panic (including arithmetic overflow, when built with debug
+// assertions) is a bug.
+fuzz_target!(|data: &[u8]| {
+    let operations = match Vec::<Operation>::arbitrary_take_rest(Unstructured::new(data)) {
+        Ok(operations) => operations,
+        Err(_) => return,
+    };
+
+    let block = build_block(&operations);
+    let mut state = STATE_AND_KEYPAIRS.0.clone();
+
+    if per_block_processing(&mut state, &block, &SPEC).is_ok() {
+        // A valid block must leave the state in a usable condition.
+        let _root = state.tree_hash_root();
+        state.build_all_caches(&SPEC).unwrap();
+    }
+});
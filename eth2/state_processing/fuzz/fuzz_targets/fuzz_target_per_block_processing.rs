@@ -0,0 +1,59 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+#[macro_use] extern crate lazy_static;
+extern crate ssz;
+extern crate state_processing;
+extern crate tree_hash;
+extern crate types;
+
+use ssz::Decode;
+use state_processing::per_block_processing;
+use tree_hash::{SignedRoot, TreeHash};
+use types::test_utils::TestingBeaconStateBuilder;
+use types::*;
+
+type E = MinimalEthSpec;
+
+const VALIDATOR_COUNT: usize = 64;
+
+lazy_static! {
+    static ref SPEC: ChainSpec = E::default_spec();
+    static ref STATE: BeaconState<E> = {
+        let mut builder: TestingBeaconStateBuilder<E> =
+            TestingBeaconStateBuilder::from_deterministic_keypairs(VALIDATOR_COUNT, &SPEC);
+
+        // Start a few epochs after genesis, so that attestations for the previous epoch and
+        // slashings/exits may be valid.
+        builder.teleport_to_slot((E::genesis_epoch() + 4).start_slot(E::slots_per_epoch()) + 1);
+        builder.build_caches(&SPEC).unwrap();
+
+        builder.build().0
+    };
+}
+
+// Fuzz per_block_processing() with an arbitrary block body.
+//
+// The input is the SSZ encoding of a `BeaconBlockBody`. The header fields of the block are set to
+// valid values for the state, so that the body is always processed. Errors are expected, however
+// any panic (including arithmetic overflow, when built with debug assertions) is a bug.
+fuzz_target!(|data: &[u8]| {
+    let body = match BeaconBlockBody::from_ssz_bytes(data) {
+        Ok(body) => body,
+        Err(_) => return,
+    };
+
+    let mut state = STATE.clone();
+    let block = BeaconBlock {
+        slot: state.slot,
+        previous_block_root: Hash256::from_slice(&state.latest_block_header.signed_root()),
+        state_root: Hash256::zero(),
+        body,
+        signature: Signature::empty_signature(),
+    };
+
+    if per_block_processing(&mut state, &block, &SPEC).is_ok() {
+        // A valid block must leave the state in a usable condition.
+        let _root = state.tree_hash_root();
+        state.build_all_caches(&SPEC).unwrap();
+    }
+});
@@ -1,4 +1,7 @@
-use super::per_block_processing::{errors::BlockProcessingError, process_deposits};
+use super::per_block_processing::{
+    errors::{BlockInvalid, BlockProcessingError},
+    process_deposits,
+};
 use tree_hash::TreeHash;
 use types::*;
 
@@ -20,10 +23,21 @@ pub fn get_genesis_beacon_state<T: EthSpec>(
     let mut state = BeaconState::genesis(genesis_time, genesis_eth1_data, spec);
 
     // Process genesis deposits.
-    process_deposits(&mut state, genesis_validator_deposits, spec)?;
+    //
+    // The deposits are processed in blocks of `max_deposits` to satisfy the deposit count check
+    // in `process_deposits`. No deposits may be processed if `max_deposits` is zero.
+    if spec.max_deposits == 0 && !genesis_validator_deposits.is_empty() {
+        return Err(BlockProcessingError::Invalid(
+            BlockInvalid::DepositCountInvalid,
+        ));
+    }
+    let chunk_size = std::cmp::max(spec.max_deposits as usize, 1);
+    for deposits in genesis_validator_deposits.chunks(chunk_size) {
+        process_deposits(&mut state, deposits, spec)?;
+    }
 
     // Process genesis activations.
-    for validator in &mut state.validator_registry {
+    for validator in state.validator_registry.iter_mut() {
         if validator.effective_balance >= spec.max_effective_balance {
             validator.activation_eligibility_epoch = T::genesis_epoch();
             validator.activation_epoch = T::genesis_epoch();
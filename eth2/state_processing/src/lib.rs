@@ -10,7 +10,14 @@ pub mod per_slot_processing;
 pub use get_genesis_state::get_genesis_beacon_state;
 pub use per_block_processing::{
     errors::{BlockInvalid, BlockProcessingError},
-    per_block_processing, per_block_processing_without_verifying_block_signature,
+    per_block_processing, per_block_processing_with_pubkey_cache,
+    per_block_processing_without_verifying_block_signature,
+};
+pub use per_epoch_processing::{
+    apply_rewards::{Delta, RewardsBreakdown},
+    errors::EpochProcessingError,
+    per_epoch_processing, per_epoch_processing_with_rewards_report,
+};
+pub use per_slot_processing::{
+    per_slot_processing, per_slot_processing_with_rewards_report, Error as SlotProcessingError,
 };
-pub use per_epoch_processing::{errors::EpochProcessingError, per_epoch_processing};
-pub use per_slot_processing::{per_slot_processing, Error as SlotProcessingError};
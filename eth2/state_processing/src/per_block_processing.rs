@@ -4,6 +4,8 @@ use rayon::prelude::*;
 use tree_hash::{SignedRoot, TreeHash};
 use types::*;
 
+pub use self::block_signature_verifier::BlockSignatureVerifier;
+pub use self::signature_sets::get_pubkey_from_state;
 pub use self::verify_attester_slashing::{
     get_slashable_indices, get_slashable_indices_modular, verify_attester_slashing,
 };
@@ -25,7 +27,9 @@ pub use verify_transfer::{
 };
 
 pub mod block_processing_builder;
+pub mod block_signature_verifier;
 pub mod errors;
+pub mod signature_sets;
 pub mod tests;
 mod validate_attestation;
 mod verify_attester_slashing;
@@ -35,6 +39,19 @@ mod verify_indexed_attestation;
 mod verify_proposer_slashing;
 mod verify_transfer;
 
+/// Indicates whether the signatures of block operations should be verified as they are processed.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum VerifySignatures {
+    True,
+    False,
+}
+
+impl VerifySignatures {
+    pub fn is_true(self) -> bool {
+        self == VerifySignatures::True
+    }
+}
+
 /// Updates the state for a new block, whilst validating that the block is valid.
 ///
 /// Returns `Ok(())` if the block is valid and the state was successfully updated. Otherwise
@@ -64,6 +81,38 @@ pub fn per_block_processing_without_verifying_block_signature<T: EthSpec>(
     per_block_processing_signature_optional(state, block, false, spec)
 }
 
+/// Updates the state for a new block, whilst validating that the block is valid.
+///
+/// Identical to `per_block_processing`, except the public keys used to verify signatures are read
+/// with `get_pubkey` (a map from validator index to public key) instead of from the validator
+/// registry of `state`. This allows a cache of public keys which is shared between states to be
+/// used.
+///
+/// `get_pubkey` must return the same keys as the validator registry of `state`. Unknown validators
+/// should return `None`.
+///
+/// Spec v0.6.3
+pub fn per_block_processing_with_pubkey_cache<'a, T, F>(
+    state: &mut BeaconState<T>,
+    block: &'a BeaconBlock,
+    get_pubkey: F,
+    spec: &ChainSpec,
+) -> Result<(), Error>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    process_block_header(state, block, spec, false)?;
+
+    // Ensure the current and previous epoch caches are built.
+    state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+    state.build_committee_cache(RelativeEpoch::Current, spec)?;
+
+    let verify_signatures = verify_signatures_in_batch(state, get_pubkey, block, true, spec);
+
+    process_block_body(state, block, true, verify_signatures, spec)
+}
+
 /// Updates the state for a new block, whilst validating that the block is valid, optionally
 /// checking the block proposer signature.
 ///
@@ -72,29 +121,175 @@ pub fn per_block_processing_without_verifying_block_signature<T: EthSpec>(
 ///
 /// Spec v0.6.3
 fn per_block_processing_signature_optional<T: EthSpec>(
-    mut state: &mut BeaconState<T>,
+    state: &mut BeaconState<T>,
     block: &BeaconBlock,
     should_verify_block_signature: bool,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    process_block_header(state, block, spec, should_verify_block_signature)?;
+    process_block_header(state, block, spec, false)?;
 
     // Ensure the current and previous epoch caches are built.
     state.build_committee_cache(RelativeEpoch::Previous, spec)?;
     state.build_committee_cache(RelativeEpoch::Current, spec)?;
 
-    process_randao(&mut state, &block, &spec)?;
-    process_eth1_data(&mut state, &block.body.eth1_data, spec)?;
-    process_proposer_slashings(&mut state, &block.body.proposer_slashings, spec)?;
-    process_attester_slashings(&mut state, &block.body.attester_slashings, spec)?;
-    process_attestations(&mut state, &block.body.attestations, spec)?;
-    process_deposits(&mut state, &block.body.deposits, spec)?;
-    process_exits(&mut state, &block.body.voluntary_exits, spec)?;
-    process_transfers(&mut state, &block.body.transfers, spec)?;
+    let verify_signatures = {
+        let state: &BeaconState<T> = state;
+        verify_signatures_in_batch(
+            state,
+            |i| get_pubkey_from_state(state, i),
+            block,
+            should_verify_block_signature,
+            spec,
+        )
+    };
+
+    process_block_body(
+        state,
+        block,
+        should_verify_block_signature,
+        verify_signatures,
+        spec,
+    )
+}
+
+/// Verify all signatures in the block with a single batch verification, before any operation
+/// modifies the state. None of the keys or domains used by the signatures are changed by block
+/// processing, so this is equivalent to verifying each signature as it is reached.
+///
+/// The batch cannot identify an invalid signature, so if it fails `VerifySignatures::True` is
+/// returned and each signature should be verified individually during processing to produce a
+/// precise error.
+fn verify_signatures_in_batch<'a, T, F>(
+    state: &BeaconState<T>,
+    get_pubkey: F,
+    block: &'a BeaconBlock,
+    should_verify_block_signature: bool,
+    spec: &ChainSpec,
+) -> VerifySignatures
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    let mut verifier = BlockSignatureVerifier::new(state, get_pubkey, spec);
+    let result = if should_verify_block_signature {
+        verifier.include_block_proposal(block)
+    } else {
+        Ok(())
+    }
+    .and_then(|()| verifier.include_all_operations(block))
+    .and_then(|()| verifier.verify());
+
+    match result {
+        Ok(()) => VerifySignatures::False,
+        Err(_) => VerifySignatures::True,
+    }
+}
+
+/// A step of `process_block_body`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BlockBodyStep {
+    /// Verifies the signature of the block proposer. Only run when the block signature is to be
+    /// verified.
+    BlockSignature,
+    Randao,
+    Eth1Data,
+    ProposerSlashings,
+    AttesterSlashings,
+    Attestations,
+    Deposits,
+    Exits,
+    Transfers,
+}
+
+/// The steps of `process_block_body`, in the order they are applied.
+pub const BLOCK_BODY_STEPS: [BlockBodyStep; 9] = [
+    BlockBodyStep::BlockSignature,
+    BlockBodyStep::Randao,
+    BlockBodyStep::Eth1Data,
+    BlockBodyStep::ProposerSlashings,
+    BlockBodyStep::AttesterSlashings,
+    BlockBodyStep::Attestations,
+    BlockBodyStep::Deposits,
+    BlockBodyStep::Exits,
+    BlockBodyStep::Transfers,
+];
+
+impl BlockBodyStep {
+    /// Returns the name of the function which applies the step.
+    pub fn name(self) -> &'static str {
+        match self {
+            BlockBodyStep::BlockSignature => "verify_block_signature",
+            BlockBodyStep::Randao => "process_randao",
+            BlockBodyStep::Eth1Data => "process_eth1_data",
+            BlockBodyStep::ProposerSlashings => "process_proposer_slashings",
+            BlockBodyStep::AttesterSlashings => "process_attester_slashings",
+            BlockBodyStep::Attestations => "process_attestations",
+            BlockBodyStep::Deposits => "process_deposits",
+            BlockBodyStep::Exits => "process_exits",
+            BlockBodyStep::Transfers => "process_transfers",
+        }
+    }
+}
+
+/// Applies the operations in the block to the state, after the block header has been processed.
+///
+/// Spec v0.6.3
+fn process_block_body<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    block: &BeaconBlock,
+    should_verify_block_signature: bool,
+    verify_signatures: VerifySignatures,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    for &step in BLOCK_BODY_STEPS.iter() {
+        if step == BlockBodyStep::BlockSignature
+            && !(should_verify_block_signature && verify_signatures.is_true())
+        {
+            continue;
+        }
+
+        process_block_body_step(state, block, step, verify_signatures, spec)?;
+    }
 
     Ok(())
 }
 
+/// Applies a single step of `process_block_body`. Applying each of `BLOCK_BODY_STEPS` in order is
+/// equivalent to `process_block_body`, allowing the state to be inspected between steps.
+///
+/// Spec v0.6.3
+pub fn process_block_body_step<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    block: &BeaconBlock,
+    step: BlockBodyStep,
+    verify_signatures: VerifySignatures,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    let body = &block.body;
+
+    match step {
+        BlockBodyStep::BlockSignature => verify_block_signature(state, block, spec),
+        BlockBodyStep::Randao => process_randao(state, block, verify_signatures, spec),
+        BlockBodyStep::Eth1Data => process_eth1_data(state, &body.eth1_data, spec),
+        BlockBodyStep::ProposerSlashings => {
+            process_proposer_slashings(state, &body.proposer_slashings, verify_signatures, spec)
+        }
+        BlockBodyStep::AttesterSlashings => {
+            process_attester_slashings(state, &body.attester_slashings, verify_signatures, spec)
+        }
+        BlockBodyStep::Attestations => {
+            process_attestations(state, &body.attestations, verify_signatures, spec)
+        }
+        BlockBodyStep::Deposits => process_deposits(state, &body.deposits, spec),
+        BlockBodyStep::Exits => {
+            process_exits(state, &body.voluntary_exits, verify_signatures, spec)
+        }
+        BlockBodyStep::Transfers => {
+            process_transfers(state, &body.transfers, verify_signatures, spec)
+        }
+    }
+}
+
 /// Processes the block header.
 ///
 /// Spec v0.6.3
@@ -164,24 +359,27 @@ pub fn verify_block_signature<T: EthSpec>(
 pub fn process_randao<T: EthSpec>(
     state: &mut BeaconState<T>,
     block: &BeaconBlock,
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    let block_proposer = &state.validator_registry
-        [state.get_beacon_proposer_index(block.slot, RelativeEpoch::Current, spec)?];
-
-    // Verify the RANDAO is a valid signature of the proposer.
-    verify!(
-        block.body.randao_reveal.verify(
-            &state.current_epoch().tree_hash_root()[..],
-            spec.get_domain(
-                block.slot.epoch(T::slots_per_epoch()),
-                Domain::Randao,
-                &state.fork
+    if verify_signatures.is_true() {
+        let block_proposer = &state.validator_registry
+            [state.get_beacon_proposer_index(block.slot, RelativeEpoch::Current, spec)?];
+
+        // Verify the RANDAO is a valid signature of the proposer.
+        verify!(
+            block.body.randao_reveal.verify(
+                &state.current_epoch().tree_hash_root()[..],
+                spec.get_domain(
+                    block.slot.epoch(T::slots_per_epoch()),
+                    Domain::Randao,
+                    &state.fork
+                ),
+                &block_proposer.pubkey
             ),
-            &block_proposer.pubkey
-        ),
-        Invalid::BadRandaoSignature
-    );
+            Invalid::BadRandaoSignature
+        );
+    }
 
     // Update the current epoch RANDAO mix.
     state.update_randao_mix(state.current_epoch(), &block.body.randao_reveal)?;
@@ -197,7 +395,10 @@ pub fn process_eth1_data<T: EthSpec>(
     eth1_data: &Eth1Data,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    state.eth1_data_votes.push(eth1_data.clone());
+    state
+        .eth1_data_votes
+        .push(eth1_data.clone())
+        .map_err(BeaconStateError::from)?;
 
     let num_votes = state
         .eth1_data_votes
@@ -221,6 +422,7 @@ pub fn process_eth1_data<T: EthSpec>(
 pub fn process_proposer_slashings<T: EthSpec>(
     state: &mut BeaconState<T>,
     proposer_slashings: &[ProposerSlashing],
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
     verify!(
@@ -233,7 +435,7 @@ pub fn process_proposer_slashings<T: EthSpec>(
         .par_iter()
         .enumerate()
         .try_for_each(|(i, proposer_slashing)| {
-            verify_proposer_slashing(proposer_slashing, &state, spec)
+            verify_proposer_slashing(proposer_slashing, &state, verify_signatures, spec)
                 .map_err(|e| e.into_with_index(i))
         })?;
 
@@ -254,6 +456,7 @@ pub fn process_proposer_slashings<T: EthSpec>(
 pub fn process_attester_slashings<T: EthSpec>(
     state: &mut BeaconState<T>,
     attester_slashings: &[AttesterSlashing],
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
     verify!(
@@ -275,8 +478,12 @@ pub fn process_attester_slashings<T: EthSpec>(
         .par_iter()
         .enumerate()
         .try_for_each(|(i, indexed_attestation)| {
-            verify_indexed_attestation(&state, indexed_attestation, spec)
-                .map_err(|e| e.into_with_index(i))
+            if verify_signatures.is_true() {
+                verify_indexed_attestation(&state, indexed_attestation, spec)
+            } else {
+                verify_indexed_attestation_without_signature(&state, indexed_attestation, spec)
+            }
+            .map_err(|e| e.into_with_index(i))
         })?;
     let all_indexed_attestations_have_been_checked = true;
 
@@ -312,6 +519,7 @@ pub fn process_attester_slashings<T: EthSpec>(
 pub fn process_attestations<T: EthSpec>(
     state: &mut BeaconState<T>,
     attestations: &[Attestation],
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
     verify!(
@@ -327,7 +535,12 @@ pub fn process_attestations<T: EthSpec>(
         .par_iter()
         .enumerate()
         .try_for_each(|(i, attestation)| {
-            validate_attestation(state, attestation, spec).map_err(|e| e.into_with_index(i))
+            if verify_signatures.is_true() {
+                validate_attestation(state, attestation, spec)
+            } else {
+                validate_attestation_without_signature(state, attestation, spec)
+            }
+            .map_err(|e| e.into_with_index(i))
         })?;
 
     // Update the state in series.
@@ -342,11 +555,14 @@ pub fn process_attestations<T: EthSpec>(
             proposer_index,
         };
 
-        if attestation.data.target_epoch == state.current_epoch() {
-            state.current_epoch_attestations.push(pending_attestation)
+        let pending_attestations = if attestation.data.target_epoch == state.current_epoch() {
+            &mut state.current_epoch_attestations
         } else {
-            state.previous_epoch_attestations.push(pending_attestation)
-        }
+            &mut state.previous_epoch_attestations
+        };
+        pending_attestations
+            .push(pending_attestation)
+            .map_err(BeaconStateError::from)?;
     }
 
     Ok(())
@@ -421,8 +637,14 @@ pub fn process_deposits<T: EthSpec>(
                 ),
                 slashed: false,
             };
-            state.validator_registry.push(validator);
-            state.balances.push(deposit.data.amount);
+            state
+                .validator_registry
+                .push(validator)
+                .map_err(BeaconStateError::from)?;
+            state
+                .balances
+                .push(deposit.data.amount)
+                .map_err(BeaconStateError::from)?;
         }
     }
 
@@ -438,6 +660,7 @@ pub fn process_deposits<T: EthSpec>(
 pub fn process_exits<T: EthSpec>(
     state: &mut BeaconState<T>,
     voluntary_exits: &[VoluntaryExit],
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
     verify!(
@@ -450,7 +673,7 @@ pub fn process_exits<T: EthSpec>(
         .par_iter()
         .enumerate()
         .try_for_each(|(i, exit)| {
-            verify_exit(&state, exit, spec).map_err(|e| e.into_with_index(i))
+            verify_exit(&state, exit, verify_signatures, spec).map_err(|e| e.into_with_index(i))
         })?;
 
     // Update the state in series.
@@ -470,6 +693,7 @@ pub fn process_exits<T: EthSpec>(
 pub fn process_transfers<T: EthSpec>(
     state: &mut BeaconState<T>,
     transfers: &[Transfer],
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
     verify!(
@@ -481,7 +705,8 @@ pub fn process_transfers<T: EthSpec>(
         .par_iter()
         .enumerate()
         .try_for_each(|(i, transfer)| {
-            verify_transfer(&state, transfer, spec).map_err(|e| e.into_with_index(i))
+            verify_transfer(&state, transfer, verify_signatures, spec)
+                .map_err(|e| e.into_with_index(i))
         })?;
 
     for (i, transfer) in transfers.iter().enumerate() {
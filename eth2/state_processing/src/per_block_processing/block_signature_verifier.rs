@@ -0,0 +1,183 @@
+use super::signature_sets::{
+    block_proposal_signature_set, exit_signature_set, indexed_attestation_signature_set,
+    proposer_slashing_signature_set, randao_signature_set, transfer_signature_set,
+    Error as SignatureSetError,
+};
+use crate::common::convert_to_indexed;
+use bls::{verify_signature_sets, SignatureSet};
+use types::{BeaconBlock, BeaconState, BeaconStateError, ChainSpec, EthSpec, PublicKey};
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// At least one signature in the block is invalid. The batch does not reveal which.
+    SignatureInvalid,
+    /// A signature set could not be built for some object in the block.
+    SignatureSetError(SignatureSetError),
+    BeaconStateError(BeaconStateError),
+}
+
+impl From<SignatureSetError> for Error {
+    fn from(e: SignatureSetError) -> Error {
+        Error::SignatureSetError(e)
+    }
+}
+
+impl From<BeaconStateError> for Error {
+    fn from(e: BeaconStateError) -> Error {
+        Error::BeaconStateError(e)
+    }
+}
+
+/// Collects the `SignatureSet` of each signed object in a block so they can all be verified with
+/// a single batch verification.
+///
+/// Deposit signatures are never included: an invalid deposit signature does not invalidate the
+/// block, it only prevents the validator from being added to the registry.
+///
+/// Validator public keys are read with `get_pubkey`, see the `signature_sets` module. The keys
+/// and the block must outlive the verifier (`'a`), whilst `state` need only be borrowed for the
+/// life of the verifier (`'b`).
+///
+/// Requires the previous and current committee caches of `state` to be built.
+pub struct BlockSignatureVerifier<'a, 'b, T, F>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    state: &'b BeaconState<T>,
+    get_pubkey: F,
+    spec: &'b ChainSpec,
+    sets: Vec<SignatureSet<'a>>,
+}
+
+impl<'a, 'b, T, F> BlockSignatureVerifier<'a, 'b, T, F>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    /// Create a verifier with no signature sets.
+    pub fn new(state: &'b BeaconState<T>, get_pubkey: F, spec: &'b ChainSpec) -> Self {
+        Self {
+            state,
+            get_pubkey,
+            spec,
+            sets: vec![],
+        }
+    }
+
+    /// Verify every signature in `block`, including the proposer's signature over the block.
+    pub fn verify_entire_block(
+        state: &'b BeaconState<T>,
+        get_pubkey: F,
+        block: &'a BeaconBlock,
+        spec: &'b ChainSpec,
+    ) -> Result<(), Error> {
+        let mut verifier = Self::new(state, get_pubkey, spec);
+        verifier.include_block_proposal(block)?;
+        verifier.include_all_operations(block)?;
+        verifier.verify()
+    }
+
+    /// Include the proposer's signature over `block`.
+    pub fn include_block_proposal(&mut self, block: &'a BeaconBlock) -> Result<(), Error> {
+        let set = block_proposal_signature_set(self.state, &self.get_pubkey, block, self.spec)?;
+        self.sets.push(set);
+        Ok(())
+    }
+
+    /// Include every signature in `block` other than the proposer's signature over the block.
+    pub fn include_all_operations(&mut self, block: &'a BeaconBlock) -> Result<(), Error> {
+        self.include_randao_reveal(block)?;
+        self.include_proposer_slashings(block)?;
+        self.include_attester_slashings(block)?;
+        self.include_attestations(block)?;
+        self.include_exits(block)?;
+        self.include_transfers(block);
+        Ok(())
+    }
+
+    /// Include the RANDAO reveal in `block`.
+    pub fn include_randao_reveal(&mut self, block: &'a BeaconBlock) -> Result<(), Error> {
+        let set = randao_signature_set(self.state, &self.get_pubkey, block, self.spec)?;
+        self.sets.push(set);
+        Ok(())
+    }
+
+    /// Include both headers of each proposer slashing in `block`.
+    pub fn include_proposer_slashings(&mut self, block: &'a BeaconBlock) -> Result<(), Error> {
+        for proposer_slashing in &block.body.proposer_slashings {
+            let (set_1, set_2) = proposer_slashing_signature_set(
+                self.state,
+                &self.get_pubkey,
+                proposer_slashing,
+                self.spec,
+            )?;
+            self.sets.push(set_1);
+            self.sets.push(set_2);
+        }
+        Ok(())
+    }
+
+    /// Include both indexed attestations of each attester slashing in `block`.
+    pub fn include_attester_slashings(&mut self, block: &'a BeaconBlock) -> Result<(), Error> {
+        for attester_slashing in &block.body.attester_slashings {
+            let indexed_attestations = [
+                &attester_slashing.attestation_1,
+                &attester_slashing.attestation_2,
+            ];
+            for indexed_attestation in indexed_attestations.iter().cloned() {
+                let set = indexed_attestation_signature_set(
+                    self.state,
+                    &self.get_pubkey,
+                    &indexed_attestation.signature,
+                    indexed_attestation,
+                    self.spec,
+                )?;
+                self.sets.push(set);
+            }
+        }
+        Ok(())
+    }
+
+    /// Include each attestation in `block`.
+    pub fn include_attestations(&mut self, block: &'a BeaconBlock) -> Result<(), Error> {
+        for attestation in &block.body.attestations {
+            let indexed_attestation = convert_to_indexed(self.state, attestation)?;
+            let set = indexed_attestation_signature_set(
+                self.state,
+                &self.get_pubkey,
+                &attestation.signature,
+                &indexed_attestation,
+                self.spec,
+            )?;
+            self.sets.push(set);
+        }
+        Ok(())
+    }
+
+    /// Include each voluntary exit in `block`.
+    pub fn include_exits(&mut self, block: &'a BeaconBlock) -> Result<(), Error> {
+        for exit in &block.body.voluntary_exits {
+            let set = exit_signature_set(self.state, &self.get_pubkey, exit, self.spec)?;
+            self.sets.push(set);
+        }
+        Ok(())
+    }
+
+    /// Include each transfer in `block`.
+    pub fn include_transfers(&mut self, block: &'a BeaconBlock) {
+        for transfer in &block.body.transfers {
+            self.sets
+                .push(transfer_signature_set(self.state, transfer, self.spec));
+        }
+    }
+
+    /// Verify all included signature sets in a single batch.
+    pub fn verify(self) -> Result<(), Error> {
+        if verify_signature_sets(&self.sets) {
+            Ok(())
+        } else {
+            Err(Error::SignatureInvalid)
+        }
+    }
+}
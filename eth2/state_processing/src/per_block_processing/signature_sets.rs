@@ -0,0 +1,230 @@
+//! Functions which return the `SignatureSet` for each signed object in a block, allowing the
+//! signatures to be verified in a batch (see `BlockSignatureVerifier`).
+//!
+//! Validator public keys are obtained from a `get_pubkey` function, which maps a validator index
+//! to its public key. This allows a cache of public keys which outlives any single `BeaconState`
+//! to be used. Use `get_pubkey_from_state` to read keys from the validator registry instead.
+use bls::{SignatureSet, SignedMessage};
+use tree_hash::{SignedRoot, TreeHash};
+use types::{
+    AggregateSignature, AttestationDataAndCustodyBit, BeaconBlock, BeaconBlockHeader, BeaconState,
+    BeaconStateError, ChainSpec, Domain, EthSpec, IndexedAttestation, ProposerSlashing, PublicKey,
+    RelativeEpoch, Transfer, VoluntaryExit,
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The object references a validator that is not in the registry.
+    ValidatorUnknown(u64),
+    /// An indexed attestation has no validators signing with either custody bit.
+    NoSigningKeys,
+    BeaconStateError(BeaconStateError),
+}
+
+impl From<BeaconStateError> for Error {
+    fn from(e: BeaconStateError) -> Error {
+        Error::BeaconStateError(e)
+    }
+}
+
+/// Returns the public key of the validator at `validator_index` in the registry of `state`.
+pub fn get_pubkey_from_state<T: EthSpec>(
+    state: &BeaconState<T>,
+    validator_index: usize,
+) -> Option<&PublicKey> {
+    state
+        .validator_registry
+        .get(validator_index)
+        .map(|validator| &validator.pubkey)
+}
+
+/// The signature set for the proposer's signature over `block`.
+pub fn block_proposal_signature_set<'a, T, F>(
+    state: &BeaconState<T>,
+    get_pubkey: F,
+    block: &'a BeaconBlock,
+    spec: &ChainSpec,
+) -> Result<SignatureSet<'a>>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    let proposer_index =
+        state.get_beacon_proposer_index(block.slot, RelativeEpoch::Current, spec)?;
+    let domain = spec.get_domain(
+        block.slot.epoch(T::slots_per_epoch()),
+        Domain::BeaconProposer,
+        &state.fork,
+    );
+
+    Ok(SignatureSet::single(
+        &block.signature,
+        validator_pubkey(&get_pubkey, proposer_index as u64)?,
+        block.signed_root(),
+        domain,
+    ))
+}
+
+/// The signature set for the proposer's RANDAO reveal in `block`.
+pub fn randao_signature_set<'a, T, F>(
+    state: &BeaconState<T>,
+    get_pubkey: F,
+    block: &'a BeaconBlock,
+    spec: &ChainSpec,
+) -> Result<SignatureSet<'a>>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    let proposer_index =
+        state.get_beacon_proposer_index(block.slot, RelativeEpoch::Current, spec)?;
+    let domain = spec.get_domain(
+        block.slot.epoch(T::slots_per_epoch()),
+        Domain::Randao,
+        &state.fork,
+    );
+
+    Ok(SignatureSet::single(
+        &block.body.randao_reveal,
+        validator_pubkey(&get_pubkey, proposer_index as u64)?,
+        state.current_epoch().tree_hash_root(),
+        domain,
+    ))
+}
+
+/// The signature sets for both headers in `proposer_slashing`.
+pub fn proposer_slashing_signature_set<'a, T, F>(
+    state: &BeaconState<T>,
+    get_pubkey: F,
+    proposer_slashing: &'a ProposerSlashing,
+    spec: &ChainSpec,
+) -> Result<(SignatureSet<'a>, SignatureSet<'a>)>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    let pubkey = validator_pubkey(&get_pubkey, proposer_slashing.proposer_index)?;
+
+    Ok((
+        block_header_signature_set::<T>(state, &proposer_slashing.header_1, pubkey, spec),
+        block_header_signature_set::<T>(state, &proposer_slashing.header_2, pubkey, spec),
+    ))
+}
+
+/// The signature set for a block header signed by `pubkey`.
+fn block_header_signature_set<'a, T: EthSpec>(
+    state: &BeaconState<T>,
+    header: &'a BeaconBlockHeader,
+    pubkey: &'a PublicKey,
+    spec: &ChainSpec,
+) -> SignatureSet<'a> {
+    let domain = spec.get_domain(
+        header.slot.epoch(T::slots_per_epoch()),
+        Domain::BeaconProposer,
+        &state.fork,
+    );
+
+    SignatureSet::single(&header.signature, pubkey, header.signed_root(), domain)
+}
+
+/// The signature set for `indexed_attestation`, which has been signed with `signature`.
+///
+/// The signature is supplied separately so that an `Attestation` may be verified via its indexed
+/// form without cloning the signature.
+pub fn indexed_attestation_signature_set<'a, T, F>(
+    state: &BeaconState<T>,
+    get_pubkey: F,
+    signature: &'a AggregateSignature,
+    indexed_attestation: &IndexedAttestation,
+    spec: &ChainSpec,
+) -> Result<SignatureSet<'a>>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    let mut signed_messages = vec![];
+
+    for (custody_bit, indices) in &[
+        (false, &indexed_attestation.custody_bit_0_indices),
+        (true, &indexed_attestation.custody_bit_1_indices),
+    ] {
+        if indices.is_empty() {
+            continue;
+        }
+
+        let signing_keys = indices
+            .iter()
+            .map(|&validator_index| validator_pubkey(&get_pubkey, validator_index))
+            .collect::<Result<_>>()?;
+        let message = AttestationDataAndCustodyBit {
+            data: indexed_attestation.data.clone(),
+            custody_bit: *custody_bit,
+        }
+        .tree_hash_root();
+
+        signed_messages.push(SignedMessage::new(signing_keys, message));
+    }
+
+    if signed_messages.is_empty() {
+        return Err(Error::NoSigningKeys);
+    }
+
+    let domain = spec.get_domain(
+        indexed_attestation.data.target_epoch,
+        Domain::Attestation,
+        &state.fork,
+    );
+
+    Ok(SignatureSet::new(signature, signed_messages, domain))
+}
+
+/// The signature set for `exit`.
+pub fn exit_signature_set<'a, T, F>(
+    state: &BeaconState<T>,
+    get_pubkey: F,
+    exit: &'a VoluntaryExit,
+    spec: &ChainSpec,
+) -> Result<SignatureSet<'a>>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    let domain = spec.get_domain(exit.epoch, Domain::VoluntaryExit, &state.fork);
+
+    Ok(SignatureSet::single(
+        &exit.signature,
+        validator_pubkey(&get_pubkey, exit.validator_index)?,
+        exit.signed_root(),
+        domain,
+    ))
+}
+
+/// The signature set for `transfer`, which is signed by `transfer.pubkey`.
+pub fn transfer_signature_set<'a, T: EthSpec>(
+    state: &BeaconState<T>,
+    transfer: &'a Transfer,
+    spec: &ChainSpec,
+) -> SignatureSet<'a> {
+    let domain = spec.get_domain(
+        transfer.slot.epoch(T::slots_per_epoch()),
+        Domain::Transfer,
+        &state.fork,
+    );
+
+    SignatureSet::single(
+        &transfer.signature,
+        &transfer.pubkey,
+        transfer.signed_root(),
+        domain,
+    )
+}
+
+/// Returns the public key of the validator at `validator_index`.
+fn validator_pubkey<'a, F>(get_pubkey: F, validator_index: u64) -> Result<&'a PublicKey>
+where
+    F: Fn(usize) -> Option<&'a PublicKey>,
+{
+    get_pubkey(validator_index as usize).ok_or_else(|| Error::ValidatorUnknown(validator_index))
+}
@@ -1,4 +1,5 @@
 use super::errors::{ProposerSlashingInvalid as Invalid, ProposerSlashingValidationError as Error};
+use super::VerifySignatures;
 use tree_hash::SignedRoot;
 use types::*;
 
@@ -11,6 +12,7 @@ use types::*;
 pub fn verify_proposer_slashing<T: EthSpec>(
     proposer_slashing: &ProposerSlashing,
     state: &BeaconState<T>,
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
     let proposer = state
@@ -39,24 +41,26 @@ pub fn verify_proposer_slashing<T: EthSpec>(
         Invalid::ProposerNotSlashable(proposer_slashing.proposer_index)
     );
 
-    verify!(
-        verify_header_signature::<T>(
-            &proposer_slashing.header_1,
-            &proposer.pubkey,
-            &state.fork,
-            spec
-        ),
-        Invalid::BadProposal1Signature
-    );
-    verify!(
-        verify_header_signature::<T>(
-            &proposer_slashing.header_2,
-            &proposer.pubkey,
-            &state.fork,
-            spec
-        ),
-        Invalid::BadProposal2Signature
-    );
+    if verify_signatures.is_true() {
+        verify!(
+            verify_header_signature::<T>(
+                &proposer_slashing.header_1,
+                &proposer.pubkey,
+                &state.fork,
+                spec
+            ),
+            Invalid::BadProposal1Signature
+        );
+        verify!(
+            verify_header_signature::<T>(
+                &proposer_slashing.header_2,
+                &proposer.pubkey,
+                &state.fork,
+                spec
+            ),
+            Invalid::BadProposal2Signature
+        );
+    }
 
     Ok(())
 }
@@ -1,4 +1,5 @@
 use super::errors::{TransferInvalid as Invalid, TransferValidationError as Error};
+use super::VerifySignatures;
 use bls::get_withdrawal_credentials;
 use tree_hash::SignedRoot;
 use types::*;
@@ -12,9 +13,10 @@ use types::*;
 pub fn verify_transfer<T: EthSpec>(
     state: &BeaconState<T>,
     transfer: &Transfer,
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    verify_transfer_parametric(state, transfer, spec, false)
+    verify_transfer_parametric(state, transfer, verify_signatures, spec, false)
 }
 
 /// Like `verify_transfer` but doesn't run checks which may become true in future states.
@@ -25,7 +27,7 @@ pub fn verify_transfer_time_independent_only<T: EthSpec>(
     transfer: &Transfer,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    verify_transfer_parametric(state, transfer, spec, true)
+    verify_transfer_parametric(state, transfer, VerifySignatures::True, spec, true)
 }
 
 /// Parametric version of `verify_transfer` that allows some checks to be skipped.
@@ -41,6 +43,7 @@ pub fn verify_transfer_time_independent_only<T: EthSpec>(
 fn verify_transfer_parametric<T: EthSpec>(
     state: &BeaconState<T>,
     transfer: &Transfer,
+    verify_signatures: VerifySignatures,
     spec: &ChainSpec,
     time_independent_only: bool,
 ) -> Result<(), Error> {
@@ -134,18 +137,20 @@ fn verify_transfer_parametric<T: EthSpec>(
     );
 
     // Verify the transfer signature.
-    let message = transfer.signed_root();
-    let domain = spec.get_domain(
-        transfer.slot.epoch(T::slots_per_epoch()),
-        Domain::Transfer,
-        &state.fork,
-    );
-    verify!(
-        transfer
-            .signature
-            .verify(&message[..], domain, &transfer.pubkey),
-        Invalid::BadSignature
-    );
+    if verify_signatures.is_true() {
+        let message = transfer.signed_root();
+        let domain = spec.get_domain(
+            transfer.slot.epoch(T::slots_per_epoch()),
+            Domain::Transfer,
+            &state.fork,
+        );
+        verify!(
+            transfer
+                .signature
+                .verify(&message[..], domain, &transfer.pubkey),
+            Invalid::BadSignature
+        );
+    }
 
     Ok(())
 }
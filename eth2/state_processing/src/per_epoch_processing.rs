@@ -1,4 +1,4 @@
-use apply_rewards::process_rewards_and_penalties;
+use apply_rewards::{get_rewards_breakdowns, process_rewards_and_penalties, RewardsBreakdown};
 use errors::EpochProcessingError as Error;
 use process_slashings::process_slashings;
 use registry_updates::process_registry_updates;
@@ -21,6 +21,12 @@ pub mod winning_root;
 /// It is generated during crosslink processing and later used to reward/penalize validators.
 pub type WinningRootHashSet = HashMap<u64, WinningRoot>;
 
+/// The number of validators handled by each parallel task when iterating over the registry.
+///
+/// Chunks are always recombined in registry order, so the result does not depend upon the number
+/// of threads.
+const VALIDATOR_CHUNK_SIZE: usize = 1_024;
+
 /// Performs per-epoch processing on some BeaconState.
 ///
 /// Mutates the given `BeaconState`, returning early if an error is encountered. If an error is
@@ -31,6 +37,31 @@ pub fn per_epoch_processing<T: EthSpec>(
     state: &mut BeaconState<T>,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
+    per_epoch_processing_optional_report(state, false, spec).map(|_| ())
+}
+
+/// Performs per-epoch processing on some BeaconState, returning a breakdown of the rewards and
+/// penalties applied to each validator (indexed by validator index).
+///
+/// Computing the breakdown is additional work, so `per_epoch_processing` should be preferred when
+/// it is not required.
+///
+/// Spec v0.6.3
+pub fn per_epoch_processing_with_rewards_report<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    spec: &ChainSpec,
+) -> Result<Vec<RewardsBreakdown>, Error> {
+    per_epoch_processing_optional_report(state, true, spec).map(|report| report.unwrap_or_default())
+}
+
+/// Performs per-epoch processing, optionally returning a breakdown of the rewards and penalties.
+///
+/// Spec v0.6.3
+fn per_epoch_processing_optional_report<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    build_report: bool,
+    spec: &ChainSpec,
+) -> Result<Option<Vec<RewardsBreakdown>>, Error> {
     // Ensure the previous and next epoch caches are built.
     state.build_committee_cache(RelativeEpoch::Previous, spec)?;
     state.build_committee_cache(RelativeEpoch::Current, spec)?;
@@ -48,6 +79,17 @@ pub fn per_epoch_processing<T: EthSpec>(
     let winning_root_for_shards = process_crosslinks(state, spec)?;
 
     // Rewards and Penalities.
+    let report = if build_report {
+        Some(get_rewards_breakdowns(
+            state,
+            &mut validator_statuses,
+            &winning_root_for_shards,
+            spec,
+        )?)
+    } else {
+        None
+    };
+
     process_rewards_and_penalties(
         state,
         &mut validator_statuses,
@@ -67,7 +109,7 @@ pub fn per_epoch_processing<T: EthSpec>(
     // Rotate the epoch caches to suit the epoch transition.
     state.advance_caches();
 
-    Ok(())
+    Ok(report)
 }
 
 /// Update the following fields on the `BeaconState`:
@@ -193,17 +235,19 @@ pub fn process_final_updates<T: EthSpec>(
 
     // Reset eth1 data votes.
     if (state.slot + 1) % spec.slots_per_eth1_voting_period == 0 {
-        state.eth1_data_votes = vec![];
+        state.eth1_data_votes = VariableList::empty();
     }
 
     // Update effective balances with hysteresis (lag).
-    for (index, validator) in state.validator_registry.iter_mut().enumerate() {
+    //
+    // Validators are only borrowed mutably when their effective balance changes, so that the
+    // rest of the registry does not need to be re-hashed.
+    for index in 0..state.validator_registry.len() {
         let balance = state.balances[index];
+        let effective_balance = state.validator_registry[index].effective_balance;
         let half_increment = spec.effective_balance_increment / 2;
-        if balance < validator.effective_balance
-            || validator.effective_balance + 3 * half_increment < balance
-        {
-            validator.effective_balance = std::cmp::min(
+        if balance < effective_balance || effective_balance + 3 * half_increment < balance {
+            state.validator_registry[index].effective_balance = std::cmp::min(
                 balance - balance % spec.effective_balance_increment,
                 spec.max_effective_balance,
             );
@@ -244,12 +288,13 @@ pub fn process_final_updates<T: EthSpec>(
         let historical_batch = state.historical_batch();
         state
             .historical_roots
-            .push(Hash256::from_slice(&historical_batch.tree_hash_root()[..]));
+            .push(Hash256::from_slice(&historical_batch.tree_hash_root()[..]))
+            .map_err(BeaconStateError::from)?;
     }
 
     // Rotate current/previous epoch attestations
     state.previous_epoch_attestations =
-        std::mem::replace(&mut state.current_epoch_attestations, vec![]);
+        std::mem::replace(&mut state.current_epoch_attestations, VariableList::empty());
 
     Ok(())
 }
@@ -1,10 +1,13 @@
 use super::validator_statuses::{TotalBalances, ValidatorStatus, ValidatorStatuses};
-use super::{Error, WinningRootHashSet};
+use super::{Error, WinningRootHashSet, VALIDATOR_CHUNK_SIZE};
 use integer_sqrt::IntegerSquareRoot;
+use rayon::prelude::*;
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
 use types::*;
 
 /// Use to track the changes to a validators balance.
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct Delta {
     rewards: u64,
     penalties: u64,
@@ -20,6 +23,16 @@ impl Delta {
     pub fn penalize(&mut self, penalty: u64) {
         self.penalties += penalty;
     }
+
+    /// The sum of all rewards.
+    pub fn rewards(&self) -> u64 {
+        self.rewards
+    }
+
+    /// The sum of all penalties.
+    pub fn penalties(&self) -> u64 {
+        self.penalties
+    }
 }
 
 impl std::ops::AddAssign for Delta {
@@ -30,6 +43,43 @@ impl std::ops::AddAssign for Delta {
     }
 }
 
+/// The rewards and penalties applied to a single validator during an epoch transition, broken
+/// down by the duty which earned them.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct RewardsBreakdown {
+    /// Attesting to the expected FFG source.
+    pub source: Delta,
+    /// Attesting to the expected FFG target.
+    pub target: Delta,
+    /// Attesting to the expected head block.
+    pub head: Delta,
+    /// The bonus for having an attestation included quickly.
+    pub inclusion_delay: Delta,
+    /// The penalty applied to eligible validators whilst the chain is not finalizing.
+    pub inactivity: Delta,
+    /// Attesting to the winning crosslink for the validator's shard.
+    pub crosslink: Delta,
+    /// Being the first to include the attestations of other validators.
+    pub proposer: Delta,
+    /// The inclusion distance of the validator's earliest-included attestation in the previous
+    /// epoch, if any.
+    pub inclusion_distance: Option<u64>,
+}
+
+impl RewardsBreakdown {
+    /// The sum of all components; the change which is applied to the validator's balance.
+    pub fn total(&self) -> Delta {
+        let mut total = self.source.clone();
+        total += self.target.clone();
+        total += self.head.clone();
+        total += self.inclusion_delay.clone();
+        total += self.inactivity.clone();
+        total += self.crosslink.clone();
+        total += self.proposer.clone();
+        total
+    }
+}
+
 /// Apply attester and proposer rewards.
 ///
 /// Spec v0.6.3
@@ -50,65 +100,146 @@ pub fn process_rewards_and_penalties<T: EthSpec>(
         return Err(Error::ValidatorStatusesInconsistent);
     }
 
+    // Update statuses with the information from winning roots, which the crosslink deltas
+    // depend upon.
+    validator_statuses.process_winning_roots(state, winning_root_for_shards, spec)?;
+
     let mut deltas = vec![Delta::default(); state.balances.len()];
 
     get_attestation_deltas(&mut deltas, state, &validator_statuses, spec)?;
     get_crosslink_deltas(&mut deltas, state, &validator_statuses, spec)?;
-
-    get_proposer_deltas(
-        &mut deltas,
-        state,
-        validator_statuses,
-        winning_root_for_shards,
-        spec,
-    )?;
+    get_proposer_deltas(&mut deltas, state, &validator_statuses, spec)?;
 
     // Apply the deltas, over-flowing but not under-flowing (saturating at 0 instead).
-    for (i, delta) in deltas.iter().enumerate() {
-        state.balances[i] += delta.rewards;
-        state.balances[i] = state.balances[i].saturating_sub(delta.penalties);
-    }
+    state
+        .balances
+        .as_mut_slice()
+        .par_iter_mut()
+        .zip(deltas.par_iter())
+        .for_each(|(balance, delta)| {
+            *balance += delta.rewards;
+            *balance = balance.saturating_sub(delta.penalties);
+        });
 
     Ok(())
 }
 
-/// For each attesting validator, reward the proposer who was first to include their attestation.
+/// Returns the rewards and penalties that `process_rewards_and_penalties` would apply to each
+/// validator in `state`, broken down by duty. The state is not modified.
 ///
 /// Spec v0.6.3
-fn get_proposer_deltas<T: EthSpec>(
-    deltas: &mut Vec<Delta>,
+pub fn get_rewards_breakdowns<T: EthSpec>(
     state: &BeaconState<T>,
     validator_statuses: &mut ValidatorStatuses,
     winning_root_for_shards: &WinningRootHashSet,
     spec: &ChainSpec,
-) -> Result<(), Error> {
+) -> Result<Vec<RewardsBreakdown>, Error> {
+    if state.current_epoch() == T::genesis_epoch() {
+        return Ok(vec![RewardsBreakdown::default(); state.balances.len()]);
+    }
+
+    if validator_statuses.statuses.len() != state.balances.len()
+        || validator_statuses.statuses.len() != state.validator_registry.len()
+    {
+        return Err(Error::ValidatorStatusesInconsistent);
+    }
+
     // Update statuses with the information from winning roots.
     validator_statuses.process_winning_roots(state, winning_root_for_shards, spec)?;
 
+    let finality_delay = (state.previous_epoch() - state.finalized_epoch).as_u64();
+    let total_balances = &validator_statuses.total_balances;
+
+    let mut breakdowns = validator_statuses
+        .statuses
+        .par_iter()
+        .enumerate()
+        .map(|(index, validator)| -> Result<_, Error> {
+            let base_reward = get_base_reward(state, index, total_balances.current_epoch, spec)?;
+
+            let mut breakdown =
+                get_attestation_delta(validator, total_balances, base_reward, finality_delay, spec);
+            breakdown.crosslink = get_crosslink_delta(validator, base_reward);
+
+            if validator.is_previous_epoch_attester {
+                breakdown.inclusion_distance = validator.inclusion_info.map(|i| i.distance);
+            }
+
+            Ok(breakdown)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
     for validator in &validator_statuses.statuses {
-        if validator.is_previous_epoch_attester {
-            let inclusion = validator
-                .inclusion_info
-                .expect("It is a logic error for an attester not to have an inclusion distance.");
-
-            let base_reward = get_base_reward(
-                state,
-                inclusion.proposer_index,
-                validator_statuses.total_balances.current_epoch,
-                spec,
-            )?;
-
-            if inclusion.proposer_index >= deltas.len() {
+        if let Some((proposer_index, reward)) =
+            get_proposer_reward(state, validator, total_balances.current_epoch, spec)?
+        {
+            breakdowns
+                .get_mut(proposer_index)
+                .ok_or(Error::ValidatorStatusesInconsistent)?
+                .proposer
+                .reward(reward);
+        }
+    }
+
+    Ok(breakdowns)
+}
+
+/// For each attesting validator, reward the proposer who was first to include their attestation.
+///
+/// Spec v0.6.3
+fn get_proposer_deltas<T: EthSpec>(
+    deltas: &mut Vec<Delta>,
+    state: &BeaconState<T>,
+    validator_statuses: &ValidatorStatuses,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    // Many attesters share a proposer, so this loop scatters writes across `deltas` and is left
+    // serial.
+    for validator in &validator_statuses.statuses {
+        if let Some((proposer_index, reward)) = get_proposer_reward(
+            state,
+            validator,
+            validator_statuses.total_balances.current_epoch,
+            spec,
+        )? {
+            if proposer_index >= deltas.len() {
                 return Err(Error::ValidatorStatusesInconsistent);
             }
 
-            deltas[inclusion.proposer_index].reward(base_reward / spec.proposer_reward_quotient);
+            deltas[proposer_index].reward(reward);
         }
     }
 
     Ok(())
 }
 
+/// Returns the index of the proposer who first included an attestation from `validator` in the
+/// previous epoch, along with that proposer's reward. Returns `None` if there is no such
+/// attestation.
+///
+/// Spec v0.6.3
+fn get_proposer_reward<T: EthSpec>(
+    state: &BeaconState<T>,
+    validator: &ValidatorStatus,
+    total_active_balance: u64,
+    spec: &ChainSpec,
+) -> Result<Option<(usize, u64)>, Error> {
+    if !validator.is_previous_epoch_attester {
+        return Ok(None);
+    }
+
+    let inclusion = validator
+        .inclusion_info
+        .expect("It is a logic error for an attester not to have an inclusion distance.");
+
+    let base_reward = get_base_reward(state, inclusion.proposer_index, total_active_balance, spec)?;
+
+    Ok(Some((
+        inclusion.proposer_index,
+        base_reward / spec.proposer_reward_quotient,
+    )))
+}
+
 /// Apply rewards for participation in attestations during the previous epoch.
 ///
 /// Spec v0.6.3
@@ -120,29 +251,36 @@ fn get_attestation_deltas<T: EthSpec>(
 ) -> Result<(), Error> {
     let finality_delay = (state.previous_epoch() - state.finalized_epoch).as_u64();
 
-    for (index, validator) in validator_statuses.statuses.iter().enumerate() {
-        let base_reward = get_base_reward(
-            state,
-            index,
-            validator_statuses.total_balances.current_epoch,
-            spec,
-        )?;
-
-        let delta = get_attestation_delta(
-            &validator,
-            &validator_statuses.total_balances,
-            base_reward,
-            finality_delay,
-            spec,
-        );
-
-        deltas[index] += delta;
-    }
+    deltas
+        .par_chunks_mut(VALIDATOR_CHUNK_SIZE)
+        .zip(validator_statuses.statuses.par_chunks(VALIDATOR_CHUNK_SIZE))
+        .enumerate()
+        .try_for_each(|(chunk_index, (deltas, statuses))| -> Result<(), Error> {
+            for (offset, (delta, validator)) in deltas.iter_mut().zip(statuses).enumerate() {
+                let index = chunk_index * VALIDATOR_CHUNK_SIZE + offset;
+
+                let base_reward = get_base_reward(
+                    state,
+                    index,
+                    validator_statuses.total_balances.current_epoch,
+                    spec,
+                )?;
+
+                *delta += get_attestation_delta(
+                    validator,
+                    &validator_statuses.total_balances,
+                    base_reward,
+                    finality_delay,
+                    spec,
+                )
+                .total();
+            }
 
-    Ok(())
+            Ok(())
+        })
 }
 
-/// Determine the delta for a single validator, sans proposer rewards.
+/// Determine the delta for a single validator, sans proposer and crosslink rewards.
 ///
 /// Spec v0.6.3
 fn get_attestation_delta(
@@ -151,8 +289,8 @@ fn get_attestation_delta(
     base_reward: u64,
     finality_delay: u64,
     spec: &ChainSpec,
-) -> Delta {
-    let mut delta = Delta::default();
+) -> RewardsBreakdown {
+    let mut breakdown = RewardsBreakdown::default();
 
     // Is this validator eligible to be rewarded or penalized?
     // Spec: validator index in `eligible_validator_indices`
@@ -160,7 +298,7 @@ fn get_attestation_delta(
         || (validator.is_slashed && !validator.is_withdrawable_in_current_epoch);
 
     if !is_eligible {
-        return delta;
+        return breakdown;
     }
 
     let total_balance = total_balances.current_epoch;
@@ -172,42 +310,52 @@ fn get_attestation_delta(
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_source_attestations)`
     if validator.is_previous_epoch_attester && !validator.is_slashed {
-        delta.reward(base_reward * total_attesting_balance / total_balance);
+        breakdown
+            .source
+            .reward(base_reward * total_attesting_balance / total_balance);
         // Inclusion speed bonus
         let inclusion = validator
             .inclusion_info
             .expect("It is a logic error for an attester not to have an inclusion distance.");
-        delta.reward(base_reward * spec.min_attestation_inclusion_delay / inclusion.distance);
+        breakdown
+            .inclusion_delay
+            .reward(base_reward * spec.min_attestation_inclusion_delay / inclusion.distance);
     } else {
-        delta.penalize(base_reward);
+        breakdown.source.penalize(base_reward);
     }
 
     // Expected FFG target.
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_target_attestations)`
     if validator.is_previous_epoch_target_attester && !validator.is_slashed {
-        delta.reward(base_reward * matching_target_balance / total_balance);
+        breakdown
+            .target
+            .reward(base_reward * matching_target_balance / total_balance);
     } else {
-        delta.penalize(base_reward);
+        breakdown.target.penalize(base_reward);
     }
 
     // Expected head.
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_head_attestations)`
     if validator.is_previous_epoch_head_attester && !validator.is_slashed {
-        delta.reward(base_reward * matching_head_balance / total_balance);
+        breakdown
+            .head
+            .reward(base_reward * matching_head_balance / total_balance);
     } else {
-        delta.penalize(base_reward);
+        breakdown.head.penalize(base_reward);
     }
 
     // Inactivity penalty
     if finality_delay > spec.min_epochs_to_inactivity_penalty {
         // All eligible validators are penalized
-        delta.penalize(spec.base_rewards_per_epoch * base_reward);
+        breakdown
+            .inactivity
+            .penalize(spec.base_rewards_per_epoch * base_reward);
 
         // Additionally, all validators whose FFG target didn't match are penalized extra
         if !validator.is_previous_epoch_target_attester {
-            delta.penalize(
+            breakdown.inactivity.penalize(
                 validator.current_epoch_effective_balance * finality_delay
                     / spec.inactivity_penalty_quotient,
             );
@@ -219,7 +367,7 @@ fn get_attestation_delta(
     // This function only computes the delta for a single validator, so it cannot also return a
     // delta for a validator.
 
-    delta
+    breakdown
 }
 
 /// Calculate the deltas based upon the winning roots for attestations during the previous epoch.
@@ -231,29 +379,44 @@ fn get_crosslink_deltas<T: EthSpec>(
     validator_statuses: &ValidatorStatuses,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    for (index, validator) in validator_statuses.statuses.iter().enumerate() {
-        let mut delta = Delta::default();
+    deltas
+        .par_chunks_mut(VALIDATOR_CHUNK_SIZE)
+        .zip(validator_statuses.statuses.par_chunks(VALIDATOR_CHUNK_SIZE))
+        .enumerate()
+        .try_for_each(|(chunk_index, (deltas, statuses))| -> Result<(), Error> {
+            for (offset, (delta, validator)) in deltas.iter_mut().zip(statuses).enumerate() {
+                let index = chunk_index * VALIDATOR_CHUNK_SIZE + offset;
+
+                let base_reward = get_base_reward(
+                    state,
+                    index,
+                    validator_statuses.total_balances.current_epoch,
+                    spec,
+                )?;
+
+                *delta += get_crosslink_delta(validator, base_reward);
+            }
 
-        let base_reward = get_base_reward(
-            state,
-            index,
-            validator_statuses.total_balances.current_epoch,
-            spec,
-        )?;
+            Ok(())
+        })
+}
 
-        if let Some(ref winning_root) = validator.winning_root_info {
-            delta.reward(
-                base_reward * winning_root.total_attesting_balance
-                    / winning_root.total_committee_balance,
-            );
-        } else {
-            delta.penalize(base_reward);
-        }
+/// Determine the crosslink delta for a single validator.
+///
+/// Spec v0.6.3
+fn get_crosslink_delta(validator: &ValidatorStatus, base_reward: u64) -> Delta {
+    let mut delta = Delta::default();
 
-        deltas[index] += delta;
+    if let Some(ref winning_root) = validator.winning_root_info {
+        delta.reward(
+            base_reward * winning_root.total_attesting_balance
+                / winning_root.total_committee_balance,
+        );
+    } else {
+        delta.penalize(base_reward);
     }
 
-    Ok(())
+    delta
 }
 
 /// Returns the base reward for some validator.
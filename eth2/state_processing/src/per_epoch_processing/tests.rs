@@ -1,5 +1,5 @@
 #![cfg(test)]
-use crate::per_epoch_processing::per_epoch_processing;
+use crate::per_epoch_processing::{per_epoch_processing, per_epoch_processing_with_rewards_report};
 use env_logger::{Builder, Env};
 use types::test_utils::TestingBeaconStateBuilder;
 use types::*;
@@ -21,3 +21,36 @@ fn runs_without_error() {
 
     per_epoch_processing(&mut state, &spec).unwrap();
 }
+
+#[test]
+fn rewards_report_matches_balance_changes() {
+    let spec = MinimalEthSpec::default_spec();
+
+    let mut builder: TestingBeaconStateBuilder<MinimalEthSpec> =
+        TestingBeaconStateBuilder::from_deterministic_keypairs(8, &spec);
+
+    let target_slot =
+        (MinimalEthSpec::genesis_epoch() + 4).end_slot(MinimalEthSpec::slots_per_epoch());
+    builder.teleport_to_slot(target_slot);
+    builder.insert_attestations(&spec);
+
+    let (state, _keypairs) = builder.build();
+
+    let mut reported_state = state.clone();
+    let report = per_epoch_processing_with_rewards_report(&mut reported_state, &spec).unwrap();
+
+    // Building the report must not alter the outcome of the transition.
+    let mut unreported_state = state.clone();
+    per_epoch_processing(&mut unreported_state, &spec).unwrap();
+    assert_eq!(reported_state, unreported_state);
+
+    assert_eq!(report.len(), state.balances.len());
+
+    for (i, breakdown) in report.iter().enumerate() {
+        let total = breakdown.total();
+        let expected = (state.balances[i] + total.rewards()).saturating_sub(total.penalties());
+        assert_eq!(reported_state.balances[i], expected, "validator {}", i);
+    }
+
+    assert!(report.iter().any(|b| b.inclusion_distance.is_some()));
+}
@@ -1,5 +1,6 @@
-use super::WinningRootHashSet;
+use super::{WinningRootHashSet, VALIDATOR_CHUNK_SIZE};
 use crate::common::get_attesting_indices_unsorted;
+use rayon::prelude::*;
 use types::*;
 
 /// Sets the boolean `var` on `self` to be true if it is true on `other`. Otherwise leaves `self`
@@ -146,6 +147,18 @@ pub struct TotalBalances {
     pub previous_epoch_head_attesters: u64,
 }
 
+impl std::ops::AddAssign for TotalBalances {
+    fn add_assign(&mut self, other: TotalBalances) {
+        self.current_epoch += other.current_epoch;
+        self.previous_epoch += other.previous_epoch;
+        self.current_epoch_attesters += other.current_epoch_attesters;
+        self.current_epoch_target_attesters += other.current_epoch_target_attesters;
+        self.previous_epoch_attesters += other.previous_epoch_attesters;
+        self.previous_epoch_target_attesters += other.previous_epoch_target_attesters;
+        self.previous_epoch_head_attesters += other.previous_epoch_head_attesters;
+    }
+}
+
 /// Summarised information about validator participation in the _previous and _current_ epochs of
 /// some `BeaconState`.
 #[derive(Clone)]
@@ -167,30 +180,49 @@ impl ValidatorStatuses {
         state: &BeaconState<T>,
         spec: &ChainSpec,
     ) -> Result<Self, BeaconStateError> {
-        let mut statuses = Vec::with_capacity(state.validator_registry.len());
-        let mut total_balances = TotalBalances::default();
+        // Each chunk of validators is processed in parallel, then the chunks are joined in order.
+        let chunks = state
+            .validator_registry
+            .par_chunks(VALIDATOR_CHUNK_SIZE)
+            .enumerate()
+            .map(|(chunk_index, validators)| -> Result<_, BeaconStateError> {
+                let mut statuses = Vec::with_capacity(validators.len());
+                let mut total_balances = TotalBalances::default();
+
+                for (offset, validator) in validators.iter().enumerate() {
+                    let i = chunk_index * VALIDATOR_CHUNK_SIZE + offset;
+                    let effective_balance = state.get_effective_balance(i, spec)?;
+                    let mut status = ValidatorStatus {
+                        is_slashed: validator.slashed,
+                        is_withdrawable_in_current_epoch: validator
+                            .is_withdrawable_at(state.current_epoch()),
+                        current_epoch_effective_balance: effective_balance,
+                        ..ValidatorStatus::default()
+                    };
+
+                    if validator.is_active_at(state.current_epoch()) {
+                        status.is_active_in_current_epoch = true;
+                        total_balances.current_epoch += effective_balance;
+                    }
 
-        for (i, validator) in state.validator_registry.iter().enumerate() {
-            let effective_balance = state.get_effective_balance(i, spec)?;
-            let mut status = ValidatorStatus {
-                is_slashed: validator.slashed,
-                is_withdrawable_in_current_epoch: validator
-                    .is_withdrawable_at(state.current_epoch()),
-                current_epoch_effective_balance: effective_balance,
-                ..ValidatorStatus::default()
-            };
-
-            if validator.is_active_at(state.current_epoch()) {
-                status.is_active_in_current_epoch = true;
-                total_balances.current_epoch += effective_balance;
-            }
+                    if validator.is_active_at(state.previous_epoch()) {
+                        status.is_active_in_previous_epoch = true;
+                        total_balances.previous_epoch += effective_balance;
+                    }
 
-            if validator.is_active_at(state.previous_epoch()) {
-                status.is_active_in_previous_epoch = true;
-                total_balances.previous_epoch += effective_balance;
-            }
+                    statuses.push(status);
+                }
 
-            statuses.push(status);
+                Ok((statuses, total_balances))
+            })
+            .collect::<Result<Vec<_>, BeaconStateError>>()?;
+
+        let mut statuses = Vec::with_capacity(state.validator_registry.len());
+        let mut total_balances = TotalBalances::default();
+
+        for (chunk_statuses, chunk_balances) in chunks {
+            statuses.extend(chunk_statuses);
+            total_balances += chunk_balances;
         }
 
         Ok(Self {
@@ -208,79 +240,104 @@ impl ValidatorStatuses {
         state: &BeaconState<T>,
         spec: &ChainSpec,
     ) -> Result<(), BeaconStateError> {
-        for a in state
+        // Profile each attestation in parallel, generating a `ValidatorStatus` object that applies
+        // to all participants in the attestation.
+        let profiles = state
             .previous_epoch_attestations
-            .iter()
-            .chain(state.current_epoch_attestations.iter())
-        {
-            let attesting_indices =
-                get_attesting_indices_unsorted(state, &a.data, &a.aggregation_bitfield)?;
+            .par_iter()
+            .chain(state.current_epoch_attestations.par_iter())
+            .map(|a| -> Result<_, BeaconStateError> {
+                let attesting_indices =
+                    get_attesting_indices_unsorted(state, &a.data, &a.aggregation_bitfield)?;
 
-            let mut status = ValidatorStatus::default();
+                let mut status = ValidatorStatus::default();
 
-            // Profile this attestation, updating the total balances and generating an
-            // `ValidatorStatus` object that applies to all participants in the attestation.
-            if is_from_epoch(a, state.current_epoch()) {
-                status.is_current_epoch_attester = true;
+                if is_from_epoch(a, state.current_epoch()) {
+                    status.is_current_epoch_attester = true;
 
-                if target_matches_epoch_start_block(a, state, state.current_epoch())? {
-                    status.is_current_epoch_target_attester = true;
-                }
-            } else if is_from_epoch(a, state.previous_epoch()) {
-                status.is_previous_epoch_attester = true;
-
-                // The inclusion slot and distance are only required for previous epoch attesters.
-                let attestation_slot = state.get_attestation_slot(&a.data)?;
-                let inclusion_slot = attestation_slot + a.inclusion_delay;
-                let relative_epoch =
-                    RelativeEpoch::from_slot(state.slot, inclusion_slot, T::slots_per_epoch())?;
-                status.inclusion_info = Some(InclusionInfo {
-                    slot: inclusion_slot,
-                    distance: a.inclusion_delay,
-                    proposer_index: state.get_beacon_proposer_index(
-                        inclusion_slot,
-                        relative_epoch,
-                        spec,
-                    )?,
-                });
-
-                if target_matches_epoch_start_block(a, state, state.previous_epoch())? {
-                    status.is_previous_epoch_target_attester = true;
-                }
+                    if target_matches_epoch_start_block(a, state, state.current_epoch())? {
+                        status.is_current_epoch_target_attester = true;
+                    }
+                } else if is_from_epoch(a, state.previous_epoch()) {
+                    status.is_previous_epoch_attester = true;
+
+                    // The inclusion slot and distance are only required for previous epoch
+                    // attesters.
+                    let attestation_slot = state.get_attestation_slot(&a.data)?;
+                    let inclusion_slot = attestation_slot + a.inclusion_delay;
+                    let relative_epoch =
+                        RelativeEpoch::from_slot(state.slot, inclusion_slot, T::slots_per_epoch())?;
+                    status.inclusion_info = Some(InclusionInfo {
+                        slot: inclusion_slot,
+                        distance: a.inclusion_delay,
+                        proposer_index: state.get_beacon_proposer_index(
+                            inclusion_slot,
+                            relative_epoch,
+                            spec,
+                        )?,
+                    });
+
+                    if target_matches_epoch_start_block(a, state, state.previous_epoch())? {
+                        status.is_previous_epoch_target_attester = true;
+                    }
 
-                if has_common_beacon_block_root(a, state)? {
-                    status.is_previous_epoch_head_attester = true;
+                    if has_common_beacon_block_root(a, state)? {
+                        status.is_previous_epoch_head_attester = true;
+                    }
                 }
-            }
 
-            // Loop through the participating validator indices and update the status vec.
+                Ok((attesting_indices, status))
+            })
+            .collect::<Result<Vec<_>, BeaconStateError>>()?;
+
+        // Loop through the participating validator indices and update the status vec.
+        //
+        // This is done serially and in attestation order, as ties between inclusion slots are
+        // resolved in favour of the first attestation seen.
+        for (attesting_indices, status) in profiles {
             for validator_index in attesting_indices {
                 self.statuses[validator_index].update(&status);
             }
         }
 
-        // Compute the total balances
-        for (index, v) in self.statuses.iter().enumerate() {
-            // According to the spec, we only count unslashed validators towards the totals.
-            if !v.is_slashed {
-                let validator_balance = state.get_effective_balance(index, spec)?;
-
-                if v.is_current_epoch_attester {
-                    self.total_balances.current_epoch_attesters += validator_balance;
-                }
-                if v.is_current_epoch_target_attester {
-                    self.total_balances.current_epoch_target_attesters += validator_balance;
-                }
-                if v.is_previous_epoch_attester {
-                    self.total_balances.previous_epoch_attesters += validator_balance;
-                }
-                if v.is_previous_epoch_target_attester {
-                    self.total_balances.previous_epoch_target_attesters += validator_balance;
-                }
-                if v.is_previous_epoch_head_attester {
-                    self.total_balances.previous_epoch_head_attesters += validator_balance;
+        // Compute the total balances for each chunk of validators, then sum the chunks.
+        let chunk_balances = self
+            .statuses
+            .par_chunks(VALIDATOR_CHUNK_SIZE)
+            .enumerate()
+            .map(|(chunk_index, statuses)| -> Result<_, BeaconStateError> {
+                let mut total_balances = TotalBalances::default();
+
+                for (offset, v) in statuses.iter().enumerate() {
+                    // According to the spec, we only count unslashed validators towards the totals.
+                    if !v.is_slashed {
+                        let index = chunk_index * VALIDATOR_CHUNK_SIZE + offset;
+                        let validator_balance = state.get_effective_balance(index, spec)?;
+
+                        if v.is_current_epoch_attester {
+                            total_balances.current_epoch_attesters += validator_balance;
+                        }
+                        if v.is_current_epoch_target_attester {
+                            total_balances.current_epoch_target_attesters += validator_balance;
+                        }
+                        if v.is_previous_epoch_attester {
+                            total_balances.previous_epoch_attesters += validator_balance;
+                        }
+                        if v.is_previous_epoch_target_attester {
+                            total_balances.previous_epoch_target_attesters += validator_balance;
+                        }
+                        if v.is_previous_epoch_head_attester {
+                            total_balances.previous_epoch_head_attesters += validator_balance;
+                        }
+                    }
                 }
-            }
+
+                Ok(total_balances)
+            })
+            .collect::<Result<Vec<_>, BeaconStateError>>()?;
+
+        for balances in chunk_balances {
+            self.total_balances += balances;
         }
 
         Ok(())
@@ -1,4 +1,5 @@
 use crate::common::get_attesting_indices_unsorted;
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use tree_hash::TreeHash;
 use types::*;
@@ -75,19 +76,28 @@ pub fn winning_root<T: EthSpec>(
         return Ok(None);
     }
 
-    let mut winning_root = None;
-    for (crosslink, attestations) in candidate_crosslink_map {
-        let attesting_validator_indices =
-            get_unslashed_attesting_indices_unsorted(state, &attestations)?;
-        let total_attesting_balance =
-            state.get_total_balance(&attesting_validator_indices, spec)?;
-
-        let candidate = WinningRoot {
-            crosslink,
-            attesting_validator_indices,
-            total_attesting_balance,
-        };
+    // Tally the attesting balance of each candidate in parallel. The candidates are collected in
+    // their original order so the winner is chosen exactly as it would be by a serial loop.
+    let candidates = candidate_crosslink_map
+        .into_iter()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(crosslink, attestations)| -> Result<_, BeaconStateError> {
+            let attesting_validator_indices =
+                get_unslashed_attesting_indices_unsorted(state, &attestations)?;
+            let total_attesting_balance =
+                state.get_total_balance(&attesting_validator_indices, spec)?;
+
+            Ok(WinningRoot {
+                crosslink,
+                attesting_validator_indices,
+                total_attesting_balance,
+            })
+        })
+        .collect::<Result<Vec<_>, BeaconStateError>>()?;
 
+    let mut winning_root: Option<WinningRoot> = None;
+    for candidate in candidates {
         if let Some(ref winner) = winning_root {
             if candidate.is_better_than(&winner) {
                 winning_root = Some(candidate);
@@ -104,14 +114,13 @@ pub fn get_unslashed_attesting_indices_unsorted<T: EthSpec>(
     state: &BeaconState<T>,
     attestations: &[&PendingAttestation],
 ) -> Result<Vec<usize>, BeaconStateError> {
-    let mut output = HashSet::new();
-    for a in attestations {
-        output.extend(get_attesting_indices_unsorted(
-            state,
-            &a.data,
-            &a.aggregation_bitfield,
-        )?);
-    }
+    let attesting_indices = attestations
+        .par_iter()
+        .map(|a| get_attesting_indices_unsorted(state, &a.data, &a.aggregation_bitfield))
+        .collect::<Result<Vec<_>, BeaconStateError>>()?;
+
+    let output: HashSet<usize> = attesting_indices.into_iter().flatten().collect();
+
     Ok(output
         .into_iter()
         .filter(|index| {
@@ -14,15 +14,42 @@ pub fn per_slot_processing<T: EthSpec>(
     state: &mut BeaconState<T>,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
+    per_slot_processing_optional_report(state, false, spec).map(|_| ())
+}
+
+/// Advances a state forward by one slot, as per `per_slot_processing`.
+///
+/// If an epoch transition occurs, returns the breakdown of rewards and penalties applied to each
+/// validator (see `per_epoch_processing_with_rewards_report`).
+///
+/// Spec v0.6.3
+pub fn per_slot_processing_with_rewards_report<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    spec: &ChainSpec,
+) -> Result<Option<Vec<RewardsBreakdown>>, Error> {
+    per_slot_processing_optional_report(state, true, spec)
+}
+
+fn per_slot_processing_optional_report<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    build_report: bool,
+    spec: &ChainSpec,
+) -> Result<Option<Vec<RewardsBreakdown>>, Error> {
     cache_state(state, spec)?;
 
+    let mut report = None;
+
     if (state.slot > spec.genesis_slot) && ((state.slot + 1) % T::slots_per_epoch() == 0) {
-        per_epoch_processing(state, spec)?;
+        if build_report {
+            report = Some(per_epoch_processing_with_rewards_report(state, spec)?);
+        } else {
+            per_epoch_processing(state, spec)?;
+        }
     }
 
     state.slot += 1;
 
-    Ok(())
+    Ok(report)
 }
 
 fn cache_state<T: EthSpec>(state: &mut BeaconState<T>, spec: &ChainSpec) -> Result<(), Error> {
@@ -0,0 +1,20 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate ssz;
+extern crate tree_hash;
+extern crate types;
+
+use ssz::{Decode, Encode};
+use tree_hash::TreeHash;
+use types::BeaconBlock;
+
+// Fuzz BeaconBlock::from_ssz_bytes()
+fuzz_target!(|data: &[u8]| {
+    if let Ok(decoded) = BeaconBlock::from_ssz_bytes(data) {
+        // Anything which decodes must survive a round-trip and be hashable.
+        let encoded = decoded.as_ssz_bytes();
+        assert_eq!(BeaconBlock::from_ssz_bytes(&encoded), Ok(decoded.clone()));
+
+        let _root = decoded.tree_hash_root();
+    }
+});
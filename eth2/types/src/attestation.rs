@@ -1,4 +1,4 @@
-use super::{AggregateSignature, AttestationData, Bitfield};
+use super::{AggregateSignature, AttestationBitfield, AttestationData};
 use crate::test_utils::TestRandom;
 
 use serde_derive::{Deserialize, Serialize};
@@ -24,9 +24,9 @@ use tree_hash_derive::{CachedTreeHash, SignedRoot, TreeHash};
     SignedRoot,
 )]
 pub struct Attestation {
-    pub aggregation_bitfield: Bitfield,
+    pub aggregation_bitfield: AttestationBitfield,
     pub data: AttestationData,
-    pub custody_bitfield: Bitfield,
+    pub custody_bitfield: AttestationBitfield,
     #[signed_root(skip_hashing)]
     pub signature: AggregateSignature,
 }
@@ -3,7 +3,7 @@ use crate::*;
 use bls::Signature;
 
 use serde_derive::{Deserialize, Serialize};
-use ssz_derive::{Decode, Encode};
+use ssz_derive::{Decode, DecodeView, Encode};
 use test_random_derive::TestRandom;
 use tree_hash::TreeHash;
 use tree_hash_derive::{CachedTreeHash, SignedRoot, TreeHash};
@@ -19,6 +19,7 @@ use tree_hash_derive::{CachedTreeHash, SignedRoot, TreeHash};
     Deserialize,
     Encode,
     Decode,
+    DecodeView,
     TreeHash,
     CachedTreeHash,
     TestRandom,
@@ -50,12 +51,12 @@ impl BeaconBlock {
                     deposit_count: 0,
                 },
                 graffiti: [0; 32],
-                proposer_slashings: vec![],
-                attester_slashings: vec![],
-                attestations: vec![],
-                deposits: vec![],
-                voluntary_exits: vec![],
-                transfers: vec![],
+                proposer_slashings: VariableList::empty(),
+                attester_slashings: VariableList::empty(),
+                attestations: VariableList::empty(),
+                deposits: VariableList::empty(),
+                voluntary_exits: VariableList::empty(),
+                transfers: VariableList::empty(),
             },
             signature: Signature::empty_signature(),
         }
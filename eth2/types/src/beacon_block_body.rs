@@ -26,12 +26,12 @@ pub struct BeaconBlockBody {
     pub eth1_data: Eth1Data,
     #[serde(deserialize_with = "graffiti_from_hex_str")]
     pub graffiti: [u8; 32],
-    pub proposer_slashings: Vec<ProposerSlashing>,
-    pub attester_slashings: Vec<AttesterSlashing>,
-    pub attestations: Vec<Attestation>,
-    pub deposits: Vec<Deposit>,
-    pub voluntary_exits: Vec<VoluntaryExit>,
-    pub transfers: Vec<Transfer>,
+    pub proposer_slashings: VariableList<ProposerSlashing, MaxProposerSlashings>,
+    pub attester_slashings: VariableList<AttesterSlashing, MaxAttesterSlashings>,
+    pub attestations: VariableList<Attestation, MaxAttestations>,
+    pub deposits: VariableList<Deposit, MaxDeposits>,
+    pub voluntary_exits: VariableList<VoluntaryExit, MaxVoluntaryExits>,
+    pub transfers: VariableList<Transfer, MaxTransfers>,
 }
 
 #[cfg(test)]
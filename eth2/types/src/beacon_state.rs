@@ -2,7 +2,7 @@ use self::committee_cache::get_active_validator_indices;
 use self::exit_cache::ExitCache;
 use crate::test_utils::TestRandom;
 use crate::*;
-use cached_tree_hash::{Error as TreeHashCacheError, TreeHashCache};
+use cached_tree_hash::{CachedTreeHash, Error as TreeHashCacheError};
 use compare_fields_derive::CompareFields;
 use fixed_len_vec::{typenum::Unsigned, FixedLenVec};
 use hashing::hash;
@@ -10,19 +10,23 @@ use int_to_bytes::{int_to_bytes32, int_to_bytes8};
 use pubkey_cache::PubkeyCache;
 use serde_derive::{Deserialize, Serialize};
 use ssz::ssz_encode;
-use ssz_derive::{Decode, Encode};
+use ssz_derive::{Decode, DecodeView, Encode};
 use test_random_derive::TestRandom;
 use tree_hash::TreeHash;
 use tree_hash_derive::{CachedTreeHash, TreeHash};
 
 pub use self::committee_cache::CommitteeCache;
+pub use self::shuffling_cache::{ShufflingCache, ShufflingId, SHUFFLING_CACHE_SIZE};
+pub use self::tree_hash_cache::BeaconTreeHashCache;
 pub use beacon_state_types::*;
 
 mod beacon_state_types;
 mod committee_cache;
 mod exit_cache;
 mod pubkey_cache;
+mod shuffling_cache;
 mod tests;
+mod tree_hash_cache;
 
 pub const CACHED_EPOCHS: usize = 3;
 const MAX_RANDOM_BYTE: u64 = (1 << 8) - 1;
@@ -59,6 +63,7 @@ pub enum Error {
     RelativeEpochError(RelativeEpochError),
     CommitteeCacheUninitialized(RelativeEpoch),
     TreeHashCacheError(TreeHashCacheError),
+    VariableListError(VariableListError),
 }
 
 /// The state of the `BeaconChain` at some slot.
@@ -73,10 +78,12 @@ pub enum Error {
     TestRandom,
     Encode,
     Decode,
+    DecodeView,
     TreeHash,
     CachedTreeHash,
     CompareFields,
 )]
+#[cached_tree_hash(cache = "BeaconTreeHashCache")]
 pub struct BeaconState<T>
 where
     T: EthSpec,
@@ -88,17 +95,20 @@ where
 
     // Validator registry
     #[compare_fields(as_slice)]
-    pub validator_registry: Vec<Validator>,
+    #[cached_tree_hash(cached)]
+    pub validator_registry: TrackedList<VariableList<Validator, T::ValidatorRegistryLimit>>,
     #[compare_fields(as_slice)]
-    pub balances: Vec<u64>,
+    #[cached_tree_hash(cached)]
+    pub balances: TrackedList<VariableList<u64, T::ValidatorRegistryLimit>>,
 
     // Randomness and committees
-    pub latest_randao_mixes: FixedLenVec<Hash256, T::LatestRandaoMixesLength>,
+    #[cached_tree_hash(cached)]
+    pub latest_randao_mixes: TrackedList<FixedLenVec<Hash256, T::LatestRandaoMixesLength>>,
     pub latest_start_shard: u64,
 
     // Finality
-    pub previous_epoch_attestations: Vec<PendingAttestation>,
-    pub current_epoch_attestations: Vec<PendingAttestation>,
+    pub previous_epoch_attestations: VariableList<PendingAttestation, T::MaxPendingAttestations>,
+    pub current_epoch_attestations: VariableList<PendingAttestation, T::MaxPendingAttestations>,
     pub previous_justified_epoch: Epoch,
     pub current_justified_epoch: Epoch,
     pub previous_justified_root: Hash256,
@@ -108,20 +118,27 @@ where
     pub finalized_root: Hash256,
 
     // Recent state
-    pub current_crosslinks: FixedLenVec<Crosslink, T::ShardCount>,
-    pub previous_crosslinks: FixedLenVec<Crosslink, T::ShardCount>,
-    pub latest_block_roots: FixedLenVec<Hash256, T::SlotsPerHistoricalRoot>,
+    #[cached_tree_hash(cached)]
+    pub current_crosslinks: TrackedList<FixedLenVec<Crosslink, T::ShardCount>>,
+    #[cached_tree_hash(cached)]
+    pub previous_crosslinks: TrackedList<FixedLenVec<Crosslink, T::ShardCount>>,
+    #[cached_tree_hash(cached)]
+    pub latest_block_roots: TrackedList<FixedLenVec<Hash256, T::SlotsPerHistoricalRoot>>,
     #[compare_fields(as_slice)]
-    pub latest_state_roots: FixedLenVec<Hash256, T::SlotsPerHistoricalRoot>,
+    #[cached_tree_hash(cached)]
+    pub latest_state_roots: TrackedList<FixedLenVec<Hash256, T::SlotsPerHistoricalRoot>>,
     #[compare_fields(as_slice)]
-    latest_active_index_roots: FixedLenVec<Hash256, T::LatestActiveIndexRootsLength>,
-    latest_slashed_balances: FixedLenVec<u64, T::LatestSlashedExitLength>,
+    #[cached_tree_hash(cached)]
+    latest_active_index_roots: TrackedList<FixedLenVec<Hash256, T::LatestActiveIndexRootsLength>>,
+    #[cached_tree_hash(cached)]
+    latest_slashed_balances: TrackedList<FixedLenVec<u64, T::LatestSlashedExitLength>>,
     pub latest_block_header: BeaconBlockHeader,
-    pub historical_roots: Vec<Hash256>,
+    #[cached_tree_hash(cached)]
+    pub historical_roots: TrackedList<VariableList<Hash256, T::HistoricalRootsLimit>>,
 
     // Ethereum 1.0 chain data
     pub latest_eth1_data: Eth1Data,
-    pub eth1_data_votes: Vec<Eth1Data>,
+    pub eth1_data_votes: VariableList<Eth1Data, T::SlotsPerEth1VotingPeriod>,
     pub deposit_index: u64,
 
     // Caching (not in the spec)
@@ -142,7 +159,7 @@ where
     #[ssz(skip_deserializing)]
     #[tree_hash(skip_hashing)]
     #[test_random(default)]
-    pub tree_hash_cache: TreeHashCache,
+    pub tree_hash_cache: BeaconTreeHashCache,
     #[serde(skip_serializing, skip_deserializing)]
     #[ssz(skip_serializing)]
     #[ssz(skip_deserializing)]
@@ -176,19 +193,17 @@ impl<T: EthSpec> BeaconState<T> {
             fork: Fork::genesis(T::genesis_epoch()),
 
             // Validator registry
-            validator_registry: vec![], // Set later in the function.
-            balances: vec![],           // Set later in the function.
+            validator_registry: TrackedList::default(), // Set later in the function.
+            balances: TrackedList::default(),           // Set later in the function.
 
             // Randomness and committees
-            latest_randao_mixes: FixedLenVec::from(vec![
-                spec.zero_hash;
-                T::LatestRandaoMixesLength::to_usize()
-            ]),
+            latest_randao_mixes: vec![spec.zero_hash; T::LatestRandaoMixesLength::to_usize()]
+                .into(),
             latest_start_shard: 0,
 
             // Finality
-            previous_epoch_attestations: vec![],
-            current_epoch_attestations: vec![],
+            previous_epoch_attestations: VariableList::empty(),
+            current_epoch_attestations: VariableList::empty(),
             previous_justified_epoch: T::genesis_epoch(),
             current_justified_epoch: T::genesis_epoch(),
             previous_justified_root: spec.zero_hash,
@@ -209,13 +224,13 @@ impl<T: EthSpec> BeaconState<T> {
             .into(),
             latest_slashed_balances: vec![0; T::LatestSlashedExitLength::to_usize()].into(),
             latest_block_header: BeaconBlock::empty(spec).temporary_block_header(spec),
-            historical_roots: vec![],
+            historical_roots: TrackedList::default(),
 
             /*
              * PoW receipt root
              */
             latest_eth1_data,
-            eth1_data_votes: vec![],
+            eth1_data_votes: VariableList::empty(),
             deposit_index: 0,
 
             /*
@@ -227,7 +242,7 @@ impl<T: EthSpec> BeaconState<T> {
                 CommitteeCache::default(),
             ],
             pubkey_cache: PubkeyCache::default(),
-            tree_hash_cache: TreeHashCache::default(),
+            tree_hash_cache: BeaconTreeHashCache::default(),
             exit_cache: ExitCache::default(),
         }
     }
@@ -241,8 +256,8 @@ impl<T: EthSpec> BeaconState<T> {
 
     pub fn historical_batch(&self) -> HistoricalBatch<T> {
         HistoricalBatch {
-            block_roots: self.latest_block_roots.clone(),
-            state_roots: self.latest_state_roots.clone(),
+            block_roots: (*self.latest_block_roots).clone(),
+            state_roots: (*self.latest_state_roots).clone(),
         }
     }
 
@@ -579,7 +594,7 @@ impl<T: EthSpec> BeaconState<T> {
     /// Gets the state root for some slot.
     ///
     /// Spec v0.6.3
-    pub fn get_state_root(&mut self, slot: Slot) -> Result<&Hash256, Error> {
+    pub fn get_state_root(&self, slot: Slot) -> Result<&Hash256, Error> {
         let i = self.get_latest_state_roots_index(slot)?;
         Ok(&self.latest_state_roots[i])
     }
@@ -786,17 +801,20 @@ impl<T: EthSpec> BeaconState<T> {
         self.exit_cache = ExitCache::default();
     }
 
+    /// Returns `true` if the committee cache for `relative_epoch` is built and ready to use.
+    pub fn committee_cache_is_initialized(&self, relative_epoch: RelativeEpoch) -> bool {
+        let i = Self::cache_index(relative_epoch);
+
+        self.committee_caches[i].is_initialized_at(relative_epoch.into_epoch(self.current_epoch()))
+    }
+
     /// Build an epoch cache, unless it is has already been built.
     pub fn build_committee_cache(
         &mut self,
         relative_epoch: RelativeEpoch,
         spec: &ChainSpec,
     ) -> Result<(), Error> {
-        let i = Self::cache_index(relative_epoch);
-
-        if self.committee_caches[i]
-            .is_initialized_at(relative_epoch.into_epoch(self.current_epoch()))
-        {
+        if self.committee_cache_is_initialized(relative_epoch) {
             Ok(())
         } else {
             self.force_build_committee_cache(relative_epoch, spec)
@@ -881,40 +899,37 @@ impl<T: EthSpec> BeaconState<T> {
 
     /// Update the tree hash cache, building it for the first time if it is empty.
     ///
+    /// Only the parts of the state which have changed since the last update are re-hashed.
+    ///
     /// Returns the `tree_hash_root` resulting from the update. This root can be considered the
     /// canonical root of `self`.
     pub fn update_tree_hash_cache(&mut self) -> Result<Hash256, Error> {
-        if self.tree_hash_cache.is_empty() {
-            self.tree_hash_cache = TreeHashCache::new(self)?;
-        } else {
-            // Move the cache outside of `self` to satisfy the borrow checker.
-            let mut cache = std::mem::replace(&mut self.tree_hash_cache, TreeHashCache::default());
+        // Move the cache outside of `self` to satisfy the borrow checker.
+        let mut cache =
+            std::mem::replace(&mut self.tree_hash_cache, BeaconTreeHashCache::default());
 
-            cache.update(self)?;
+        let result = self.recalculate_tree_hash_root(&mut cache);
 
-            // Move the updated cache back into `self`.
-            self.tree_hash_cache = cache
-        }
+        // Move the updated cache back into `self`.
+        self.tree_hash_cache = cache;
 
-        self.cached_tree_hash_root()
+        result.map_err(Into::into)
     }
 
     /// Returns the tree hash root determined by the last execution of `self.update_tree_hash_cache(..)`.
     ///
     /// Note: does _not_ update the cache and may return an outdated root.
     ///
-    /// Returns an error if the cache is not initialized or if an error is encountered during the
-    /// cache update.
+    /// Returns an error if the cache is not initialized.
     pub fn cached_tree_hash_root(&self) -> Result<Hash256, Error> {
         self.tree_hash_cache
-            .tree_hash_root()
-            .and_then(|b| Ok(Hash256::from_slice(b)))
-            .map_err(Into::into)
+            .root()
+            .ok_or_else(|| Error::TreeHashCacheError(TreeHashCacheError::CacheNotInitialized))
     }
 
     /// Completely drops the tree hash cache, replacing it with a new, empty cache.
     pub fn drop_tree_hash_cache(&mut self) {
-        self.tree_hash_cache = TreeHashCache::default()
+        self.tree_hash_cache = BeaconTreeHashCache::default()
     }
 }
 
@@ -929,3 +944,9 @@ impl From<TreeHashCacheError> for Error {
         Error::TreeHashCacheError(e)
     }
 }
+
+impl From<VariableListError> for Error {
+    fn from(e: VariableListError) -> Error {
+        Error::VariableListError(e)
+    }
+}
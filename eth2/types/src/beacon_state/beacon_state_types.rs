@@ -1,5 +1,5 @@
 use crate::*;
-use fixed_len_vec::typenum::{Unsigned, U0, U1024, U64, U8, U8192};
+use fixed_len_vec::typenum::{Unsigned, U0, U1024, U1099511627776, U16, U16777216, U64, U8, U8192};
 use serde_derive::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -13,6 +13,15 @@ pub trait EthSpec: 'static + Default + Sync + Send + Clone + Debug + PartialEq {
     /// it here just for the convenience of not passing `slots_per_epoch` around all the time.
     type SlotsPerEpoch: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type GenesisEpoch: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    /*
+     * Upper bounds on the variable-length `BeaconState` lists, enforced when decoding.
+     */
+    type ValidatorRegistryLimit: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type HistoricalRootsLimit: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    /// Must be at least `MAX_ATTESTATIONS * SLOTS_PER_EPOCH`.
+    type MaxPendingAttestations: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    /// Must be at least `SLOTS_PER_ETH1_VOTING_PERIOD`.
+    type SlotsPerEth1VotingPeriod: Unsigned + Clone + Sync + Send + Debug + PartialEq;
 
     fn default_spec() -> ChainSpec;
 
@@ -115,6 +124,10 @@ impl EthSpec for MainnetEthSpec {
     type LatestSlashedExitLength = U8192;
     type SlotsPerEpoch = U64;
     type GenesisEpoch = U0;
+    type ValidatorRegistryLimit = U1099511627776;
+    type HistoricalRootsLimit = U16777216;
+    type MaxPendingAttestations = U8192;
+    type SlotsPerEth1VotingPeriod = U1024;
 
     fn default_spec() -> ChainSpec {
         ChainSpec::mainnet()
@@ -139,6 +152,10 @@ impl EthSpec for MinimalEthSpec {
     type LatestSlashedExitLength = U64;
     type SlotsPerEpoch = U8;
     type GenesisEpoch = U0;
+    type ValidatorRegistryLimit = U1099511627776;
+    type HistoricalRootsLimit = U16777216;
+    type MaxPendingAttestations = U1024;
+    type SlotsPerEth1VotingPeriod = U16;
 
     fn default_spec() -> ChainSpec {
         ChainSpec::minimal()
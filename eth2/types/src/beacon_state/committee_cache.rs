@@ -1,9 +1,11 @@
-use super::BeaconState;
+use super::{BeaconState, ShufflingCache, ShufflingId};
 use crate::*;
 use core::num::NonZeroUsize;
+use parking_lot::Mutex;
 use serde_derive::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
 use std::ops::Range;
+use std::sync::Arc;
 use swap_or_not_shuffle::shuffle_list;
 
 mod tests;
@@ -24,11 +26,25 @@ pub struct CommitteeCache {
 impl CommitteeCache {
     /// Return a new, fully initialized cache.
     ///
+    /// Shufflings are shared with other states via the process-wide `ShufflingCache`.
+    ///
     /// Spec v0.6.3
     pub fn initialized<T: EthSpec>(
         state: &BeaconState<T>,
         epoch: Epoch,
         spec: &ChainSpec,
+    ) -> Result<CommitteeCache, Error> {
+        Self::initialized_with_shuffling_cache(state, epoch, ShufflingCache::global(), spec)
+    }
+
+    /// As per `initialized`, reading and storing the shuffling in `shuffling_cache`.
+    ///
+    /// The lock on `shuffling_cache` is not held whilst shuffling.
+    pub fn initialized_with_shuffling_cache<T: EthSpec>(
+        state: &BeaconState<T>,
+        epoch: Epoch,
+        shuffling_cache: &Mutex<ShufflingCache>,
+        spec: &ChainSpec,
     ) -> Result<CommitteeCache, Error> {
         let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch)
             .map_err(|_| Error::EpochOutOfBounds)?;
@@ -53,15 +69,36 @@ impl CommitteeCache {
         let shuffling_start_shard =
             Self::compute_start_shard(state, relative_epoch, active_validator_indices.len(), spec);
 
-        let seed = state.generate_seed(epoch, spec)?;
-
-        let shuffling = shuffle_list(
-            active_validator_indices,
-            spec.shuffle_round_count,
-            &seed[..],
-            false,
-        )
-        .ok_or_else(|| Error::UnableToShuffle)?;
+        let shuffling_id = ShufflingId {
+            seed: state.generate_seed(epoch, spec)?,
+            active_index_root: state.get_active_index_root(epoch, spec)?,
+        };
+
+        // Shuffling is expensive, so re-use the shuffling from any state (e.g., on a sibling fork)
+        // which has the same seed and active validators.
+        let cached_shuffling = shuffling_cache
+            .lock()
+            .get(&shuffling_id, &active_validator_indices);
+        let shuffling = match cached_shuffling {
+            Some(shuffling) => shuffling.to_vec(),
+            None => {
+                let shuffling = shuffle_list(
+                    active_validator_indices.clone(),
+                    spec.shuffle_round_count,
+                    &shuffling_id.seed[..],
+                    false,
+                )
+                .ok_or_else(|| Error::UnableToShuffle)?;
+
+                shuffling_cache.lock().insert(
+                    shuffling_id,
+                    active_validator_indices,
+                    Arc::new(shuffling.clone()),
+                );
+
+                shuffling
+            }
+        };
 
         // The use of `NonZeroUsize` reduces the maximum number of possible validators by one.
         if state.validator_registry.len() > usize::max_value() - 1 {
@@ -75,7 +75,7 @@ fn shuffles_for_the_right_epoch() {
         .map(|i| Hash256::from(i as u64))
         .collect();
 
-    state.latest_randao_mixes = FixedLenVec::from(distinct_hashes);
+    state.latest_randao_mixes = distinct_hashes.into();
 
     let previous_seed = state.generate_seed(state.previous_epoch(), spec).unwrap();
     let current_seed = state.generate_seed(state.current_epoch(), spec).unwrap();
@@ -160,6 +160,10 @@ impl EthSpec for ExcessShardsEthSpec {
     type LatestSlashedExitLength = U8192;
     type SlotsPerEpoch = U8;
     type GenesisEpoch = U0;
+    type ValidatorRegistryLimit = U1099511627776;
+    type HistoricalRootsLimit = U16777216;
+    type MaxPendingAttestations = U1024;
+    type SlotsPerEth1VotingPeriod = U16;
 
     fn default_spec() -> ChainSpec {
         ChainSpec::minimal()
@@ -251,3 +255,50 @@ fn starts_on_the_correct_shard() {
         );
     }
 }
+
+#[test]
+fn reuses_shuffling_across_states() {
+    let state = new_state::<MinimalEthSpec>(64, Slot::new(0));
+    let spec = &MinimalEthSpec::default_spec();
+    let epoch = state.current_epoch();
+    let shuffling_cache = Mutex::new(ShufflingCache::new(SHUFFLING_CACHE_SIZE));
+
+    let cache =
+        CommitteeCache::initialized_with_shuffling_cache(&state, epoch, &shuffling_cache, spec)
+            .unwrap();
+    assert_eq!(shuffling_cache.lock().hits(), 0);
+    assert_eq!(shuffling_cache.lock().misses(), 1);
+
+    let sibling = state.clone();
+    let sibling_cache =
+        CommitteeCache::initialized_with_shuffling_cache(&sibling, epoch, &shuffling_cache, spec)
+            .unwrap();
+    assert_eq!(shuffling_cache.lock().hits(), 1);
+    assert_eq!(shuffling_cache.lock().misses(), 1);
+
+    assert_eq!(cache, sibling_cache);
+}
+
+#[test]
+fn does_not_reuse_shuffling_of_other_validators() {
+    let mut state = new_state::<MinimalEthSpec>(64, Slot::new(0));
+    let spec = &MinimalEthSpec::default_spec();
+    let epoch = state.current_epoch();
+
+    CommitteeCache::initialized(&state, epoch, spec).unwrap();
+
+    // Deactivate a validator without updating `latest_active_index_roots`, so the `ShufflingId`
+    // is unchanged.
+    state.validator_registry[0].exit_epoch = epoch;
+
+    let cache = CommitteeCache::initialized(&state, epoch, spec).unwrap();
+    let expected = shuffle_list(
+        get_active_validator_indices(&state.validator_registry, epoch),
+        spec.shuffle_round_count,
+        &state.generate_seed(epoch, spec).unwrap()[..],
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(cache.shuffling, expected);
+}
@@ -0,0 +1,205 @@
+use crate::Hash256;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// The number of shufflings stored by the process-wide cache.
+///
+/// Each entry for 300k validators is ~4.8MB. Sibling forks share shufflings, so the previous,
+/// current and next epochs of a few forks should fit.
+pub const SHUFFLING_CACHE_SIZE: usize = 8;
+
+lazy_static! {
+    static ref SHUFFLING_CACHE: Mutex<ShufflingCache> =
+        Mutex::new(ShufflingCache::new(SHUFFLING_CACHE_SIZE));
+}
+
+/// Identifies a shuffling of the active validators at some epoch.
+///
+/// The `seed` commits to the epoch and its `active_index_root`, whilst the `active_index_root`
+/// commits to the list of active validators that is shuffled. Two states (e.g., on sibling forks)
+/// with the same `ShufflingId` for an epoch will therefore have the same shuffling.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ShufflingId {
+    pub seed: Hash256,
+    pub active_index_root: Hash256,
+}
+
+/// A least-recently-used cache of shufflings, so that states which share a shuffling (e.g., on
+/// sibling forks, or clones of the same state) only compute it once.
+///
+/// The active validator indices are stored alongside each shuffling and must match for a
+/// shuffling to be returned. This protects against states which do not maintain their
+/// `latest_active_index_roots` (e.g., some testing states) at the cost of a comparison.
+///
+/// `CommitteeCache::initialized` uses a single cache shared by the whole process, see
+/// `ShufflingCache::global`. Other caches may be used via
+/// `CommitteeCache::initialized_with_shuffling_cache`.
+#[derive(Debug, Clone)]
+pub struct ShufflingCache {
+    /// Ordered from least to most recently used.
+    entries: Vec<Entry>,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl ShufflingCache {
+    /// Returns an empty cache which will store at most `capacity` shufflings.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            capacity,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the shuffling of `active_validator_indices` for `id`, if known.
+    pub fn get(
+        &mut self,
+        id: &ShufflingId,
+        active_validator_indices: &[usize],
+    ) -> Option<Arc<Vec<usize>>> {
+        let position = self.entries.iter().position(|entry| {
+            entry.id == *id && entry.active_validator_indices[..] == *active_validator_indices
+        });
+
+        match position {
+            Some(i) => {
+                self.hits += 1;
+
+                let entry = self.entries.remove(i);
+                let shuffling = entry.shuffling.clone();
+                self.entries.push(entry);
+
+                Some(shuffling)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Stores the `shuffling` of `active_validator_indices` for `id`, evicting the least
+    /// recently used shuffling if the cache is full.
+    pub fn insert(
+        &mut self,
+        id: ShufflingId,
+        active_validator_indices: Vec<usize>,
+        shuffling: Arc<Vec<usize>>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.entries.retain(|entry| entry.id != id);
+
+        if self.entries.len() >= self.capacity {
+            self.entries.remove(0);
+        }
+
+        self.entries.push(Entry {
+            id,
+            active_validator_indices,
+            shuffling,
+        });
+    }
+
+    /// Returns the number of shufflings stored.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no shufflings stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of calls to `get` which found a shuffling.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of calls to `get` which did not find a shuffling.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Returns the process-wide cache, used by `CommitteeCache::initialized`.
+    pub fn global() -> &'static Mutex<ShufflingCache> {
+        &SHUFFLING_CACHE
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    id: ShufflingId,
+    active_validator_indices: Vec<usize>,
+    shuffling: Arc<Vec<usize>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(i: u64) -> ShufflingId {
+        ShufflingId {
+            seed: Hash256::from_low_u64_be(i),
+            active_index_root: Hash256::from_low_u64_be(i),
+        }
+    }
+
+    #[test]
+    fn get_and_insert() {
+        let mut cache = ShufflingCache::new(2);
+
+        assert_eq!(cache.get(&id(1), &[1]), None);
+
+        cache.insert(id(1), vec![1], Arc::new(vec![1]));
+        assert_eq!(cache.get(&id(1), &[1]), Some(Arc::new(vec![1])));
+
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = ShufflingCache::new(2);
+
+        cache.insert(id(1), vec![1], Arc::new(vec![1]));
+        cache.insert(id(2), vec![2], Arc::new(vec![2]));
+
+        // Use `1`, so that `2` is the least recently used.
+        assert!(cache.get(&id(1), &[1]).is_some());
+
+        cache.insert(id(3), vec![3], Arc::new(vec![3]));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&id(1), &[1]).is_some());
+        assert!(cache.get(&id(2), &[2]).is_none());
+        assert!(cache.get(&id(3), &[3]).is_some());
+    }
+
+    #[test]
+    fn replaces_existing() {
+        let mut cache = ShufflingCache::new(2);
+
+        cache.insert(id(1), vec![1], Arc::new(vec![1]));
+        cache.insert(id(1), vec![1], Arc::new(vec![2]));
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&id(1), &[1]), Some(Arc::new(vec![2])));
+    }
+
+    #[test]
+    fn requires_matching_active_validator_indices() {
+        let mut cache = ShufflingCache::new(2);
+
+        cache.insert(id(1), vec![0, 1], Arc::new(vec![1, 0]));
+
+        assert_eq!(cache.get(&id(1), &[0, 2]), None);
+        assert_eq!(cache.get(&id(1), &[0, 1]), Some(Arc::new(vec![1, 0])));
+    }
+}
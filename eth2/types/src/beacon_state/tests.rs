@@ -4,7 +4,6 @@ use crate::test_utils::*;
 use std::ops::RangeInclusive;
 
 ssz_tests!(FoundationBeaconState);
-cached_tree_hash_tests!(FoundationBeaconState);
 
 fn test_beacon_proposer_index<T: EthSpec>() {
     let spec = T::default_spec();
@@ -144,9 +143,13 @@ fn test_cache_initialization<'a, T: EthSpec>(
         ))
     );
 
+    assert!(!state.committee_cache_is_initialized(relative_epoch));
+
     // Build the cache.
     state.build_committee_cache(relative_epoch, spec).unwrap();
 
+    assert!(state.committee_cache_is_initialized(relative_epoch));
+
     // Assert a call to a cache-using function passes.
     let _ = state
         .get_beacon_proposer_index(slot, relative_epoch, spec)
@@ -155,6 +158,8 @@ fn test_cache_initialization<'a, T: EthSpec>(
     // Drop the cache.
     state.drop_committee_cache(relative_epoch);
 
+    assert!(!state.committee_cache_is_initialized(relative_epoch));
+
     // Assert a call to a cache-using function fail.
     assert_eq!(
         state.get_beacon_proposer_index(slot, relative_epoch, spec),
@@ -199,6 +204,116 @@ fn tree_hash_cache() {
     assert_eq!(root.as_bytes(), &state.tree_hash_root()[..]);
 }
 
+/// Applies a random modification to `state`, of a kind made during block or epoch processing.
+fn mutate_state<T: EthSpec>(state: &mut BeaconState<T>, rng: &mut XorShiftRng) {
+    let random_index = |rng: &mut XorShiftRng, len: usize| rng.next_u64() as usize % len;
+
+    match rng.next_u64() % 8 {
+        0 if !state.balances.is_empty() => {
+            let i = random_index(rng, state.balances.len());
+            state.balances[i] += 1;
+        }
+        1 if !state.validator_registry.is_empty() => {
+            let i = random_index(rng, state.validator_registry.len());
+            state.validator_registry[i].effective_balance = rng.next_u64();
+            state.validator_registry[i].exit_epoch = Epoch::new(rng.next_u64());
+        }
+        2 => {
+            state
+                .validator_registry
+                .push(Validator::random_for_test(rng))
+                .unwrap();
+            state.balances.push(rng.next_u64()).unwrap();
+        }
+        3 => {
+            let i = random_index(rng, state.latest_randao_mixes.len());
+            state.latest_randao_mixes[i] = Hash256::random_for_test(rng);
+
+            let i = random_index(rng, state.latest_active_index_roots.len());
+            state.latest_active_index_roots[i] = Hash256::random_for_test(rng);
+        }
+        4 => {
+            let i = random_index(rng, state.current_crosslinks.len());
+            state.current_crosslinks[i] = Crosslink::random_for_test(rng);
+        }
+        5 => {
+            let i = random_index(rng, state.latest_block_roots.len());
+            state.latest_block_roots[i] = Hash256::random_for_test(rng);
+            state.latest_state_roots[i] = Hash256::random_for_test(rng);
+
+            let i = random_index(rng, state.latest_slashed_balances.len());
+            state.latest_slashed_balances[i] = rng.next_u64();
+        }
+        6 => {
+            state
+                .historical_roots
+                .push(Hash256::random_for_test(rng))
+                .unwrap();
+        }
+        _ => {
+            state.slot += 1;
+            state.previous_crosslinks = state.current_crosslinks.clone();
+        }
+    }
+}
+
+#[test]
+fn tree_hash_cache_random_modifications() {
+    use tree_hash::TreeHash;
+
+    let mut rng = XorShiftRng::from_seed([42; 16]);
+
+    let mut state: BeaconState<MinimalEthSpec> = BeaconState::random_for_test(&mut rng);
+    assert!(state.cached_tree_hash_root().is_err());
+
+    for _ in 0..64 {
+        for _ in 0..rng.next_u64() % 4 {
+            mutate_state(&mut state, &mut rng);
+        }
+
+        let root = state.update_tree_hash_cache().unwrap();
+        assert_eq!(root.as_bytes(), &state.tree_hash_root()[..]);
+        assert_eq!(state.cached_tree_hash_root(), Ok(root));
+    }
+
+    // Ensure the updated cache is identical to a new cache.
+    let mut fresh = state.clone();
+    fresh.drop_tree_hash_cache();
+    fresh.update_tree_hash_cache().unwrap();
+    assert_eq!(fresh.tree_hash_cache, state.tree_hash_cache);
+}
+
+#[test]
+fn tree_hash_proofs() {
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use tree_hash::{PathElement, TreeHash};
+
+    let mut rng = XorShiftRng::from_seed([42; 16]);
+
+    let mut state: FoundationBeaconState = BeaconState::random_for_test(&mut rng);
+    state
+        .validator_registry
+        .push(Validator::random_for_test(&mut rng))
+        .unwrap();
+    let root = Hash256::from_slice(&state.tree_hash_root());
+
+    let gindex = state
+        .tree_hash_gindex(&[PathElement::Field("finalized_root")])
+        .unwrap();
+    let proof = state.tree_hash_proof(gindex).unwrap();
+    assert_eq!(proof.leaf, state.finalized_root);
+    assert!(proof.verify(root));
+
+    let path = [
+        PathElement::Field("validator_registry"),
+        PathElement::Index(state.validator_registry.len() - 1),
+        PathElement::Field("effective_balance"),
+    ];
+    let gindex = state.tree_hash_gindex(&path).unwrap();
+    let proof = state.tree_hash_proof(gindex).unwrap();
+    assert!(proof.verify(root));
+}
+
 /// Tests committee-specific components
 #[cfg(test)]
 mod committees {
@@ -310,7 +425,7 @@ mod committees {
             .into_iter()
             .map(|i| Hash256::from(i as u64))
             .collect();
-        state.latest_randao_mixes = FixedLenVec::from(distinct_hashes);
+        state.latest_randao_mixes = distinct_hashes.into();
 
         state
             .build_committee_cache(RelativeEpoch::Previous, spec)
@@ -0,0 +1,30 @@
+use super::Hash256;
+use cached_tree_hash::{ListTreeHashCache, TreeHashCache};
+
+/// The tree hash cache for a `BeaconState`, used by its `CachedTreeHash` implementation.
+///
+/// `fields` holds the roots of every field of the state. The large lists and vectors of the state
+/// are each a `TrackedList` with its own cache, which records the items modified since the last
+/// update. A change to a single item (e.g., one balance) therefore only re-packs that item's leaf
+/// and re-hashes the path from it to the root of the state.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BeaconTreeHashCache {
+    pub(crate) fields: TreeHashCache,
+    pub(crate) validator_registry: ListTreeHashCache,
+    pub(crate) balances: ListTreeHashCache,
+    pub(crate) latest_randao_mixes: ListTreeHashCache,
+    pub(crate) current_crosslinks: ListTreeHashCache,
+    pub(crate) previous_crosslinks: ListTreeHashCache,
+    pub(crate) latest_block_roots: ListTreeHashCache,
+    pub(crate) latest_state_roots: ListTreeHashCache,
+    pub(crate) latest_active_index_roots: ListTreeHashCache,
+    pub(crate) latest_slashed_balances: ListTreeHashCache,
+    pub(crate) historical_roots: ListTreeHashCache,
+}
+
+impl BeaconTreeHashCache {
+    /// Returns the root of the state determined by the last update of the cache, if any.
+    pub fn root(&self) -> Option<Hash256> {
+        self.fields.root()
+    }
+}
@@ -243,6 +243,7 @@ impl ChainSpec {
             shuffle_round_count: 10,
             min_attestation_inclusion_delay: 2,
             slots_per_eth1_voting_period: 16,
+            slots_per_historical_root: 64,
             genesis_slot,
             chain_id: 2, // lighthouse testnet chain id
             boot_nodes,
@@ -257,6 +258,295 @@ impl Default for ChainSpec {
     }
 }
 
+/// The representation of a `ChainSpec` used by the configuration files of the Ethereum 2.0
+/// specification (e.g., `configs/constant_presets/minimal.yaml`).
+///
+/// Unknown keys are ignored, as are `FAR_FUTURE_EPOCH`, `ZERO_HASH` and
+/// `BLS_WITHDRAWAL_PREFIX_BYTE` which are not configurable. Values which are not part of the
+/// specification (e.g., `genesis_time`, `boot_nodes`) are retained from the `ChainSpec` to which
+/// the config is applied.
+///
+/// Spec v0.6.3
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct YamlConfig {
+    /*
+     * Misc
+     */
+    pub shard_count: u64,
+    pub target_committee_size: usize,
+    pub max_indices_per_attestation: u64,
+    pub min_per_epoch_churn_limit: u64,
+    pub churn_limit_quotient: u64,
+    pub base_rewards_per_epoch: u64,
+    pub shuffle_round_count: u8,
+
+    /*
+     *  Deposit contract
+     */
+    pub deposit_contract_tree_depth: u64,
+
+    /*
+     *  Gwei values
+     */
+    pub min_deposit_amount: u64,
+    pub max_effective_balance: u64,
+    pub ejection_balance: u64,
+    pub effective_balance_increment: u64,
+
+    /*
+     * Initial Values
+     */
+    pub genesis_slot: u64,
+    pub genesis_epoch: u64,
+
+    /*
+     * Time parameters
+     */
+    pub seconds_per_slot: u64,
+    pub min_attestation_inclusion_delay: u64,
+    pub slots_per_epoch: u64,
+    pub min_seed_lookahead: u64,
+    pub activation_exit_delay: u64,
+    pub slots_per_eth1_voting_period: u64,
+    pub slots_per_historical_root: u64,
+    pub min_validator_withdrawability_delay: u64,
+    pub persistent_committee_period: u64,
+    pub max_crosslink_epochs: u64,
+    pub min_epochs_to_inactivity_penalty: u64,
+
+    /*
+     * State list lengths
+     */
+    pub latest_randao_mixes_length: u64,
+    pub latest_active_index_roots_length: u64,
+    pub latest_slashed_exit_length: u64,
+
+    /*
+     * Reward and penalty quotients
+     */
+    pub base_reward_quotient: u64,
+    pub whistleblowing_reward_quotient: u64,
+    pub proposer_reward_quotient: u64,
+    pub inactivity_penalty_quotient: u64,
+    pub min_slashing_penalty_quotient: u64,
+
+    /*
+     * Max operations per block
+     */
+    pub max_proposer_slashings: u64,
+    pub max_attester_slashings: u64,
+    pub max_attestations: u64,
+    pub max_deposits: u64,
+    pub max_voluntary_exits: u64,
+    pub max_transfers: u64,
+
+    /*
+     * Signature domains
+     */
+    pub domain_beacon_proposer: u32,
+    pub domain_randao: u32,
+    pub domain_attestation: u32,
+    pub domain_deposit: u32,
+    pub domain_voluntary_exit: u32,
+    pub domain_transfer: u32,
+}
+
+impl YamlConfig {
+    /// Returns the config equivalent to `spec` and the compile-time constants of `T`.
+    pub fn from_spec<T: EthSpec>(spec: &ChainSpec) -> Self {
+        Self {
+            shard_count: T::shard_count() as u64,
+            target_committee_size: spec.target_committee_size,
+            max_indices_per_attestation: spec.max_indices_per_attestation,
+            min_per_epoch_churn_limit: spec.min_per_epoch_churn_limit,
+            churn_limit_quotient: spec.churn_limit_quotient,
+            base_rewards_per_epoch: spec.base_rewards_per_epoch,
+            shuffle_round_count: spec.shuffle_round_count,
+            deposit_contract_tree_depth: spec.deposit_contract_tree_depth,
+            min_deposit_amount: spec.min_deposit_amount,
+            max_effective_balance: spec.max_effective_balance,
+            ejection_balance: spec.ejection_balance,
+            effective_balance_increment: spec.effective_balance_increment,
+            genesis_slot: spec.genesis_slot.as_u64(),
+            genesis_epoch: T::genesis_epoch().as_u64(),
+            seconds_per_slot: spec.seconds_per_slot,
+            min_attestation_inclusion_delay: spec.min_attestation_inclusion_delay,
+            slots_per_epoch: T::slots_per_epoch(),
+            min_seed_lookahead: spec.min_seed_lookahead.as_u64(),
+            activation_exit_delay: spec.activation_exit_delay,
+            slots_per_eth1_voting_period: spec.slots_per_eth1_voting_period,
+            slots_per_historical_root: T::slots_per_historical_root() as u64,
+            min_validator_withdrawability_delay: spec.min_validator_withdrawability_delay.as_u64(),
+            persistent_committee_period: spec.persistent_committee_period,
+            max_crosslink_epochs: spec.max_crosslink_epochs,
+            min_epochs_to_inactivity_penalty: spec.min_epochs_to_inactivity_penalty,
+            latest_randao_mixes_length: T::latest_randao_mixes_length() as u64,
+            latest_active_index_roots_length: T::latest_active_index_roots() as u64,
+            latest_slashed_exit_length: T::latest_slashed_exit_length() as u64,
+            base_reward_quotient: spec.base_reward_quotient,
+            whistleblowing_reward_quotient: spec.whistleblowing_reward_quotient,
+            proposer_reward_quotient: spec.proposer_reward_quotient,
+            inactivity_penalty_quotient: spec.inactivity_penalty_quotient,
+            min_slashing_penalty_quotient: spec.min_slashing_penalty_quotient,
+            max_proposer_slashings: spec.max_proposer_slashings,
+            max_attester_slashings: spec.max_attester_slashings,
+            max_attestations: spec.max_attestations,
+            max_deposits: spec.max_deposits,
+            max_voluntary_exits: spec.max_voluntary_exits,
+            max_transfers: spec.max_transfers,
+            domain_beacon_proposer: spec.domain_beacon_proposer,
+            domain_randao: spec.domain_randao,
+            domain_attestation: spec.domain_attestation,
+            domain_deposit: spec.domain_deposit,
+            domain_voluntary_exit: spec.domain_voluntary_exit,
+            domain_transfer: spec.domain_transfer,
+        }
+    }
+
+    /// Returns a copy of `spec` with all values defined by `self` replaced.
+    ///
+    /// Returns an error if `self` disagrees with any of the compile-time constants of `T` (e.g.,
+    /// `SLOTS_PER_EPOCH`), or exceeds any of the compile-time list lengths (e.g., `MAX_DEPOSITS`),
+    /// as a `BeaconState<T>` or `BeaconBlock` could not represent such a chain.
+    pub fn apply_to_chain_spec<T: EthSpec>(&self, spec: &ChainSpec) -> Result<ChainSpec, String> {
+        let expected = Self::from_spec::<T>(spec);
+
+        let compile_time_constants = [
+            ("SHARD_COUNT", self.shard_count, expected.shard_count),
+            ("GENESIS_EPOCH", self.genesis_epoch, expected.genesis_epoch),
+            (
+                "SLOTS_PER_EPOCH",
+                self.slots_per_epoch,
+                expected.slots_per_epoch,
+            ),
+            (
+                "SLOTS_PER_HISTORICAL_ROOT",
+                self.slots_per_historical_root,
+                expected.slots_per_historical_root,
+            ),
+            (
+                "LATEST_RANDAO_MIXES_LENGTH",
+                self.latest_randao_mixes_length,
+                expected.latest_randao_mixes_length,
+            ),
+            (
+                "LATEST_ACTIVE_INDEX_ROOTS_LENGTH",
+                self.latest_active_index_roots_length,
+                expected.latest_active_index_roots_length,
+            ),
+            (
+                "LATEST_SLASHED_EXIT_LENGTH",
+                self.latest_slashed_exit_length,
+                expected.latest_slashed_exit_length,
+            ),
+        ];
+
+        for (name, value, compiled) in compile_time_constants.iter() {
+            if value != compiled {
+                return Err(format!(
+                    "{} is {} in the config, however it is compiled as {}",
+                    name, value, compiled
+                ));
+            }
+        }
+
+        let upper_bounds = [
+            (
+                "MAX_INDICES_PER_ATTESTATION",
+                self.max_indices_per_attestation,
+                MaxIndicesPerAttestation::to_u64(),
+            ),
+            (
+                "MAX_PROPOSER_SLASHINGS",
+                self.max_proposer_slashings,
+                MaxProposerSlashings::to_u64(),
+            ),
+            (
+                "MAX_ATTESTER_SLASHINGS",
+                self.max_attester_slashings,
+                MaxAttesterSlashings::to_u64(),
+            ),
+            (
+                "MAX_ATTESTATIONS",
+                self.max_attestations,
+                MaxAttestations::to_u64(),
+            ),
+            (
+                "MAX_ATTESTATIONS * SLOTS_PER_EPOCH",
+                self.max_attestations.saturating_mul(self.slots_per_epoch),
+                T::MaxPendingAttestations::to_u64(),
+            ),
+            ("MAX_DEPOSITS", self.max_deposits, MaxDeposits::to_u64()),
+            (
+                "MAX_VOLUNTARY_EXITS",
+                self.max_voluntary_exits,
+                MaxVoluntaryExits::to_u64(),
+            ),
+            ("MAX_TRANSFERS", self.max_transfers, MaxTransfers::to_u64()),
+            (
+                "SLOTS_PER_ETH1_VOTING_PERIOD",
+                self.slots_per_eth1_voting_period,
+                T::SlotsPerEth1VotingPeriod::to_u64(),
+            ),
+        ];
+
+        for (name, value, limit) in upper_bounds.iter() {
+            if value > limit {
+                return Err(format!(
+                    "{} is {} in the config, however it is compiled with a limit of {}",
+                    name, value, limit
+                ));
+            }
+        }
+
+        Ok(ChainSpec {
+            target_committee_size: self.target_committee_size,
+            max_indices_per_attestation: self.max_indices_per_attestation,
+            min_per_epoch_churn_limit: self.min_per_epoch_churn_limit,
+            churn_limit_quotient: self.churn_limit_quotient,
+            base_rewards_per_epoch: self.base_rewards_per_epoch,
+            shuffle_round_count: self.shuffle_round_count,
+            deposit_contract_tree_depth: self.deposit_contract_tree_depth,
+            min_deposit_amount: self.min_deposit_amount,
+            max_effective_balance: self.max_effective_balance,
+            ejection_balance: self.ejection_balance,
+            effective_balance_increment: self.effective_balance_increment,
+            genesis_slot: Slot::new(self.genesis_slot),
+            seconds_per_slot: self.seconds_per_slot,
+            min_attestation_inclusion_delay: self.min_attestation_inclusion_delay,
+            min_seed_lookahead: Epoch::new(self.min_seed_lookahead),
+            activation_exit_delay: self.activation_exit_delay,
+            slots_per_eth1_voting_period: self.slots_per_eth1_voting_period,
+            slots_per_historical_root: self.slots_per_historical_root as usize,
+            min_validator_withdrawability_delay: Epoch::new(
+                self.min_validator_withdrawability_delay,
+            ),
+            persistent_committee_period: self.persistent_committee_period,
+            max_crosslink_epochs: self.max_crosslink_epochs,
+            min_epochs_to_inactivity_penalty: self.min_epochs_to_inactivity_penalty,
+            base_reward_quotient: self.base_reward_quotient,
+            whistleblowing_reward_quotient: self.whistleblowing_reward_quotient,
+            proposer_reward_quotient: self.proposer_reward_quotient,
+            inactivity_penalty_quotient: self.inactivity_penalty_quotient,
+            min_slashing_penalty_quotient: self.min_slashing_penalty_quotient,
+            max_proposer_slashings: self.max_proposer_slashings,
+            max_attester_slashings: self.max_attester_slashings,
+            max_attestations: self.max_attestations,
+            max_deposits: self.max_deposits,
+            max_voluntary_exits: self.max_voluntary_exits,
+            max_transfers: self.max_transfers,
+            domain_beacon_proposer: self.domain_beacon_proposer,
+            domain_randao: self.domain_randao,
+            domain_attestation: self.domain_attestation,
+            domain_deposit: self.domain_deposit,
+            domain_voluntary_exit: self.domain_voluntary_exit,
+            domain_transfer: self.domain_transfer,
+            ..spec.clone()
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,4 +580,83 @@ mod tests {
         test_domain(Domain::VoluntaryExit, spec.domain_voluntary_exit, &spec);
         test_domain(Domain::Transfer, spec.domain_transfer, &spec);
     }
+
+    #[test]
+    fn yaml_config_round_trip() {
+        let spec = ChainSpec::minimal();
+
+        let yaml = serde_yaml::to_string(&YamlConfig::from_spec::<MinimalEthSpec>(&spec)).unwrap();
+        let config: YamlConfig = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(
+            config.apply_to_chain_spec::<MinimalEthSpec>(&ChainSpec::mainnet()),
+            Ok(ChainSpec {
+                chain_id: ChainSpec::mainnet().chain_id,
+                boot_nodes: ChainSpec::mainnet().boot_nodes,
+                ..spec
+            })
+        );
+    }
+
+    #[test]
+    fn yaml_config_rejects_mismatched_lengths() {
+        let config = YamlConfig::from_spec::<MinimalEthSpec>(&ChainSpec::minimal());
+
+        assert!(config
+            .apply_to_chain_spec::<MainnetEthSpec>(&ChainSpec::mainnet())
+            .is_err());
+    }
+
+    #[test]
+    fn yaml_config_rejects_lists_exceeding_limits() {
+        let spec = ChainSpec::minimal();
+        let config = YamlConfig::from_spec::<MinimalEthSpec>(&spec);
+
+        let exceeding = [
+            YamlConfig {
+                max_indices_per_attestation: MaxIndicesPerAttestation::to_u64() + 1,
+                ..config.clone()
+            },
+            YamlConfig {
+                max_proposer_slashings: MaxProposerSlashings::to_u64() + 1,
+                ..config.clone()
+            },
+            YamlConfig {
+                max_attester_slashings: MaxAttesterSlashings::to_u64() + 1,
+                ..config.clone()
+            },
+            YamlConfig {
+                max_attestations: MaxAttestations::to_u64() + 1,
+                ..config.clone()
+            },
+            YamlConfig {
+                max_deposits: MaxDeposits::to_u64() + 1,
+                ..config.clone()
+            },
+            YamlConfig {
+                max_voluntary_exits: MaxVoluntaryExits::to_u64() + 1,
+                ..config.clone()
+            },
+            YamlConfig {
+                max_transfers: MaxTransfers::to_u64() + 1,
+                ..config.clone()
+            },
+            YamlConfig {
+                slots_per_eth1_voting_period:
+                    <MinimalEthSpec as EthSpec>::SlotsPerEth1VotingPeriod::to_u64() + 1,
+                ..config.clone()
+            },
+        ];
+
+        for config in exceeding.iter() {
+            assert!(config.apply_to_chain_spec::<MinimalEthSpec>(&spec).is_err());
+        }
+
+        // At the limit is fine.
+        let config = YamlConfig {
+            max_deposits: MaxDeposits::to_u64(),
+            ..config
+        };
+        assert!(config.apply_to_chain_spec::<MinimalEthSpec>(&spec).is_ok());
+    }
 }
@@ -41,11 +41,11 @@ pub use crate::attestation_data::AttestationData;
 pub use crate::attestation_data_and_custody_bit::AttestationDataAndCustodyBit;
 pub use crate::attestation_duty::AttestationDuty;
 pub use crate::attester_slashing::AttesterSlashing;
-pub use crate::beacon_block::BeaconBlock;
+pub use crate::beacon_block::{BeaconBlock, BeaconBlockView};
 pub use crate::beacon_block_body::BeaconBlockBody;
 pub use crate::beacon_block_header::BeaconBlockHeader;
 pub use crate::beacon_state::{Error as BeaconStateError, *};
-pub use crate::chain_spec::{ChainSpec, Domain};
+pub use crate::chain_spec::{ChainSpec, Domain, YamlConfig};
 pub use crate::crosslink::Crosslink;
 pub use crate::crosslink_committee::{CrosslinkCommittee, OwnedCrosslinkCommittee};
 pub use crate::deposit::Deposit;
@@ -73,6 +73,22 @@ pub type Address = H160;
 pub type EthBalance = U256;
 pub type Bitfield = boolean_bitfield::BooleanBitfield;
 pub type BitfieldError = boolean_bitfield::Error;
+/// An attestation bitfield, with at most `MaxIndicesPerAttestation` bits.
+pub type AttestationBitfield = boolean_bitfield::BitList<MaxIndicesPerAttestation>;
+
+/*
+ * Upper bounds on variable-length lists in blocks and attestations, enforced when decoding.
+ *
+ * These are no smaller than the corresponding `ChainSpec` constants under the Ethereum Foundation
+ * specification. Block processing continues to enforce the (possibly smaller) `ChainSpec` values.
+ */
+pub type MaxIndicesPerAttestation = typenum::U4096;
+pub type MaxProposerSlashings = typenum::U16;
+pub type MaxAttesterSlashings = typenum::U1;
+pub type MaxAttestations = typenum::U128;
+pub type MaxDeposits = typenum::U16;
+pub type MaxVoluntaryExits = typenum::U16;
+pub type MaxTransfers = typenum::U16;
 
 /// Maps a (slot, shard_id) to attestation_indices.
 pub type AttesterMap = HashMap<(u64, u64), Vec<usize>>;
@@ -81,7 +97,11 @@ pub type AttesterMap = HashMap<(u64, u64), Vec<usize>>;
 pub type ProposerMap = HashMap<u64, usize>;
 
 pub use bls::{AggregatePublicKey, AggregateSignature, Keypair, PublicKey, SecretKey, Signature};
-pub use fixed_len_vec::{typenum, typenum::Unsigned, FixedLenVec};
+pub use boolean_bitfield::{BitList, BitVector};
+pub use cached_tree_hash::TrackedList;
+pub use fixed_len_vec::{
+    typenum, typenum::Unsigned, Error as VariableListError, FixedLenVec, VariableList,
+};
 pub use libp2p::floodsub::{Topic, TopicBuilder, TopicHash};
 pub use libp2p::multiaddr;
 pub use libp2p::Multiaddr;
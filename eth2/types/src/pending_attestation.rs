@@ -1,5 +1,5 @@
 use crate::test_utils::TestRandom;
-use crate::{AttestationData, Bitfield};
+use crate::{AttestationBitfield, AttestationData};
 
 use serde_derive::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
@@ -22,7 +22,7 @@ use tree_hash_derive::{CachedTreeHash, TreeHash};
     TestRandom,
 )]
 pub struct PendingAttestation {
-    pub aggregation_bitfield: Bitfield,
+    pub aggregation_bitfield: AttestationBitfield,
     pub data: AttestationData,
     pub inclusion_delay: u64,
     pub proposer_index: u64,
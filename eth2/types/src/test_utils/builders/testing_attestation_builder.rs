@@ -21,12 +21,16 @@ impl TestingAttestationBuilder {
     ) -> Self {
         let data_builder = TestingAttestationDataBuilder::new(state, shard, slot, spec);
 
-        let mut aggregation_bitfield = Bitfield::new();
-        let mut custody_bitfield = Bitfield::new();
+        let mut aggregation_bitfield = AttestationBitfield::default();
+        let mut custody_bitfield = AttestationBitfield::default();
 
         for (i, _) in committee.iter().enumerate() {
-            custody_bitfield.set(i, false);
-            aggregation_bitfield.set(i, false);
+            custody_bitfield
+                .set(i, false)
+                .expect("Committee should not exceed bitfield max len");
+            aggregation_bitfield
+                .set(i, false)
+                .expect("Committee should not exceed bitfield max len");
         }
 
         let attestation = Attestation {
@@ -68,7 +72,8 @@ impl TestingAttestationBuilder {
 
             self.attestation
                 .aggregation_bitfield
-                .set(committee_index, true);
+                .set(committee_index, true)
+                .expect("Committee should not exceed bitfield max len");
 
             let message = AttestationDataAndCustodyBit {
                 data: self.attestation.data.clone(),
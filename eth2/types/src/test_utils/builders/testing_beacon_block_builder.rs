@@ -68,7 +68,11 @@ impl TestingBeaconBlockBuilder {
     ) {
         let proposer_slashing =
             build_proposer_slashing::<T>(validator_index, secret_key, fork, spec);
-        self.block.body.proposer_slashings.push(proposer_slashing);
+        self.block
+            .body
+            .proposer_slashings
+            .push(proposer_slashing)
+            .expect("Block should not exceed max proposer slashings");
     }
 
     /// Inserts a signed, valid `AttesterSlashing` for each validator index in `validator_indices`.
@@ -81,7 +85,11 @@ impl TestingBeaconBlockBuilder {
     ) {
         let attester_slashing =
             build_double_vote_attester_slashing(validator_indices, secret_keys, fork, spec);
-        self.block.body.attester_slashings.push(attester_slashing);
+        self.block
+            .body
+            .attester_slashings
+            .push(attester_slashing)
+            .expect("Block should not exceed max attester slashings");
     }
 
     /// Fills the block with `num_attestations` attestations.
@@ -176,7 +184,13 @@ impl TestingBeaconBlockBuilder {
             })
             .collect();
 
-        self.block.body.attestations.append(&mut attestations);
+        for attestation in attestations {
+            self.block
+                .body
+                .attestations
+                .push(attestation)
+                .expect("Block should not exceed max attestations");
+        }
 
         Ok(())
     }
@@ -200,7 +214,11 @@ impl TestingBeaconBlockBuilder {
             spec,
         );
 
-        self.block.body.deposits.push(builder.build())
+        self.block
+            .body
+            .deposits
+            .push(builder.build())
+            .expect("Block should not exceed max deposits")
     }
 
     /// Insert a `Valid` exit into the state.
@@ -218,7 +236,11 @@ impl TestingBeaconBlockBuilder {
 
         builder.sign(secret_key, &state.fork, spec);
 
-        self.block.body.voluntary_exits.push(builder.build())
+        self.block
+            .body
+            .voluntary_exits
+            .push(builder.build())
+            .expect("Block should not exceed max voluntary exits")
     }
 
     /// Insert a `Valid` transfer into the state.
@@ -237,7 +259,11 @@ impl TestingBeaconBlockBuilder {
         let mut builder = TestingTransferBuilder::new(from, to, amount, state.slot);
         builder.sign::<T>(keypair, &state.fork, spec);
 
-        self.block.body.transfers.push(builder.build())
+        self.block
+            .body
+            .transfers
+            .push(builder.build())
+            .expect("Block should not exceed max transfers")
     }
 
     /// Signs and returns the block, consuming the builder.
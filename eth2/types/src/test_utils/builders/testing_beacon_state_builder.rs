@@ -100,7 +100,7 @@ impl<T: EthSpec> TestingBeaconStateBuilder<T> {
             "Building {} Validator objects from keypairs...",
             validator_count
         );
-        let validators = keypairs
+        let validators: Vec<Validator> = keypairs
             .par_iter()
             .map(|keypair| {
                 let withdrawal_credentials = Hash256::from_slice(&get_withdrawal_credentials(
@@ -135,8 +135,8 @@ impl<T: EthSpec> TestingBeaconStateBuilder<T> {
         let balances = vec![starting_balance; validator_count];
 
         debug!("Importing {} existing validators...", validator_count);
-        state.validator_registry = validators;
-        state.balances = balances;
+        state.validator_registry = validators.into();
+        state.balances = balances.into();
 
         debug!("BeaconState initialized.");
 
@@ -228,11 +228,15 @@ impl<T: EthSpec> TestingBeaconStateBuilder<T> {
                 builder.add_committee_participation(signers);
                 let attestation = builder.build();
 
-                if attestation.data.target_epoch < state.current_epoch() {
-                    state.previous_epoch_attestations.push(attestation)
+                let target_epoch = attestation.data.target_epoch;
+                let pending_attestations = if target_epoch < state.current_epoch() {
+                    &mut state.previous_epoch_attestations
                 } else {
-                    state.current_epoch_attestations.push(attestation)
-                }
+                    &mut state.current_epoch_attestations
+                };
+                pending_attestations
+                    .push(attestation)
+                    .expect("Pending attestations should not exceed max len");
             }
         }
     }
@@ -8,7 +8,7 @@ macro_rules! ssz_tests {
             use ssz::{ssz_encode, Decode};
 
             let mut rng = XorShiftRng::from_seed([42; 16]);
-            let original = $type::random_for_test(&mut rng);
+            let mut original = $type::random_for_test(&mut rng);
 
             let bytes = ssz_encode(&original);
             let decoded = $type::from_ssz_bytes(&bytes).unwrap();
@@ -22,7 +22,7 @@ macro_rules! ssz_tests {
             use tree_hash::TreeHash;
 
             let mut rng = XorShiftRng::from_seed([42; 16]);
-            let original = $type::random_for_test(&mut rng);
+            let mut original = $type::random_for_test(&mut rng);
 
             let result = original.tree_hash_root();
 
@@ -40,35 +40,37 @@ macro_rules! cached_tree_hash_tests {
         #[test]
         pub fn test_cached_tree_hash() {
             use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+            use cached_tree_hash::{CachedTreeHash, TreeHashCache};
             use tree_hash::TreeHash;
 
             let mut rng = XorShiftRng::from_seed([42; 16]);
 
             // Test the original hash
-            let original = $type::random_for_test(&mut rng);
-            let mut cache = cached_tree_hash::TreeHashCache::new(&original).unwrap();
+            let mut original = $type::random_for_test(&mut rng);
+            let mut cache = TreeHashCache::default();
+            let root = original.recalculate_tree_hash_root(&mut cache).unwrap();
 
             assert_eq!(
-                cache.tree_hash_root().unwrap().to_vec(),
-                original.tree_hash_root(),
+                root.as_bytes(),
+                &original.tree_hash_root()[..],
                 "Original hash failed."
             );
 
             // Test the updated hash
-            let modified = $type::random_for_test(&mut rng);
-            cache.update(&modified).unwrap();
+            let mut modified = $type::random_for_test(&mut rng);
+            let root = modified.recalculate_tree_hash_root(&mut cache).unwrap();
             assert_eq!(
-                cache.tree_hash_root().unwrap().to_vec(),
-                modified.tree_hash_root(),
+                root.as_bytes(),
+                &modified.tree_hash_root()[..],
                 "Modification hash failed"
             );
 
             // Produce a new cache for the modified object and compare it to the updated cache.
-            let mut modified_cache = cached_tree_hash::TreeHashCache::new(&modified).unwrap();
-
-            // Reset the caches.
-            cache.reset_modifications();
-            modified_cache.reset_modifications();
+            let mut modified_cache = TreeHashCache::default();
+            modified
+                .clone()
+                .recalculate_tree_hash_root(&mut modified_cache)
+                .unwrap();
 
             // Ensure the modified cache is the same as a newly created cache. This is a sanity
             // check to make sure there are no artifacts of the original cache remaining after an
@@ -68,6 +68,25 @@ where
     }
 }
 
+impl<T, N: Unsigned> TestRandom for VariableList<T, N>
+where
+    T: TestRandom,
+{
+    fn random_for_test(rng: &mut impl RngCore) -> Self {
+        VariableList::from(<Vec<T>>::random_for_test(rng))
+    }
+}
+
+impl<L, T> TestRandom for TrackedList<L>
+where
+    L: TestRandom + std::ops::Deref<Target = [T]> + Into<Vec<T>> + From<Vec<T>>,
+{
+    fn random_for_test(rng: &mut impl RngCore) -> Self {
+        let vec: Vec<T> = L::random_for_test(rng).into();
+        TrackedList::from(vec)
+    }
+}
+
 macro_rules! impl_test_random_for_u8_array {
     ($len: expr) => {
         impl TestRandom for [u8; $len] {
@@ -8,3 +8,11 @@ impl TestRandom for Bitfield {
         Bitfield::from_bytes(&raw_bytes)
     }
 }
+
+impl<N: Unsigned> TestRandom for BitList<N> {
+    fn random_for_test(rng: &mut impl RngCore) -> Self {
+        let mut raw_bytes = vec![0; std::cmp::min(32, N::to_usize() / 8)];
+        rng.fill_bytes(&mut raw_bytes);
+        BitList::from_bytes(&raw_bytes).expect("Bytes should not exceed N bits")
+    }
+}
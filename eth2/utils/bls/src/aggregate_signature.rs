@@ -1,5 +1,4 @@
 use super::*;
-use cached_tree_hash::cached_tree_hash_ssz_encoding_as_vector;
 use milagro_bls::{
     AggregatePublicKey as RawAggregatePublicKey, AggregateSignature as RawAggregateSignature,
 };
@@ -87,6 +86,11 @@ impl AggregateSignature {
             .verify_multiple(&msg[..], domain, &aggregate_public_keys[..])
     }
 
+    /// Returns the underlying aggregate signature.
+    pub fn as_raw(&self) -> &RawAggregateSignature {
+        &self.aggregate_signature
+    }
+
     /// Return AggregateSiganture as bytes
     pub fn as_bytes(&self) -> Vec<u8> {
         if self.is_empty {
@@ -168,7 +172,6 @@ impl<'de> Deserialize<'de> for AggregateSignature {
 }
 
 tree_hash_ssz_encoding_as_vector!(AggregateSignature);
-cached_tree_hash_ssz_encoding_as_vector!(AggregateSignature, 96);
 
 #[cfg(test)]
 mod tests {
@@ -1,5 +1,4 @@
 use super::{SecretKey, BLS_PUBLIC_KEY_BYTE_SIZE};
-use cached_tree_hash::cached_tree_hash_ssz_encoding_as_vector;
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_hex::{encode as hex_encode, HexVisitor};
@@ -106,7 +105,6 @@ impl<'de> Deserialize<'de> for FakePublicKey {
 }
 
 tree_hash_ssz_encoding_as_vector!(FakePublicKey);
-cached_tree_hash_ssz_encoding_as_vector!(FakePublicKey, 48);
 
 impl PartialEq for FakePublicKey {
     fn eq(&self, other: &FakePublicKey) -> bool {
@@ -130,7 +128,6 @@ impl Hash for FakePublicKey {
 mod tests {
     use super::*;
     use ssz::ssz_encode;
-    use tree_hash::TreeHash;
 
     #[test]
     pub fn test_ssz_round_trip() {
@@ -142,27 +139,4 @@ mod tests {
 
         assert_eq!(original, decoded);
     }
-
-    #[test]
-    pub fn test_cached_tree_hash() {
-        let sk = SecretKey::random();
-        let original = FakePublicKey::from_secret_key(&sk);
-
-        let mut cache = cached_tree_hash::TreeHashCache::new(&original).unwrap();
-
-        assert_eq!(
-            cache.tree_hash_root().unwrap().to_vec(),
-            original.tree_hash_root()
-        );
-
-        let sk = SecretKey::random();
-        let modified = FakePublicKey::from_secret_key(&sk);
-
-        cache.update(&modified).unwrap();
-
-        assert_eq!(
-            cache.tree_hash_root().unwrap().to_vec(),
-            modified.tree_hash_root()
-        );
-    }
 }
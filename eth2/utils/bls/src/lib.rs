@@ -5,9 +5,11 @@ extern crate ssz;
 mod macros;
 mod keypair;
 mod secret_key;
+mod signature_set;
 
 pub use crate::keypair::Keypair;
 pub use crate::secret_key::SecretKey;
+pub use crate::signature_set::{verify_signature_sets, SignatureRef, SignatureSet, SignedMessage};
 pub use milagro_bls::{compress_g2, hash_on_g2};
 
 #[cfg(feature = "fake_crypto")]
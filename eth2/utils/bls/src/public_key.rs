@@ -1,5 +1,4 @@
 use super::{SecretKey, BLS_PUBLIC_KEY_BYTE_SIZE};
-use cached_tree_hash::cached_tree_hash_ssz_encoding_as_vector;
 use milagro_bls::PublicKey as RawPublicKey;
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
@@ -114,7 +113,6 @@ impl<'de> Deserialize<'de> for PublicKey {
 }
 
 tree_hash_ssz_encoding_as_vector!(PublicKey);
-cached_tree_hash_ssz_encoding_as_vector!(PublicKey, 48);
 
 impl PartialEq for PublicKey {
     fn eq(&self, other: &PublicKey) -> bool {
@@ -138,7 +136,6 @@ impl Hash for PublicKey {
 mod tests {
     use super::*;
     use ssz::ssz_encode;
-    use tree_hash::TreeHash;
 
     #[test]
     pub fn test_ssz_round_trip() {
@@ -150,27 +147,4 @@ mod tests {
 
         assert_eq!(original, decoded);
     }
-
-    #[test]
-    pub fn test_cached_tree_hash() {
-        let sk = SecretKey::random();
-        let original = PublicKey::from_secret_key(&sk);
-
-        let mut cache = cached_tree_hash::TreeHashCache::new(&original).unwrap();
-
-        assert_eq!(
-            cache.tree_hash_root().unwrap().to_vec(),
-            original.tree_hash_root()
-        );
-
-        let sk = SecretKey::random();
-        let modified = PublicKey::from_secret_key(&sk);
-
-        cache.update(&modified).unwrap();
-
-        assert_eq!(
-            cache.tree_hash_root().unwrap().to_vec(),
-            modified.tree_hash_root()
-        );
-    }
 }
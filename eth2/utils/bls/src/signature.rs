@@ -1,5 +1,4 @@
 use super::{PublicKey, SecretKey, BLS_SIG_BYTE_SIZE};
-use cached_tree_hash::cached_tree_hash_ssz_encoding_as_vector;
 use hex::encode as hex_encode;
 use milagro_bls::Signature as RawSignature;
 use serde::de::{Deserialize, Deserializer};
@@ -112,7 +111,6 @@ impl Signature {
 impl_ssz!(Signature, BLS_SIG_BYTE_SIZE, "Signature");
 
 tree_hash_ssz_encoding_as_vector!(Signature);
-cached_tree_hash_ssz_encoding_as_vector!(Signature, 96);
 
 impl Serialize for Signature {
     /// Serde serialization is compliant the Ethereum YAML test format.
@@ -142,7 +140,6 @@ mod tests {
     use super::super::Keypair;
     use super::*;
     use ssz::ssz_encode;
-    use tree_hash::TreeHash;
 
     #[test]
     pub fn test_ssz_round_trip() {
@@ -156,28 +153,6 @@ mod tests {
         assert_eq!(original, decoded);
     }
 
-    #[test]
-    pub fn test_cached_tree_hash() {
-        let keypair = Keypair::random();
-        let original = Signature::new(&[42, 42], 0, &keypair.sk);
-
-        let mut cache = cached_tree_hash::TreeHashCache::new(&original).unwrap();
-
-        assert_eq!(
-            cache.tree_hash_root().unwrap().to_vec(),
-            original.tree_hash_root()
-        );
-
-        let modified = Signature::new(&[99, 99], 0, &keypair.sk);
-
-        cache.update(&modified).unwrap();
-
-        assert_eq!(
-            cache.tree_hash_root().unwrap().to_vec(),
-            modified.tree_hash_root()
-        );
-    }
-
     #[test]
     pub fn test_empty_signature() {
         let sig = Signature::empty_signature();
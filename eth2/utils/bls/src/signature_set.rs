@@ -0,0 +1,246 @@
+use crate::{AggregateSignature, PublicKey, Signature};
+
+#[cfg(not(feature = "fake_crypto"))]
+use milagro_bls::{
+    AggregatePublicKey as RawAggregatePublicKey, AggregateSignature as RawAggregateSignature,
+    G1Point, G2Point,
+};
+
+/// A signature, which may or may not be an aggregate.
+#[derive(Debug, Clone, Copy)]
+pub enum SignatureRef<'a> {
+    Single(&'a Signature),
+    Aggregate(&'a AggregateSignature),
+}
+
+impl<'a> From<&'a Signature> for SignatureRef<'a> {
+    fn from(signature: &'a Signature) -> Self {
+        SignatureRef::Single(signature)
+    }
+}
+
+impl<'a> From<&'a AggregateSignature> for SignatureRef<'a> {
+    fn from(signature: &'a AggregateSignature) -> Self {
+        SignatureRef::Aggregate(signature)
+    }
+}
+
+/// A message and the public keys of every validator that signed it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "fake_crypto", allow(dead_code))]
+pub struct SignedMessage<'a> {
+    signing_keys: Vec<&'a PublicKey>,
+    message: Vec<u8>,
+}
+
+impl<'a> SignedMessage<'a> {
+    pub fn new(signing_keys: Vec<&'a PublicKey>, message: Vec<u8>) -> Self {
+        Self {
+            signing_keys,
+            message,
+        }
+    }
+}
+
+/// A signature, the messages it signs and the domain it was signed with.
+///
+/// Many `SignatureSet`s can be verified at once (and much faster than individually) with
+/// `verify_signature_sets`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "fake_crypto", allow(dead_code))]
+pub struct SignatureSet<'a> {
+    signature: SignatureRef<'a>,
+    signed_messages: Vec<SignedMessage<'a>>,
+    domain: u64,
+}
+
+impl<'a> SignatureSet<'a> {
+    /// A set where `signature` signs `message` with a single key.
+    pub fn single<S>(
+        signature: S,
+        signing_key: &'a PublicKey,
+        message: Vec<u8>,
+        domain: u64,
+    ) -> Self
+    where
+        S: Into<SignatureRef<'a>>,
+    {
+        Self {
+            signature: signature.into(),
+            signed_messages: vec![SignedMessage::new(vec![signing_key], message)],
+            domain,
+        }
+    }
+
+    /// A set where `signature` is an aggregate over each of `signed_messages`.
+    pub fn new<S>(signature: S, signed_messages: Vec<SignedMessage<'a>>, domain: u64) -> Self
+    where
+        S: Into<SignatureRef<'a>>,
+    {
+        Self {
+            signature: signature.into(),
+            signed_messages,
+            domain,
+        }
+    }
+
+    /// Returns `true` if the signature is empty or there are no messages, in which case the set
+    /// can never be valid.
+    #[cfg(not(feature = "fake_crypto"))]
+    fn is_trivially_invalid(&self) -> bool {
+        let signature_is_empty = match self.signature {
+            SignatureRef::Single(signature) => signature.is_empty(),
+            SignatureRef::Aggregate(signature) => signature.is_empty(),
+        };
+
+        signature_is_empty
+            || self.signed_messages.is_empty()
+            || self
+                .signed_messages
+                .iter()
+                .any(|signed_message| signed_message.signing_keys.is_empty())
+    }
+
+    /// Converts `self` into the form accepted by `milagro_bls`, aggregating the keys that signed
+    /// each message.
+    #[cfg(not(feature = "fake_crypto"))]
+    fn to_raw(&self) -> (G2Point, Vec<G1Point>, Vec<Vec<u8>>, u64) {
+        let signature = match self.signature {
+            SignatureRef::Single(signature) => signature.as_raw().point.clone(),
+            SignatureRef::Aggregate(signature) => signature.as_raw().point.clone(),
+        };
+
+        let (pubkeys, messages) = self
+            .signed_messages
+            .iter()
+            .map(|signed_message| {
+                let mut aggregate = RawAggregatePublicKey::new();
+                for key in &signed_message.signing_keys {
+                    aggregate.add(key.as_raw());
+                }
+                (aggregate.point, signed_message.message.clone())
+            })
+            .unzip();
+
+        (signature, pubkeys, messages, self.domain)
+    }
+}
+
+/// Verify every set in `signature_sets` with a single randomized batch verification.
+///
+/// Returns `true` only if all sets are valid. An invalid set cannot be identified from the
+/// result; verify the sets individually if that is required.
+#[cfg(not(feature = "fake_crypto"))]
+pub fn verify_signature_sets<'a, 'b, I>(signature_sets: I) -> bool
+where
+    I: IntoIterator<Item = &'b SignatureSet<'a>>,
+    'a: 'b,
+{
+    let mut raw_sets = vec![];
+    for set in signature_sets {
+        if set.is_trivially_invalid() {
+            return false;
+        }
+        raw_sets.push(set.to_raw());
+    }
+
+    RawAggregateSignature::verify_multiple_signatures(&mut rand::thread_rng(), raw_sets.into_iter())
+}
+
+/// _Always_ returns `true`.
+#[cfg(feature = "fake_crypto")]
+pub fn verify_signature_sets<'a, 'b, I>(_signature_sets: I) -> bool
+where
+    I: IntoIterator<Item = &'b SignatureSet<'a>>,
+    'a: 'b,
+{
+    true
+}
+
+#[cfg(all(test, not(feature = "fake_crypto")))]
+mod tests {
+    use super::*;
+    use crate::{AggregatePublicKey, Keypair};
+
+    #[test]
+    fn verify_valid_sets() {
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::random()).collect();
+        let domain = 42;
+
+        let single_message = vec![1; 32];
+        let single_signature = Signature::new(&single_message, domain, &keypairs[0].sk);
+
+        let aggregate_message = vec![2; 32];
+        let mut aggregate_signature = AggregateSignature::new();
+        for keypair in &keypairs[1..] {
+            aggregate_signature.add(&Signature::new(&aggregate_message, domain, &keypair.sk));
+        }
+
+        let sets = vec![
+            SignatureSet::single(
+                &single_signature,
+                &keypairs[0].pk,
+                single_message.clone(),
+                domain,
+            ),
+            SignatureSet::new(
+                &aggregate_signature,
+                vec![SignedMessage::new(
+                    keypairs[1..].iter().map(|keypair| &keypair.pk).collect(),
+                    aggregate_message.clone(),
+                )],
+                domain,
+            ),
+        ];
+
+        assert!(verify_signature_sets(&sets));
+
+        // The batch agrees with individual verification.
+        let mut aggregate_pubkey = AggregatePublicKey::new();
+        for keypair in &keypairs[1..] {
+            aggregate_pubkey.add(&keypair.pk);
+        }
+        assert!(single_signature.verify(&single_message, domain, &keypairs[0].pk));
+        assert!(aggregate_signature.verify(&aggregate_message, domain, &aggregate_pubkey));
+    }
+
+    #[test]
+    fn reject_any_invalid_set() {
+        let keypairs: Vec<Keypair> = (0..3).map(|_| Keypair::random()).collect();
+        let domain = 42;
+        let message = vec![1; 32];
+
+        let signatures: Vec<Signature> = keypairs
+            .iter()
+            .map(|keypair| Signature::new(&message, domain, &keypair.sk))
+            .collect();
+
+        let mut sets: Vec<SignatureSet> = signatures
+            .iter()
+            .zip(keypairs.iter())
+            .map(|(signature, keypair)| {
+                SignatureSet::single(signature, &keypair.pk, message.clone(), domain)
+            })
+            .collect();
+
+        assert!(verify_signature_sets(&sets));
+
+        // Signed by a different key.
+        sets[1] = SignatureSet::single(&signatures[1], &keypairs[2].pk, message.clone(), domain);
+        assert!(!verify_signature_sets(&sets));
+    }
+
+    #[test]
+    fn reject_empty_signature() {
+        let keypair = Keypair::random();
+        let signature = Signature::empty_signature();
+        let sets = vec![SignatureSet::single(
+            &signature,
+            &keypair.pk,
+            vec![1; 32],
+            42,
+        )];
+
+        assert!(!verify_signature_sets(&sets));
+    }
+}
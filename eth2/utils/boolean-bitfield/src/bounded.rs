@@ -0,0 +1,440 @@
+use crate::{BooleanBitfield, Error};
+use serde::de::{Deserialize, Deserializer, Error as SerdeError};
+use serde::ser::{Serialize, Serializer};
+use ssz::{Decode, Encode};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use typenum::Unsigned;
+
+/// Returns the number of bytes required to store `bits` bits.
+fn bytes_for_bit_len(bits: usize) -> usize {
+    (bits + 7) / 8
+}
+
+/// Returns an error if `bitfield` has a set bit at or beyond `max_len`, or if it would require
+/// more than `bytes_for_bit_len(max_len)` bytes to encode.
+fn verify_max_len(bitfield: &BooleanBitfield, max_len: usize) -> Result<(), Error> {
+    if bitfield.num_bytes() > bytes_for_bit_len(max_len) {
+        return Err(Error::ExceedsMaxLen(bitfield.len(), max_len));
+    }
+
+    match (max_len..bitfield.len())
+        .rev()
+        .find(|&i| bitfield.get(i) == Ok(true))
+    {
+        Some(i) => Err(Error::ExceedsMaxLen(i + 1, max_len)),
+        None => Ok(()),
+    }
+}
+
+/// A `BooleanBitfield` which may not have any bit set at or beyond `N`.
+///
+/// Attempting to set a bit at or beyond `N` (by calling `set` or decoding) results in an error,
+/// rather than an allocation.
+///
+/// Encoded and tree-hashed identically to a `BooleanBitfield`, with the length mixed-in to the
+/// root.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BitList<N> {
+    bitfield: BooleanBitfield,
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Unsigned> BitList<N> {
+    /// Returns a new list with `initial_len` unset bits, or an error if `initial_len > N`.
+    pub fn with_capacity(initial_len: usize) -> Result<Self, Error> {
+        if initial_len <= Self::max_len() {
+            Ok(Self {
+                bitfield: BooleanBitfield::with_capacity(initial_len),
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(Error::ExceedsMaxLen(initial_len, Self::max_len()))
+        }
+    }
+
+    /// Returns a new list from the given `bitfield`, or an error if it exceeds `N` bits.
+    pub fn from_bitfield(bitfield: BooleanBitfield) -> Result<Self, Error> {
+        verify_max_len(&bitfield, Self::max_len())?;
+
+        Ok(Self {
+            bitfield,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Create a new list using the supplied `bytes` as input, returning an error if they
+    /// represent more than `N` bits.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_bitfield(BooleanBitfield::from_bytes(bytes))
+    }
+
+    /// The maximum number of bits in the list, `N`.
+    pub fn max_len() -> usize {
+        N::to_usize()
+    }
+
+    /// Set the value of a bit, expanding the list if required.
+    ///
+    /// Returns the previous value if there was one, or an error if `i >= N`.
+    pub fn set(&mut self, i: usize, value: bool) -> Result<Option<bool>, Error> {
+        if i < Self::max_len() {
+            Ok(self.bitfield.set(i, value))
+        } else {
+            Err(Error::ExceedsMaxLen(i + 1, Self::max_len()))
+        }
+    }
+
+    /// Like `BooleanBitfield::union_inplace`.
+    pub fn union_inplace(&mut self, other: &Self) {
+        self.bitfield.union_inplace(&other.bitfield)
+    }
+
+    /// Like `BooleanBitfield::intersection_inplace`.
+    pub fn intersection_inplace(&mut self, other: &Self) {
+        self.bitfield.intersection_inplace(&other.bitfield)
+    }
+
+    /// Like `BooleanBitfield::difference_inplace`.
+    pub fn difference_inplace(&mut self, other: &Self) {
+        self.bitfield.difference_inplace(&other.bitfield)
+    }
+
+    /// Returns the underlying `BooleanBitfield`.
+    pub fn into_bitfield(self) -> BooleanBitfield {
+        self.bitfield
+    }
+}
+
+impl<N: Unsigned> Default for BitList<N> {
+    fn default() -> Self {
+        Self {
+            bitfield: BooleanBitfield::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<N: Unsigned> Deref for BitList<N> {
+    type Target = BooleanBitfield;
+
+    fn deref(&self) -> &BooleanBitfield {
+        &self.bitfield
+    }
+}
+
+impl<N: Unsigned> Encode for BitList<N> {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        self.bitfield.ssz_append(buf)
+    }
+}
+
+impl<N: Unsigned> Decode for BitList<N> {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        Self::from_bytes(bytes)
+            .map_err(|e| ssz::DecodeError::BytesInvalid(format!("Invalid BitList: {:?}", e)))
+    }
+}
+
+impl<N: Unsigned> Serialize for BitList<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.bitfield.serialize(serializer)
+    }
+}
+
+impl<'de, N: Unsigned> Deserialize<'de> for BitList<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bitfield = BooleanBitfield::deserialize(deserializer)?;
+        Self::from_bitfield(bitfield).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+impl<N: Unsigned> tree_hash::TreeHash for BitList<N> {
+    fn tree_hash_type() -> tree_hash::TreeHashType {
+        tree_hash::TreeHashType::List
+    }
+
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_root(&self) -> Vec<u8> {
+        self.bitfield.tree_hash_root()
+    }
+}
+
+/// A `BooleanBitfield` of exactly `N` bits.
+///
+/// Encoded as exactly `(N + 7) / 8` bytes and tree-hashed as a fixed-length vector of bytes
+/// (i.e., without a length mix-in).
+#[derive(Debug, PartialEq, Clone)]
+pub struct BitVector<N> {
+    bitfield: BooleanBitfield,
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Unsigned> BitVector<N> {
+    /// Returns a new vector with all `N` bits unset.
+    pub fn new() -> Self {
+        Self {
+            bitfield: BooleanBitfield::with_capacity(N::to_usize()),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Create a new vector using the supplied `bytes` as input, returning an error if they
+    /// represent more than `N` bits.
+    ///
+    /// If `bytes` represents fewer than `N` bits, the remaining bits are unset.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut bitfield = BooleanBitfield::from_bytes(bytes);
+        verify_max_len(&bitfield, N::to_usize())?;
+
+        if bitfield.len() < N::to_usize() {
+            bitfield.set(N::to_usize() - 1, false);
+        }
+
+        Ok(Self {
+            bitfield,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The number of bits in the vector, `N`.
+    pub fn len(&self) -> usize {
+        N::to_usize()
+    }
+
+    /// Returns true if `N == 0`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Set the value of a bit.
+    ///
+    /// Returns the previous value, or an error if `i >= N`.
+    pub fn set(&mut self, i: usize, value: bool) -> Result<bool, Error> {
+        if i < self.len() {
+            Ok(self.bitfield.set(i, value).unwrap_or(false))
+        } else {
+            Err(Error::OutOfBounds(i, self.len()))
+        }
+    }
+}
+
+impl<N: Unsigned> Default for BitVector<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Unsigned> Deref for BitVector<N> {
+    type Target = BooleanBitfield;
+
+    fn deref(&self) -> &BooleanBitfield {
+        &self.bitfield
+    }
+}
+
+impl<N: Unsigned> Encode for BitVector<N> {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        bytes_for_bit_len(N::to_usize())
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        self.bitfield.ssz_append(buf)
+    }
+}
+
+impl<N: Unsigned> Decode for BitVector<N> {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        bytes_for_bit_len(N::to_usize())
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        let expected = <Self as Decode>::ssz_fixed_len();
+
+        if bytes.len() != expected {
+            return Err(ssz::DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected,
+            });
+        }
+
+        Self::from_bytes(bytes)
+            .map_err(|e| ssz::DecodeError::BytesInvalid(format!("Invalid BitVector: {:?}", e)))
+    }
+}
+
+impl<N: Unsigned> Serialize for BitVector<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.bitfield.serialize(serializer)
+    }
+}
+
+impl<'de, N: Unsigned> Deserialize<'de> for BitVector<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bitfield = BooleanBitfield::deserialize(deserializer)?;
+        Self::from_bytes(&bitfield.to_bytes()).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+impl<N: Unsigned> tree_hash::TreeHash for BitVector<N> {
+    fn tree_hash_type() -> tree_hash::TreeHashType {
+        tree_hash::TreeHashType::Vector
+    }
+
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        unreachable!("Vector should never be packed.")
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        unreachable!("Vector should never be packed.")
+    }
+
+    fn tree_hash_root(&self) -> Vec<u8> {
+        tree_hash::impls::vec_tree_hash_root(&self.as_ssz_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_hash::TreeHash;
+    use typenum::*;
+
+    #[test]
+    fn bit_list_set() {
+        let mut list: BitList<U10> = BitList::default();
+
+        assert_eq!(list.set(9, true), Ok(None));
+        assert_eq!(list.set(9, false), Ok(Some(true)));
+        assert_eq!(list.set(10, true), Err(Error::ExceedsMaxLen(11, 10)));
+        assert_eq!(list.get(10), Err(Error::OutOfBounds(10, 10)));
+    }
+
+    #[test]
+    fn bit_list_with_capacity() {
+        assert!(BitList::<U10>::with_capacity(10).is_ok());
+        assert_eq!(
+            BitList::<U10>::with_capacity(11),
+            Err(Error::ExceedsMaxLen(11, 10))
+        );
+    }
+
+    #[test]
+    fn bit_list_from_bytes() {
+        assert!(BitList::<U10>::from_bytes(&[0b0000_0011, 0xff]).is_ok());
+        assert_eq!(
+            BitList::<U10>::from_bytes(&[0b0000_0100, 0xff]),
+            Err(Error::ExceedsMaxLen(11, 10))
+        );
+        assert_eq!(
+            BitList::<U10>::from_bytes(&[0, 0, 0]),
+            Err(Error::ExceedsMaxLen(24, 10))
+        );
+    }
+
+    #[test]
+    fn bit_list_ssz() {
+        let mut list: BitList<U10> = BitList::default();
+        list.set(1, true).unwrap();
+        list.set(9, true).unwrap();
+
+        let bytes = list.as_ssz_bytes();
+        assert_eq!(bytes, list.bitfield.as_ssz_bytes());
+        assert_eq!(BitList::from_ssz_bytes(&bytes), Ok(list));
+
+        assert!(BitList::<U10>::from_ssz_bytes(&[0b0000_0100, 0]).is_err());
+    }
+
+    #[test]
+    fn bit_list_tree_hash() {
+        let list: BitList<U16> = BitList::from_bytes(&[1, 2]).unwrap();
+
+        assert_eq!(
+            list.tree_hash_root(),
+            BooleanBitfield::from_bytes(&[1, 2]).tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn bit_vector_set() {
+        let mut vector: BitVector<U10> = BitVector::new();
+
+        assert_eq!(vector.len(), 10);
+        assert_eq!(vector.set(9, true), Ok(false));
+        assert_eq!(vector.set(9, true), Ok(true));
+        assert_eq!(vector.set(10, true), Err(Error::OutOfBounds(10, 10)));
+    }
+
+    #[test]
+    fn bit_vector_ssz() {
+        let mut vector: BitVector<U10> = BitVector::new();
+        vector.set(9, true).unwrap();
+
+        let bytes = vector.as_ssz_bytes();
+        assert_eq!(bytes, vec![0b0000_0010, 0]);
+        assert_eq!(BitVector::from_ssz_bytes(&bytes), Ok(vector));
+
+        assert_eq!(
+            BitVector::<U10>::from_ssz_bytes(&[0]),
+            Err(ssz::DecodeError::InvalidByteLength {
+                len: 1,
+                expected: 2
+            })
+        );
+        assert!(BitVector::<U10>::from_ssz_bytes(&[0b0000_0100, 0]).is_err());
+    }
+
+    #[test]
+    fn bit_vector_short_bytes_are_padded() {
+        let vector: BitVector<U16> = BitVector::from_bytes(&[1]).unwrap();
+
+        assert_eq!(vector.as_ssz_bytes(), vec![0, 1]);
+        assert_eq!(vector.get(0), Ok(true));
+        assert_eq!(vector.get(15), Ok(false));
+    }
+
+    #[test]
+    fn bit_vector_tree_hash() {
+        let vector: BitVector<U16> = BitVector::from_bytes(&[1, 2]).unwrap();
+
+        assert_eq!(
+            vector.tree_hash_root(),
+            tree_hash::impls::vec_tree_hash_root(&[1_u8, 2][..])
+        );
+    }
+}
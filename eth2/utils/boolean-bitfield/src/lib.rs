@@ -1,9 +1,12 @@
 extern crate bit_vec;
 extern crate ssz;
 
+mod bounded;
+
+pub use bounded::{BitList, BitVector};
+
 use bit_reverse::LookupReverse;
 use bit_vec::BitVec;
-use cached_tree_hash::cached_tree_hash_bytes_as_list;
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_hex::{encode, PrefixedHexVisitor};
@@ -21,6 +24,8 @@ pub struct BooleanBitfield(BitVec);
 pub enum Error {
     /// OutOfBounds refers to indexing into a bitfield where no bits exist; returns the illegal index and the current size of the bitfield, respectively
     OutOfBounds(usize, usize),
+    /// ExceedsMaxLen refers to a bitfield which is longer than its maximum permitted length; returns the length and the maximum length, respectively
+    ExceedsMaxLen(usize, usize),
 }
 
 impl BooleanBitfield {
@@ -79,10 +84,10 @@ impl BooleanBitfield {
     /// If the index is out of bounds, we expand the size of the underlying set to include the new index.
     /// Returns the previous value if there was one.
     pub fn set(&mut self, i: usize, value: bool) -> Option<bool> {
-        let previous = match self.get(i) {
-            Ok(previous) => Some(previous),
-            Err(Error::OutOfBounds(_, len)) => {
-                let new_len = i - len + 1;
+        let previous = match self.0.get(i) {
+            Some(previous) => Some(previous),
+            None => {
+                let new_len = i - self.0.len() + 1;
                 self.0.grow(new_len, false);
                 None
             }
@@ -265,35 +270,11 @@ impl tree_hash::TreeHash for BooleanBitfield {
     }
 }
 
-cached_tree_hash_bytes_as_list!(BooleanBitfield);
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_yaml;
     use ssz::ssz_encode;
-    use tree_hash::TreeHash;
-
-    #[test]
-    pub fn test_cached_tree_hash() {
-        let original = BooleanBitfield::from_bytes(&vec![18; 12][..]);
-
-        let mut cache = cached_tree_hash::TreeHashCache::new(&original).unwrap();
-
-        assert_eq!(
-            cache.tree_hash_root().unwrap().to_vec(),
-            original.tree_hash_root()
-        );
-
-        let modified = BooleanBitfield::from_bytes(&vec![2; 1][..]);
-
-        cache.update(&modified).unwrap();
-
-        assert_eq!(
-            cache.tree_hash_root().unwrap().to_vec(),
-            modified.tree_hash_root()
-        );
-    }
 
     #[test]
     fn test_new_bitfield() {
@@ -1,72 +1,99 @@
 #[macro_use]
 extern crate criterion;
 
-use cached_tree_hash::TreeHashCache;
+use cached_tree_hash::{CachedTreeHash, Hash256, ListTreeHashCache, TrackedList};
 use criterion::black_box;
 use criterion::{Benchmark, Criterion};
-use ethereum_types::H256 as Hash256;
-use hashing::hash;
+use fixed_len_vec::{typenum::U1099511627776, VariableList};
 use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
 
-fn criterion_benchmark(c: &mut Criterion) {
-    let n = 1024;
-
-    let source_vec: Vec<Hash256> = (0..n).map(|_| Hash256::random()).collect();
+type List<T> = TrackedList<VariableList<T, U1099511627776>>;
 
-    let mut source_modified_vec = source_vec.clone();
-    source_modified_vec[n - 1] = Hash256::random();
+#[derive(Clone, PartialEq, TreeHash)]
+pub struct Item {
+    pub a: u64,
+    pub b: u64,
+    pub c: Hash256,
+    pub d: bool,
+}
 
-    let modified_vec = source_modified_vec.clone();
+fn bench_list<T>(c: &mut Criterion, desc: &str, mut list: List<T>, modify: fn(&mut List<T>))
+where
+    T: Clone + 'static,
+    List<T>: CachedTreeHash<ListTreeHashCache>,
+{
+    let list_clone = list.clone();
     c.bench(
-        &format!("vec_of_{}_hashes", n),
+        desc,
         Benchmark::new("standard", move |b| {
-            b.iter_with_setup(
-                || modified_vec.clone(),
-                |modified_vec| black_box(modified_vec.tree_hash_root()),
-            )
+            b.iter(|| black_box(list_clone.tree_hash_root()))
         })
-        .sample_size(100),
+        .sample_size(10),
     );
 
-    let modified_vec = source_modified_vec.clone();
+    let list_clone = list.clone();
     c.bench(
-        &format!("vec_of_{}_hashes", n),
+        desc,
         Benchmark::new("build_cache", move |b| {
             b.iter_with_setup(
-                || modified_vec.clone(),
-                |vec| black_box(TreeHashCache::new(&vec, 0)),
+                || (list_clone.clone(), ListTreeHashCache::default()),
+                |(mut list, mut cache)| {
+                    black_box(list.recalculate_tree_hash_root(&mut cache).unwrap())
+                },
             )
         })
-        .sample_size(100),
+        .sample_size(10),
     );
 
-    let vec = source_vec.clone();
-    let modified_vec = source_modified_vec.clone();
+    let mut cache = ListTreeHashCache::default();
+    list.recalculate_tree_hash_root(&mut cache).unwrap();
     c.bench(
-        &format!("vec_of_{}_hashes", n),
-        Benchmark::new("cache_update", move |b| {
+        desc,
+        Benchmark::new("single_change", move |b| {
             b.iter_with_setup(
                 || {
-                    let cache = TreeHashCache::new(&vec, 0).unwrap();
-                    (cache, modified_vec.clone())
+                    let mut list = list.clone();
+                    modify(&mut list);
+                    (list, cache.clone())
+                },
+                |(mut list, mut cache)| {
+                    black_box(list.recalculate_tree_hash_root(&mut cache).unwrap())
                 },
-                |(mut cache, modified_vec)| black_box(cache.update(&modified_vec)),
             )
         })
-        .sample_size(100),
+        .sample_size(10),
     );
+}
 
-    c.bench(
-        &format!("{}_hashes", n),
-        Benchmark::new("hash_64_bytes", move |b| {
-            b.iter(|| {
-                for _ in 0..n {
-                    let _digest = hash(&[42; 64]);
-                }
-            })
+fn criterion_benchmark(c: &mut Criterion) {
+    let n = 1 << 16;
+
+    let hashes: List<Hash256> = (0..n)
+        .map(|i| Hash256::from_slice(&[i as u8; 32]))
+        .collect::<Vec<_>>()
+        .into();
+    bench_list(c, &format!("list_of_{}_hashes", n), hashes, |list| {
+        list[0] = Hash256::zero()
+    });
+
+    let balances: List<u64> = (0..n as u64).collect::<Vec<_>>().into();
+    bench_list(c, &format!("list_of_{}_u64", n), balances, |list| {
+        list[0] += 1
+    });
+
+    let items: List<Item> = (0..n as u64)
+        .map(|i| Item {
+            a: i,
+            b: i,
+            c: Hash256::zero(),
+            d: false,
         })
-        .sample_size(100),
-    );
+        .collect::<Vec<_>>()
+        .into();
+    bench_list(c, &format!("list_of_{}_containers", n), items, |list| {
+        list[0].b += 1
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
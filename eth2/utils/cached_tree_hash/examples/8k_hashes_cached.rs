@@ -1,21 +1,23 @@
-use cached_tree_hash::TreeHashCache;
+use cached_tree_hash::{CachedTreeHash, ListTreeHashCache, TrackedList};
 use ethereum_types::H256 as Hash256;
+use fixed_len_vec::{typenum::U8192, FixedLenVec};
 
-fn run(vec: &Vec<Hash256>, modified_vec: &Vec<Hash256>) {
-    let mut cache = TreeHashCache::new(vec).unwrap();
+fn run(vec: &TrackedList<FixedLenVec<Hash256, U8192>>) {
+    let mut vec = vec.clone();
+    let mut cache = ListTreeHashCache::default();
+    vec.recalculate_tree_hash_root(&mut cache).unwrap();
 
-    cache.update(modified_vec).unwrap();
+    vec[8191] = Hash256::random();
+    vec.recalculate_tree_hash_root(&mut cache).unwrap();
 }
 
 fn main() {
-    let n = 2048;
+    let n = 8192;
 
     let vec: Vec<Hash256> = (0..n).map(|_| Hash256::random()).collect();
+    let vec = vec.into();
 
-    let mut modified_vec = vec.clone();
-    modified_vec[n - 1] = Hash256::random();
-
-    for _ in 0..10_000 {
-        run(&vec, &modified_vec);
+    for _ in 0..100 {
+        run(&vec);
     }
 }
@@ -1,19 +1,5 @@
-use tree_hash::TreeHashType;
-
 #[derive(Debug, PartialEq, Clone)]
 pub enum Error {
-    ShouldNotProduceBTreeOverlay,
-    NoFirstNode,
-    NoBytesForRoot,
-    UnableToObtainSlices,
-    UnableToGrowMerkleTree,
-    UnableToShrinkMerkleTree,
-    TreeCannotHaveZeroNodes,
+    /// A root was requested from a cache which has never been used to calculate a root.
     CacheNotInitialized,
-    ShouldNeverBePacked(TreeHashType),
-    BytesAreNotEvenChunks(usize),
-    NoModifiedFieldForChunk(usize),
-    NoBytesForChunk(usize),
-    NoSchemaForIndex(usize),
-    NotLeafNode(usize),
 }
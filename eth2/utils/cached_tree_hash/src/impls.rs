@@ -1,117 +1,41 @@
 use super::*;
-use crate::merkleize::merkleize;
-use ethereum_types::H256;
-
-pub mod vec;
-
-macro_rules! impl_for_single_leaf_int {
-    ($type: ident) => {
-        impl CachedTreeHash for $type {
-            fn new_tree_hash_cache(&self, _depth: usize) -> Result<TreeHashCache, Error> {
-                Ok(TreeHashCache::from_bytes(
-                    merkleize(self.to_le_bytes().to_vec()),
-                    false,
-                    None,
-                )?)
-            }
-
-            fn tree_hash_cache_schema(&self, depth: usize) -> BTreeSchema {
-                BTreeSchema::from_lengths(depth, vec![1])
-            }
-
-            fn update_tree_hash_cache(&self, cache: &mut TreeHashCache) -> Result<(), Error> {
-                let leaf = merkleize(self.to_le_bytes().to_vec());
-                cache.maybe_update_chunk(cache.chunk_index, &leaf)?;
-
-                cache.chunk_index += 1;
-
-                Ok(())
-            }
-        }
-    };
-}
-
-impl_for_single_leaf_int!(u8);
-impl_for_single_leaf_int!(u16);
-impl_for_single_leaf_int!(u32);
-impl_for_single_leaf_int!(u64);
-impl_for_single_leaf_int!(usize);
-
-impl CachedTreeHash for bool {
-    fn new_tree_hash_cache(&self, _depth: usize) -> Result<TreeHashCache, Error> {
-        Ok(TreeHashCache::from_bytes(
-            merkleize((*self as u8).to_le_bytes().to_vec()),
-            false,
-            None,
-        )?)
-    }
-
-    fn tree_hash_cache_schema(&self, depth: usize) -> BTreeSchema {
-        BTreeSchema::from_lengths(depth, vec![1])
-    }
-
-    fn update_tree_hash_cache(&self, cache: &mut TreeHashCache) -> Result<(), Error> {
-        let leaf = merkleize((*self as u8).to_le_bytes().to_vec());
-        cache.maybe_update_chunk(cache.chunk_index, &leaf)?;
-
-        cache.chunk_index += 1;
-
-        Ok(())
+use fixed_len_vec::{typenum::Unsigned, FixedLenVec, VariableList};
+use hashing::hash;
+use int_to_bytes::int_to_bytes32;
+use tree_hash::HASHSIZE;
+
+impl<T, N: Unsigned> CachedTreeHash<ListTreeHashCache> for TrackedList<FixedLenVec<T, N>>
+where
+    T: TreeHash,
+{
+    fn recalculate_tree_hash_root(
+        &mut self,
+        cache: &mut ListTreeHashCache,
+    ) -> Result<Hash256, Error> {
+        Ok(self.recalculate_merkle_root(cache))
     }
 }
 
-macro_rules! impl_for_u8_array {
-    ($len: expr) => {
-        impl CachedTreeHash for [u8; $len] {
-            fn new_tree_hash_cache(&self, _depth: usize) -> Result<TreeHashCache, Error> {
-                Ok(TreeHashCache::from_bytes(
-                    merkleize(self.to_vec()),
-                    false,
-                    None,
-                )?)
-            }
-
-            fn tree_hash_cache_schema(&self, depth: usize) -> BTreeSchema {
-                BTreeSchema::from_lengths(depth, vec![1])
-            }
-
-            fn update_tree_hash_cache(&self, cache: &mut TreeHashCache) -> Result<(), Error> {
-                let leaf = merkleize(self.to_vec());
-                cache.maybe_update_chunk(cache.chunk_index, &leaf)?;
-
-                cache.chunk_index += 1;
-
-                Ok(())
-            }
-        }
-    };
-}
-
-impl_for_u8_array!(4);
-impl_for_u8_array!(32);
-
-impl CachedTreeHash for H256 {
-    fn new_tree_hash_cache(&self, _depth: usize) -> Result<TreeHashCache, Error> {
-        Ok(TreeHashCache::from_bytes(
-            self.as_bytes().to_vec(),
-            false,
-            None,
-        )?)
+impl<T, N: Unsigned> CachedTreeHash<ListTreeHashCache> for TrackedList<VariableList<T, N>>
+where
+    T: TreeHash,
+{
+    fn recalculate_tree_hash_root(
+        &mut self,
+        cache: &mut ListTreeHashCache,
+    ) -> Result<Hash256, Error> {
+        let root = self.recalculate_merkle_root(cache);
+
+        Ok(mix_in_length(&root, self.len()))
     }
+}
 
-    fn num_tree_hash_cache_chunks(&self) -> usize {
-        1
-    }
-
-    fn tree_hash_cache_schema(&self, depth: usize) -> BTreeSchema {
-        BTreeSchema::from_lengths(depth, vec![1])
-    }
-
-    fn update_tree_hash_cache(&self, cache: &mut TreeHashCache) -> Result<(), Error> {
-        cache.maybe_update_chunk(cache.chunk_index, self.as_bytes())?;
-
-        cache.chunk_index += 1;
+/// Returns the root of a list with the given `root` and `len`, as per the `List` case of
+/// `tree_hash_root`.
+fn mix_in_length(root: &Hash256, len: usize) -> Hash256 {
+    let mut root_and_len = Vec::with_capacity(HASHSIZE * 2);
+    root_and_len.extend_from_slice(root.as_bytes());
+    root_and_len.append(&mut int_to_bytes32(len as u64));
 
-        Ok(())
-    }
+    Hash256::from_slice(&hash(&root_and_len))
 }
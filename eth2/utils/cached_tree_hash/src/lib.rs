@@ -1,150 +1,73 @@
 //! Performs cached merkle-hashing adhering to the Ethereum 2.0 specification defined
-//! [here](https://github.com/ethereum/eth2.0-specs/blob/v0.5.1/specs/simple-serialize.md#merkleization).
+//! [here](https://github.com/ethereum/eth2.0-specs/blob/v0.6.3/specs/simple-serialize.md#merkleization).
 //!
 //! Caching allows for reduced hashing when some object has only been partially modified, which
-//! consumes less CPU-time at the cost of additional storage. For example,
-//! determining the root of a list of 1024 items with a single modification has been observed to
-//! run in 1/25th of the time of a full merkle hash.
+//! consumes less CPU-time at the cost of additional storage. A `TreeHashCache` stores every node
+//! of a merkle tree and, when recalculating the root, compares each leaf to its previous value so
+//! that only the nodes above modified leaves are re-hashed. For example, changing a single item in
+//! a list of `n` items requires `log2(n)` hashes, rather than `n`.
 //!
+//! Caches are not stored within the objects they describe; each type chooses its cache via the
+//! type parameter of `CachedTreeHash`:
+//!
+//! - `TreeHashCache` for containers.
+//! - `ListTreeHashCache` for a `TrackedList`, a list/vector which records which of its items are
+//!   modified.
+//! - A custom struct for containers with fields that have their own caches (see the
+//!   `CachedTreeHash` derive in `tree_hash_derive`).
+//!
+//! A `TrackedList` only recalculates the leaves of the items modified since it was last hashed,
+//! rather than re-packing, re-hashing or comparing every item.
 //!
 //! # Example:
 //!
 //! ```
-//! use cached_tree_hash::TreeHashCache;
-//! use tree_hash_derive::{TreeHash, CachedTreeHash};
+//! use cached_tree_hash::{CachedTreeHash, ListTreeHashCache, TrackedList};
+//! use fixed_len_vec::{typenum::U1024, VariableList};
+//! use tree_hash::TreeHash;
+//! use tree_hash_derive::TreeHash;
 //!
-//! #[derive(TreeHash, CachedTreeHash)]
+//! #[derive(TreeHash)]
 //! struct Foo {
 //!     bar: u64,
-//!     baz: Vec<u64>
+//!     baz: u64,
 //! }
 //!
-//! let mut foo = Foo {
-//!     bar: 1,
-//!     baz: vec![0, 1, 2]
-//! };
+//! let mut list: TrackedList<VariableList<Foo, U1024>> = TrackedList::default();
+//! list.push(Foo { bar: 1, baz: 2 }).unwrap();
 //!
-//! let mut cache = TreeHashCache::new(&foo).unwrap();
+//! let mut cache = ListTreeHashCache::default();
+//! list.recalculate_tree_hash_root(&mut cache).unwrap();
 //!
-//! foo.baz[1] = 0;
+//! list[0].baz = 3;
+//! list.push(Foo { bar: 4, baz: 5 }).unwrap();
 //!
-//! cache.update(&foo).unwrap();
-//!
-//! println!("Root is: {:?}", cache.tree_hash_root().unwrap());
+//! let root = list.recalculate_tree_hash_root(&mut cache).unwrap();
+//! assert_eq!(root.as_bytes(), &list.tree_hash_root()[..]);
 //! ```
 
-use hashing::hash;
-use std::ops::Range;
-use tree_hash::{TreeHash, TreeHashType, BYTES_PER_CHUNK, HASHSIZE};
+use tree_hash::TreeHash;
 
-mod btree_overlay;
 mod errors;
 mod impls;
-pub mod merkleize;
-mod resize;
+mod tracked_list;
 mod tree_hash_cache;
 
-pub use btree_overlay::{BTreeOverlay, BTreeSchema};
 pub use errors::Error;
-pub use impls::vec;
+pub use ethereum_types::H256 as Hash256;
+pub use tracked_list::{ListTreeHashCache, TrackedList};
 pub use tree_hash_cache::TreeHashCache;
 
-pub trait CachedTreeHash: TreeHash {
-    fn tree_hash_cache_schema(&self, depth: usize) -> BTreeSchema;
-
-    fn num_tree_hash_cache_chunks(&self) -> usize {
-        self.tree_hash_cache_schema(0).into_overlay(0).num_chunks()
-    }
-
-    fn new_tree_hash_cache(&self, depth: usize) -> Result<TreeHashCache, Error>;
-
-    fn update_tree_hash_cache(&self, cache: &mut TreeHashCache) -> Result<(), Error>;
-}
-
-/// Implements `CachedTreeHash` on `$type`, where `$type` is a fixed-length vector and each item in
-/// the `$type` is encoded as bytes using `ssz_encode`.
-#[macro_export]
-macro_rules! cached_tree_hash_ssz_encoding_as_vector {
-    ($type: ident, $num_bytes: expr) => {
-        impl cached_tree_hash::CachedTreeHash for $type {
-            fn new_tree_hash_cache(
-                &self,
-                depth: usize,
-            ) -> Result<cached_tree_hash::TreeHashCache, cached_tree_hash::Error> {
-                let (cache, _schema) =
-                    cached_tree_hash::vec::new_tree_hash_cache(&ssz::ssz_encode(self), depth)?;
-
-                Ok(cache)
-            }
-
-            fn tree_hash_cache_schema(&self, depth: usize) -> cached_tree_hash::BTreeSchema {
-                let lengths =
-                    vec![1; cached_tree_hash::merkleize::num_unsanitized_leaves($num_bytes)];
-                cached_tree_hash::BTreeSchema::from_lengths(depth, lengths)
-            }
-
-            fn update_tree_hash_cache(
-                &self,
-                cache: &mut cached_tree_hash::TreeHashCache,
-            ) -> Result<(), cached_tree_hash::Error> {
-                cached_tree_hash::vec::update_tree_hash_cache(&ssz::ssz_encode(self), cache)?;
-
-                Ok(())
-            }
-        }
-    };
-}
-
-/// Implements `CachedTreeHash` on `$type`, where `$type` is a variable-length list and each item
-/// in `$type` is encoded as bytes by calling `item.to_bytes()`.
-#[macro_export]
-macro_rules! cached_tree_hash_bytes_as_list {
-    ($type: ident) => {
-        impl cached_tree_hash::CachedTreeHash for $type {
-            fn new_tree_hash_cache(
-                &self,
-                depth: usize,
-            ) -> Result<cached_tree_hash::TreeHashCache, cached_tree_hash::Error> {
-                let bytes = self.to_bytes();
-
-                let (mut cache, schema) =
-                    cached_tree_hash::vec::new_tree_hash_cache(&bytes, depth)?;
-
-                cache.add_length_nodes(schema.into_overlay(0).chunk_range(), bytes.len())?;
-
-                Ok(cache)
-            }
-
-            fn num_tree_hash_cache_chunks(&self) -> usize {
-                // Add two extra nodes to cater for the node before and after to allow mixing-in length.
-                cached_tree_hash::BTreeOverlay::new(self, 0, 0).num_chunks() + 2
-            }
-
-            fn tree_hash_cache_schema(&self, depth: usize) -> cached_tree_hash::BTreeSchema {
-                let bytes = self.to_bytes();
-                cached_tree_hash::vec::produce_schema(&bytes, depth)
-            }
-
-            fn update_tree_hash_cache(
-                &self,
-                cache: &mut cached_tree_hash::TreeHashCache,
-            ) -> Result<(), cached_tree_hash::Error> {
-                let bytes = self.to_bytes();
-
-                // Skip the length-mixed-in root node.
-                cache.chunk_index += 1;
-
-                // Update the cache, returning the new overlay.
-                let new_overlay = cached_tree_hash::vec::update_tree_hash_cache(&bytes, cache)?;
-
-                // Mix in length
-                cache.mix_in_length(new_overlay.chunk_range(), bytes.len())?;
-
-                // Skip an extra node to clear the length node.
-                cache.chunk_index += 1;
-
-                Ok(())
-            }
-        }
-    };
+/// Calculates the `tree_hash_root` of `self` using some `Cache`, re-using the results of the
+/// previous calculation where parts of `self` have not changed.
+pub trait CachedTreeHash<Cache: Default>: TreeHash {
+    /// Returns the `tree_hash_root` of `self`, updating `cache` to reflect the current value of
+    /// `self`.
+    ///
+    /// `self` is mutable so that it may clear any record of its modifications (e.g., the dirty
+    /// leaves of a `TrackedList`) once they are reflected in `cache`.
+    ///
+    /// A new cache is created with `Cache::default()`. The first call on a new cache is no faster
+    /// than `tree_hash_root`.
+    fn recalculate_tree_hash_root(&mut self, cache: &mut Cache) -> Result<Hash256, Error>;
 }
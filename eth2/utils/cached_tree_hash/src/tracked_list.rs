@@ -0,0 +1,338 @@
+use crate::{Hash256, TreeHashCache};
+use fixed_len_vec::{typenum::Unsigned, Error as VariableListError, VariableList};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tree_hash::{TreeHash, TreeHashType, BYTES_PER_CHUNK};
+
+/// The source of the tags which pair a `TrackedList` with the `ListTreeHashCache` it was last
+/// hashed with. `0` is never issued.
+static NEXT_TAG: AtomicU64 = AtomicU64::new(1);
+
+fn next_tag() -> u64 {
+    NEXT_TAG.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The leaves of a `TrackedList` which have been modified since it was last hashed.
+#[derive(Debug, Clone)]
+enum Dirty {
+    /// The given leaves, in the order they were modified (possibly with duplicates).
+    Leaves(Vec<usize>),
+    /// Every leaf, e.g., because the list has never been hashed or was modified in bulk.
+    All,
+}
+
+/// A list or vector (e.g., a `VariableList` or `FixedLenVec`) which records the leaves of its tree
+/// that are modified, so that a `ListTreeHashCache` only needs to recalculate those leaves.
+///
+/// Items may only be modified through the methods of `TrackedList` (e.g., `IndexMut`, `get_mut`,
+/// `push`), which mark the leaf of the modified item as dirty. Read-only access is provided by
+/// `Deref`. Changing a single item of a list of `n` items therefore costs a single leaf and
+/// `log2(n)` hashes when recalculating the root.
+///
+/// Each time the root is recalculated, the list and the cache are given the same new tag. The
+/// dirty leaves are only used if the tags still match, which is the case when the list and the
+/// cache are (clones of) the pair which were last hashed together. Otherwise (e.g., the list was
+/// decoded, or replaced by an unrelated list), every leaf is recalculated, so pairing a list with
+/// the wrong cache costs time but never produces an incorrect root.
+#[derive(Clone)]
+pub struct TrackedList<L> {
+    list: L,
+    dirty: Dirty,
+    /// The tag shared with the cache this list was last hashed with, or `0` if it never was.
+    tag: u64,
+}
+
+/// The cache for a `TrackedList`, storing every node of its tree.
+#[derive(Debug, Clone, Default)]
+pub struct ListTreeHashCache {
+    /// The tag shared with the list this cache was last updated with, or `0` if it never was.
+    tag: u64,
+    tree: TreeHashCache,
+}
+
+/// Two caches are equal if they hold the same tree, regardless of the list they were last updated
+/// with.
+impl PartialEq for ListTreeHashCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.tree == other.tree
+    }
+}
+
+impl<L> TrackedList<L> {
+    fn new(list: L) -> Self {
+        Self {
+            list,
+            dirty: Dirty::All,
+            tag: 0,
+        }
+    }
+}
+
+impl<L, T> TrackedList<L>
+where
+    L: Deref<Target = [T]> + DerefMut,
+    T: TreeHash,
+{
+    /// Returns a mutable reference to the item at `index`, marking it as modified.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.list.len() {
+            self.mark_dirty(index);
+        }
+
+        self.list.get_mut(index)
+    }
+
+    /// Returns a mutable slice of all items, marking every item as modified.
+    ///
+    /// Prefer `IndexMut` or `get_mut` when modifying only some of the items.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.dirty = Dirty::All;
+
+        &mut self.list
+    }
+
+    /// Returns an iterator over mutable references to all items, marking every item as modified.
+    ///
+    /// Prefer `IndexMut` or `get_mut` when modifying only some of the items.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Marks the leaf containing the item at `index` as modified.
+    fn mark_dirty(&mut self, index: usize) {
+        let len = self.list.len();
+
+        if let Dirty::Leaves(leaves) = &mut self.dirty {
+            let leaf = index / items_per_leaf::<T>();
+
+            if leaves.last() != Some(&leaf) {
+                leaves.push(leaf);
+            }
+
+            // Bound the memory used by a list which is repeatedly modified without being hashed.
+            if leaves.len() > len {
+                self.dirty = Dirty::All;
+            }
+        }
+    }
+
+    /// Returns the merkle root of the tree whose leaves are the (packed) items, recalculating only
+    /// the leaves which have been modified since `self` was last hashed with `cache`.
+    pub(crate) fn recalculate_merkle_root(&mut self, cache: &mut ListTreeHashCache) -> Hash256 {
+        let items_per_leaf = items_per_leaf::<T>();
+        let num_leaves = (self.list.len() + items_per_leaf - 1) / items_per_leaf;
+
+        let mut dirty_leaves = match std::mem::replace(&mut self.dirty, Dirty::Leaves(vec![])) {
+            Dirty::Leaves(leaves) => leaves,
+            Dirty::All => (0..num_leaves).collect(),
+        };
+
+        // The dirty leaves are only meaningful to the cache `self` was last hashed with.
+        if self.tag == 0 || self.tag != cache.tag {
+            dirty_leaves = (0..num_leaves).collect();
+        }
+
+        dirty_leaves.sort_unstable();
+        dirty_leaves.dedup();
+
+        let leaves: Vec<(usize, Hash256)> = dirty_leaves
+            .into_iter()
+            .map(|leaf| {
+                let start = leaf * items_per_leaf;
+                let end = std::cmp::min(start + items_per_leaf, self.list.len());
+
+                (leaf, leaf_for_items(&self.list[start..end]))
+            })
+            .collect();
+
+        let root = cache.tree.update_leaves(num_leaves, &leaves);
+
+        let tag = next_tag();
+        self.tag = tag;
+        cache.tag = tag;
+
+        root
+    }
+}
+
+impl<T: TreeHash, N: Unsigned> TrackedList<VariableList<T, N>> {
+    /// Appends `value` to the list, marking it as modified.
+    ///
+    /// Returns an error if the list is full.
+    pub fn push(&mut self, value: T) -> Result<(), VariableListError> {
+        self.list.push(value)?;
+
+        let index = self.list.len() - 1;
+        self.mark_dirty(index);
+
+        Ok(())
+    }
+}
+
+/// Returns the number of items packed into each leaf of a list of `T`.
+fn items_per_leaf<T: TreeHash>() -> usize {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => T::tree_hash_packing_factor(),
+        TreeHashType::Container | TreeHashType::List | TreeHashType::Vector => 1,
+    }
+}
+
+/// Returns the leaf containing `items`, which are either a single item or the packed items of
+/// one chunk.
+fn leaf_for_items<T: TreeHash>(items: &[T]) -> Hash256 {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => {
+            let mut leaf = [0; BYTES_PER_CHUNK];
+            let mut offset = 0;
+
+            for item in items {
+                let bytes = item.tree_hash_packed_encoding();
+                leaf[offset..offset + bytes.len()].copy_from_slice(&bytes);
+                offset += bytes.len();
+            }
+
+            Hash256::from(leaf)
+        }
+        TreeHashType::Container | TreeHashType::List | TreeHashType::Vector => {
+            Hash256::from_slice(&items[0].tree_hash_root())
+        }
+    }
+}
+
+impl<L> Deref for TrackedList<L> {
+    type Target = L;
+
+    fn deref(&self) -> &L {
+        &self.list
+    }
+}
+
+impl<L: Index<I>, I> Index<I> for TrackedList<L> {
+    type Output = L::Output;
+
+    fn index(&self, index: I) -> &L::Output {
+        self.list.index(index)
+    }
+}
+
+impl<L, T> IndexMut<usize> for TrackedList<L>
+where
+    L: Deref<Target = [T]> + DerefMut + IndexMut<usize, Output = T>,
+    T: TreeHash,
+{
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        if index < self.list.len() {
+            self.mark_dirty(index);
+        }
+
+        self.list.index_mut(index)
+    }
+}
+
+impl<'a, T, N: Unsigned> IntoIterator for &'a TrackedList<VariableList<T, N>> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.iter()
+    }
+}
+
+impl<L: From<Vec<T>>, T> From<Vec<T>> for TrackedList<L> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::new(L::from(vec))
+    }
+}
+
+impl<L: Default> Default for TrackedList<L> {
+    fn default() -> Self {
+        Self::new(L::default())
+    }
+}
+
+/// Two lists are equal if their items are equal, regardless of which items have been modified.
+impl<L: PartialEq> PartialEq for TrackedList<L> {
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl<L: fmt::Debug> fmt::Debug for TrackedList<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+impl<L: TreeHash> TreeHash for TrackedList<L> {
+    fn tree_hash_type() -> TreeHashType {
+        L::tree_hash_type()
+    }
+
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        self.list.tree_hash_packed_encoding()
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        L::tree_hash_packing_factor()
+    }
+
+    fn tree_hash_root(&self) -> Vec<u8> {
+        self.list.tree_hash_root()
+    }
+
+    fn tree_hash_proof(&self, gindex: usize) -> Result<tree_hash::Proof, tree_hash::Error> {
+        self.list.tree_hash_proof(gindex)
+    }
+
+    fn tree_hash_gindex(&self, path: &[tree_hash::PathElement]) -> Result<usize, tree_hash::Error> {
+        self.list.tree_hash_gindex(path)
+    }
+}
+
+impl<L: ssz::Encode> ssz::Encode for TrackedList<L> {
+    fn is_ssz_fixed_len() -> bool {
+        L::is_ssz_fixed_len()
+    }
+
+    fn ssz_fixed_len() -> usize {
+        L::ssz_fixed_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        self.list.ssz_append(buf)
+    }
+}
+
+impl<L: ssz::Decode> ssz::Decode for TrackedList<L> {
+    fn is_ssz_fixed_len() -> bool {
+        L::is_ssz_fixed_len()
+    }
+
+    fn ssz_fixed_len() -> usize {
+        L::ssz_fixed_len()
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        L::from_ssz_bytes(bytes).map(Self::new)
+    }
+}
+
+impl<L: Serialize> Serialize for TrackedList<L> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.list.serialize(serializer)
+    }
+}
+
+impl<'de, L: Deserialize<'de>> Deserialize<'de> for TrackedList<L> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        L::deserialize(deserializer).map(Self::new)
+    }
+}
@@ -5,7 +5,7 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::path::PathBuf;
 use std::time::SystemTime;
-use types::ChainSpec;
+use types::{ChainSpec, MainnetEthSpec, MinimalEthSpec, YamlConfig};
 
 /// The core configuration of a Lighthouse beacon node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,11 +45,35 @@ impl Eth2Config {
     ///
     /// Returns an error if arguments are obviously invalid. May succeed even if some values are
     /// invalid.
-    pub fn apply_cli_args(&mut self, args: &ArgMatches) -> Result<(), &'static str> {
+    pub fn apply_cli_args(&mut self, args: &ArgMatches) -> Result<(), String> {
+        if let Some(path) = args.value_of("spec-yaml") {
+            self.apply_yaml_config(&read_yaml_config(PathBuf::from(path))?)?;
+        }
+
         if args.is_present("recent-genesis") {
             self.spec.genesis_time = recent_genesis_time()
         }
 
+        if let Some(genesis_time) = args.value_of("genesis-time") {
+            self.spec.genesis_time = genesis_time
+                .parse()
+                .map_err(|_| format!("Invalid genesis-time: {}", genesis_time))?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the values in `self.spec` with those defined in `config`.
+    ///
+    /// Returns an error if `config` is incompatible with the compile-time constants of
+    /// `self.spec_constants`.
+    pub fn apply_yaml_config(&mut self, config: &YamlConfig) -> Result<(), String> {
+        self.spec = match self.spec_constants.as_str() {
+            "mainnet" => config.apply_to_chain_spec::<MainnetEthSpec>(&self.spec),
+            "minimal" => config.apply_to_chain_spec::<MinimalEthSpec>(&self.spec),
+            other => Err(format!("Unknown spec constants: {}", other)),
+        }?;
+
         Ok(())
     }
 }
@@ -106,6 +130,15 @@ where
     }
 }
 
+/// Loads a spec-compliant YAML config (e.g., `configs/constant_presets/minimal.yaml` from the
+/// Ethereum 2.0 specification).
+pub fn read_yaml_config(path: PathBuf) -> Result<YamlConfig, String> {
+    let file =
+        File::open(path.clone()).map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+
+    serde_yaml::from_reader(file).map_err(|e| format!("Unable to parse {:?}: {:?}", path, e))
+}
+
 pub fn get_data_dir(args: &ArgMatches, default_data_dir: PathBuf) -> Result<PathBuf, &'static str> {
     if let Some(data_dir) = args.value_of("data_dir") {
         Ok(PathBuf::from(data_dir))
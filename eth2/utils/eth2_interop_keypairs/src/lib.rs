@@ -0,0 +1,84 @@
+//! Produces the "deterministic" validator private keys used for inter-operability testing of
+//! Ethereum 2.0 clients.
+//!
+//! The secret key for the validator at `index` is:
+//!
+//! `int.from_bytes(sha256(int_to_bytes32(index)), "little") % curve_order`
+//!
+//! These keys are public knowledge, they must **never** be used to secure real funds.
+use bls::{Keypair, PublicKey, SecretKey, BLS_SECRET_KEY_BYTE_SIZE};
+use hashing::hash;
+use int_to_bytes::int_to_bytes32;
+use num_bigint::BigUint;
+use rayon::prelude::*;
+
+/// The order of the BLS12-381 curve subgroup, in decimal.
+const CURVE_ORDER: &[u8] =
+    b"52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+/// Returns the big-endian bytes of the secret key for the validator at `validator_index`, left
+/// padded to `BLS_SECRET_KEY_BYTE_SIZE`.
+pub fn be_private_key(validator_index: usize) -> Vec<u8> {
+    let curve_order = BigUint::parse_bytes(CURVE_ORDER, 10).expect("Curve order should be valid");
+
+    let preimage = int_to_bytes32(validator_index as u64);
+    let privkey = BigUint::from_bytes_le(&hash(&preimage)) % curve_order;
+
+    let bytes = privkey.to_bytes_be();
+    let mut padded = vec![0; BLS_SECRET_KEY_BYTE_SIZE - bytes.len()];
+    padded.extend_from_slice(&bytes);
+    padded
+}
+
+/// Returns the interop keypair for the validator at `validator_index`.
+pub fn keypair(validator_index: usize) -> Keypair {
+    let sk = SecretKey::from_bytes(&be_private_key(validator_index))
+        .expect("Interop secret keys should always be valid");
+    let pk = PublicKey::from_secret_key(&sk);
+
+    Keypair { sk, pk }
+}
+
+/// Returns the interop keypairs for each of the validators in `validator_indices`, in order.
+pub fn keypairs<I: IntoIterator<Item = usize>>(validator_indices: I) -> Vec<Keypair> {
+    validator_indices
+        .into_iter()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(keypair)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test vectors from the interop "mocked start" specification.
+    const PRIVKEYS: &[&str] = &[
+        "25295f0d1d592a90b333e26e85149708208e9f8e8bc18f6c77bd62f8ad7a6866",
+        "51d0b65185db6989ab0b560d6deed19c7ead0e24b9b6372cbecb1f26bdfad000",
+        "315ed405fafe339603932eebe8dbfd650ce5dafa561f6928664c75db85f97857",
+    ];
+
+    #[test]
+    fn private_keys() {
+        for (i, expected) in PRIVKEYS.iter().enumerate() {
+            let key = be_private_key(i);
+
+            assert_eq!(key.len(), BLS_SECRET_KEY_BYTE_SIZE);
+            assert_eq!(
+                &hex::encode(&key[BLS_SECRET_KEY_BYTE_SIZE - 32..]),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn keypairs_are_in_order() {
+        let batch = keypairs(3..6);
+
+        for (i, keypair) in batch.iter().enumerate() {
+            assert_eq!(keypair.pk, super::keypair(i + 3).pk);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{Error, KeyPath};
+use bls::{Keypair, PublicKey, SecretKey, BLS_SECRET_KEY_BYTE_SIZE};
+use hashing::hash;
+use num_bigint::BigUint;
+use ring::{digest, hkdf, hmac};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// The minimum number of bytes in a seed, as per EIP-2333.
+pub const MIN_SEED_BYTES: usize = 32;
+
+/// The number of bytes in a secret key produced by the tree.
+const SECRET_KEY_BYTES: usize = 32;
+/// The number of bytes output by the hash function (SHA-256).
+const HASH_SIZE: usize = 32;
+/// The number of chunks in each half of a Lamport secret key.
+const LAMPORT_ARRAY_SIZE: usize = 255;
+/// The number of bytes of key material expanded before reduction modulo `r`.
+const MOD_R_L: usize = 48;
+/// The initial salt used when deriving a secret key from key material.
+const SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+/// The order of the BLS12-381 curve subgroup, in decimal.
+const CURVE_ORDER: &[u8] =
+    b"52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+/// A node in the EIP-2333 key tree.
+///
+/// The secret key is zeroed when dropped and is not printed by `Debug`.
+///
+/// Spec: https://eips.ethereum.org/EIPS/eip-2333
+#[derive(PartialEq, Clone)]
+pub struct DerivedKey([u8; SECRET_KEY_BYTES]);
+
+impl DerivedKey {
+    /// Returns the master (root) key for `seed`.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, Error> {
+        if seed.len() < MIN_SEED_BYTES {
+            return Err(Error::SeedTooShort(seed.len()));
+        }
+
+        Ok(Self::from_biguint(hkdf_mod_r(seed)))
+    }
+
+    /// Returns the key at `path`, relative to the master key for `seed`.
+    pub fn from_path(seed: &[u8], path: &KeyPath) -> Result<Self, Error> {
+        let master = Self::from_seed(seed)?;
+        Ok(path
+            .nodes()
+            .iter()
+            .fold(master, |key, index| key.child(*index)))
+    }
+
+    /// Returns the child of `self` at `index`.
+    pub fn child(&self, index: u32) -> Self {
+        let compressed_lamport_pk = parent_sk_to_lamport_pk(&self.0, index);
+        Self::from_biguint(hkdf_mod_r(&compressed_lamport_pk))
+    }
+
+    /// Returns the secret key as 32 big-endian bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Returns the secret key as a decimal string, the format used in the EIP-2333 test vectors.
+    pub fn to_dec_string(&self) -> String {
+        BigUint::from_bytes_be(&self.0).to_str_radix(10)
+    }
+
+    /// Convert `self` into a `bls::SecretKey`.
+    pub fn secret_key(&self) -> Result<SecretKey, Error> {
+        SecretKey::from_bytes(&left_pad(&self.0, BLS_SECRET_KEY_BYTE_SIZE))
+            .map_err(|e| Error::InvalidSecretKey(format!("{:?}", e)))
+    }
+
+    /// Convert `self` into a `bls::Keypair`.
+    pub fn keypair(&self) -> Result<Keypair, Error> {
+        let sk = self.secret_key()?;
+        let pk = PublicKey::from_secret_key(&sk);
+        Ok(Keypair { sk, pk })
+    }
+
+    /// Store `sk`, which is less than the curve order, as big-endian bytes.
+    fn from_biguint(sk: BigUint) -> Self {
+        let mut bytes = [0; SECRET_KEY_BYTES];
+        bytes.copy_from_slice(&left_pad(&sk.to_bytes_be(), SECRET_KEY_BYTES));
+        DerivedKey(bytes)
+    }
+}
+
+impl fmt::Debug for DerivedKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DerivedKey(<redacted>)")
+    }
+}
+
+impl Drop for DerivedKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Reduce the output of HKDF over `ikm` to a non-zero integer modulo the curve order.
+///
+/// Spec: `HKDF_mod_r`, with an empty `key_info`.
+fn hkdf_mod_r(ikm: &[u8]) -> BigUint {
+    let curve_order = BigUint::parse_bytes(CURVE_ORDER, 10).expect("Curve order is valid");
+    let ikm = [ikm, &[0][..]].concat();
+    let info = (MOD_R_L as u16).to_be_bytes();
+
+    let mut salt = SALT.to_vec();
+    loop {
+        salt = hash(&salt);
+
+        let mut okm = [0; MOD_R_L];
+        hkdf_extract_and_expand(&salt, &ikm, &info, &mut okm);
+
+        let sk = BigUint::from_bytes_be(&okm) % &curve_order;
+        if sk != BigUint::from(0_u8) {
+            return sk;
+        }
+    }
+}
+
+/// Returns the compressed Lamport public key used to derive the child of `parent_sk` at `index`.
+///
+/// Spec: `parent_SK_to_lamport_PK`.
+fn parent_sk_to_lamport_pk(parent_sk: &[u8], index: u32) -> Vec<u8> {
+    let salt = index.to_be_bytes();
+    let not_ikm: Vec<u8> = parent_sk.iter().map(|byte| !byte).collect();
+
+    let lamport_pk: Vec<u8> = [
+        ikm_to_lamport_sk(&salt, parent_sk),
+        ikm_to_lamport_sk(&salt, &not_ikm),
+    ]
+    .iter()
+    .flat_map(|lamport_sk| lamport_sk.chunks(HASH_SIZE).flat_map(hash))
+    .collect();
+
+    hash(&lamport_pk)
+}
+
+/// Returns the concatenated chunks of a Lamport secret key.
+///
+/// Spec: `IKM_to_lamport_SK`.
+fn ikm_to_lamport_sk(salt: &[u8], ikm: &[u8]) -> Vec<u8> {
+    let mut okm = vec![0; HASH_SIZE * LAMPORT_ARRAY_SIZE];
+    hkdf_extract_and_expand(salt, ikm, &[], &mut okm);
+    okm
+}
+
+/// HKDF-SHA256, filling `out` with output key material.
+fn hkdf_extract_and_expand(salt: &[u8], ikm: &[u8], info: &[u8], out: &mut [u8]) {
+    let salt = hmac::SigningKey::new(&digest::SHA256, salt);
+    hkdf::extract_and_expand(&salt, ikm, info, out);
+}
+
+/// Left-pad `bytes` with zeros until it is `len` bytes long.
+fn left_pad(bytes: &[u8], len: usize) -> Vec<u8> {
+    let mut padded = vec![0; len.saturating_sub(bytes.len())];
+    padded.extend_from_slice(bytes);
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test case 0 from EIP-2333.
+    const SEED: &str = "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04";
+    const MASTER_SK: &str =
+        "6083874454709270928345386274498605044986640685124978867557563392430687146096";
+    const CHILD_INDEX: u32 = 0;
+    const CHILD_SK: &str =
+        "20397789859736650942317412262472558107875392172444076792671091975210932703118";
+
+    #[test]
+    fn eip2333_test_vector() {
+        let seed = hex::decode(SEED).unwrap();
+
+        let master = DerivedKey::from_seed(&seed).unwrap();
+        assert_eq!(master.to_dec_string(), MASTER_SK);
+
+        let child = master.child(CHILD_INDEX);
+        assert_eq!(child.to_dec_string(), CHILD_SK);
+    }
+
+    #[test]
+    fn rejects_short_seed() {
+        assert_eq!(
+            DerivedKey::from_seed(&[0; MIN_SEED_BYTES - 1]),
+            Err(Error::SeedTooShort(MIN_SEED_BYTES - 1))
+        );
+    }
+
+    #[test]
+    fn from_path_matches_children() {
+        let seed = hex::decode(SEED).unwrap();
+        let path: KeyPath = "m/12381/3600/0/0/0".parse().unwrap();
+
+        let expected = DerivedKey::from_seed(&seed)
+            .unwrap()
+            .child(12381)
+            .child(3600)
+            .child(0)
+            .child(0)
+            .child(0);
+
+        assert_eq!(DerivedKey::from_path(&seed, &path), Ok(expected));
+    }
+
+    #[test]
+    fn converts_to_keypair() {
+        let seed = hex::decode(SEED).unwrap();
+        let keypair = DerivedKey::from_seed(&seed).unwrap().keypair().unwrap();
+
+        assert_eq!(keypair.pk, PublicKey::from_secret_key(&keypair.sk));
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let seed = hex::decode(SEED).unwrap();
+        let master = DerivedKey::from_seed(&seed).unwrap();
+
+        assert_eq!(format!("{:?}", master), "DerivedKey(<redacted>)");
+    }
+}
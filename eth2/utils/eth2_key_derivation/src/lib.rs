@@ -0,0 +1,41 @@
+//! Hierarchical derivation of BLS keys from a single seed.
+//!
+//! - A seed is produced from a BIP-39 mnemonic (and optional password).
+//! - A tree of BLS secret keys is derived from the seed, as per EIP-2333.
+//! - Validator signing and withdrawal keys are found at the paths defined in EIP-2334.
+//!
+//! This allows every validator key to be recovered from the mnemonic alone.
+mod derived_key;
+mod path;
+
+pub use bip39::{Language, Mnemonic, MnemonicType, Seed};
+pub use derived_key::{DerivedKey, MIN_SEED_BYTES};
+pub use path::{KeyPath, KeyType, COIN_TYPE, PURPOSE};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// The seed was shorter than `MIN_SEED_BYTES`.
+    SeedTooShort(usize),
+    /// The derived key could not be converted into a `bls::SecretKey`.
+    InvalidSecretKey(String),
+    /// The mnemonic phrase was not valid BIP-39.
+    InvalidMnemonic(String),
+    /// The path was not of the form `m/a/b/c`.
+    InvalidPath(String),
+}
+
+/// Generate a new random 24-word English mnemonic.
+pub fn generate_mnemonic() -> Mnemonic {
+    Mnemonic::new(MnemonicType::Words24, Language::English)
+}
+
+/// Parse and validate an English mnemonic phrase.
+pub fn mnemonic_from_phrase(phrase: &str) -> Result<Mnemonic, Error> {
+    Mnemonic::from_phrase(phrase.trim(), Language::English)
+        .map_err(|e| Error::InvalidMnemonic(format!("{:?}", e)))
+}
+
+/// Returns the BIP-39 seed for `mnemonic`, protected by `password` (which may be empty).
+pub fn seed_from_mnemonic(mnemonic: &Mnemonic, password: &str) -> Seed {
+    Seed::new(mnemonic, password)
+}
@@ -0,0 +1,105 @@
+use crate::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The first node of every EIP-2334 path.
+pub const PURPOSE: u32 = 12381;
+/// The second node of every EIP-2334 path, identifying Ethereum 2.0.
+pub const COIN_TYPE: u32 = 3600;
+
+/// The kinds of keys held by a validator.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum KeyType {
+    /// Used to withdraw the validator's balance.
+    Withdrawal,
+    /// Used to sign blocks, attestations and other messages.
+    Signing,
+}
+
+/// A path in the EIP-2333 key tree, such as `m/12381/3600/0/0/0`.
+///
+/// Spec: https://eips.ethereum.org/EIPS/eip-2334
+#[derive(Debug, PartialEq, Clone)]
+pub struct KeyPath(Vec<u32>);
+
+impl KeyPath {
+    /// Returns the EIP-2334 path for the `key_type` key of the `account`th validator.
+    ///
+    /// - Withdrawal keys: `m/12381/3600/account/0`
+    /// - Signing keys: `m/12381/3600/account/0/0`
+    pub fn validator(account: u32, key_type: KeyType) -> Self {
+        let mut nodes = vec![PURPOSE, COIN_TYPE, account, 0];
+
+        if key_type == KeyType::Signing {
+            nodes.push(0);
+        }
+
+        KeyPath(nodes)
+    }
+
+    /// The indices of each node following the master key.
+    pub fn nodes(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for node in &self.0 {
+            write!(f, "/{}", node)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeyPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut parts = s.trim().split('/');
+
+        if parts.next() != Some("m") {
+            return Err(Error::InvalidPath(format!("{} does not start with m", s)));
+        }
+
+        parts
+            .map(|part| {
+                part.parse::<u32>()
+                    .map_err(|_| Error::InvalidPath(format!("{} has invalid node {}", s, part)))
+            })
+            .collect::<Result<Vec<u32>, Error>>()
+            .map(KeyPath)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validator_paths() {
+        assert_eq!(
+            KeyPath::validator(7, KeyType::Withdrawal).to_string(),
+            "m/12381/3600/7/0"
+        );
+        assert_eq!(
+            KeyPath::validator(7, KeyType::Signing).to_string(),
+            "m/12381/3600/7/0/0"
+        );
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let path = KeyPath::validator(42, KeyType::Signing);
+        assert_eq!(path.to_string().parse::<KeyPath>(), Ok(path));
+        assert_eq!("m".parse::<KeyPath>(), Ok(KeyPath(vec![])));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("12381/3600".parse::<KeyPath>().is_err());
+        assert!("m/12381/x".parse::<KeyPath>().is_err());
+        assert!("m/12381/".parse::<KeyPath>().is_err());
+    }
+}
@@ -19,32 +19,13 @@ where
     fn tree_hash_root(&self) -> Vec<u8> {
         tree_hash::impls::vec_tree_hash_root(&self.vec)
     }
-}
-
-impl<T, N: Unsigned> cached_tree_hash::CachedTreeHash for FixedLenVec<T, N>
-where
-    T: cached_tree_hash::CachedTreeHash + tree_hash::TreeHash,
-{
-    fn new_tree_hash_cache(
-        &self,
-        depth: usize,
-    ) -> Result<cached_tree_hash::TreeHashCache, cached_tree_hash::Error> {
-        let (cache, _overlay) = cached_tree_hash::vec::new_tree_hash_cache(&self.vec, depth)?;
 
-        Ok(cache)
+    fn tree_hash_proof(&self, gindex: usize) -> Result<tree_hash::Proof, tree_hash::Error> {
+        tree_hash::proof::vec_proof(&self.vec, gindex)
     }
 
-    fn tree_hash_cache_schema(&self, depth: usize) -> cached_tree_hash::BTreeSchema {
-        cached_tree_hash::vec::produce_schema(&self.vec, depth)
-    }
-
-    fn update_tree_hash_cache(
-        &self,
-        cache: &mut cached_tree_hash::TreeHashCache,
-    ) -> Result<(), cached_tree_hash::Error> {
-        cached_tree_hash::vec::update_tree_hash_cache(&self.vec, cache)?;
-
-        Ok(())
+    fn tree_hash_gindex(&self, path: &[tree_hash::PathElement]) -> Result<usize, tree_hash::Error> {
+        tree_hash::proof::vec_gindex(&self.vec, path)
     }
 }
 
@@ -81,6 +62,14 @@ where
             encoder.finalize();
         }
     }
+
+    fn ssz_bytes_len(&self) -> usize {
+        ssz::list_bytes_len(&self.vec)
+    }
+
+    fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ssz::encode_list_to_writer(&self.vec, writer)
+    }
 }
 
 impl<T, N: Unsigned> ssz::Decode for FixedLenVec<T, N>
@@ -109,9 +98,128 @@ where
                 .collect::<Result<Vec<T>, _>>()
                 .and_then(|vec| Ok(vec.into()))
         } else {
-            ssz::decode_list_of_variable_length_items(bytes).and_then(|vec| Ok(vec.into()))
+            ssz::decode_list_of_variable_length_items(bytes, Some(N::to_usize()))
+                .and_then(|vec| Ok(vec.into()))
+        }
+    }
+
+    fn from_ssz_reader<R: std::io::Read>(
+        reader: &mut R,
+        len: usize,
+    ) -> Result<Self, ssz::DecodeError> {
+        ssz::decode_list_from_reader(reader, len, Some(N::to_usize())).map(Into::into)
+    }
+}
+
+impl<T, N: Unsigned> tree_hash::TreeHash for VariableList<T, N>
+where
+    T: tree_hash::TreeHash,
+{
+    fn tree_hash_type() -> tree_hash::TreeHashType {
+        tree_hash::TreeHashType::List
+    }
+
+    fn tree_hash_packed_encoding(&self) -> Vec<u8> {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_packing_factor() -> usize {
+        unreachable!("List should never be packed.")
+    }
+
+    fn tree_hash_root(&self) -> Vec<u8> {
+        // Hashing the slice mixes-in the length.
+        <&[T] as tree_hash::TreeHash>::tree_hash_root(&&self[..])
+    }
+
+    fn tree_hash_proof(&self, gindex: usize) -> Result<tree_hash::Proof, tree_hash::Error> {
+        tree_hash::proof::list_proof(self, gindex)
+    }
+
+    fn tree_hash_gindex(&self, path: &[tree_hash::PathElement]) -> Result<usize, tree_hash::Error> {
+        tree_hash::proof::list_gindex(self, path)
+    }
+}
+
+impl<T, N: Unsigned> ssz::Encode for VariableList<T, N>
+where
+    T: ssz::Encode,
+{
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        if T::is_ssz_fixed_len() {
+            buf.reserve(T::ssz_fixed_len() * self.len());
+
+            for item in self.iter() {
+                item.ssz_append(buf);
+            }
+        } else {
+            let mut encoder = ssz::SszEncoder::list(buf, self.len() * ssz::BYTES_PER_LENGTH_OFFSET);
+
+            for item in self.iter() {
+                encoder.append(item);
+            }
+
+            encoder.finalize();
         }
     }
+
+    fn ssz_bytes_len(&self) -> usize {
+        ssz::list_bytes_len(self)
+    }
+
+    fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        ssz::encode_list_to_writer(self, writer)
+    }
+}
+
+impl<T, N: Unsigned> ssz::Decode for VariableList<T, N>
+where
+    T: ssz::Decode,
+{
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    /// Returns an error if `bytes` contains more than `N` items, without decoding them.
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        let max_len = N::to_usize();
+
+        let vec = if bytes.is_empty() {
+            vec![]
+        } else if T::is_ssz_fixed_len() {
+            let num_items = bytes.len() / T::ssz_fixed_len();
+
+            if num_items > max_len {
+                return Err(ssz::DecodeError::BytesInvalid(format!(
+                    "List of {} items exceeds maximum of {}",
+                    num_items, max_len
+                )));
+            }
+
+            bytes
+                .chunks(T::ssz_fixed_len())
+                .map(|chunk| T::from_ssz_bytes(chunk))
+                .collect::<Result<Vec<T>, _>>()?
+        } else {
+            ssz::decode_list_of_variable_length_items(bytes, Some(max_len))?
+        };
+
+        Self::new(vec).map_err(|e| ssz::DecodeError::BytesInvalid(format!("{:?}", e)))
+    }
+
+    /// Returns an error if the bytes contain more than `N` items, without reading them.
+    fn from_ssz_reader<R: std::io::Read>(
+        reader: &mut R,
+        len: usize,
+    ) -> Result<Self, ssz::DecodeError> {
+        let vec = ssz::decode_list_from_reader(reader, len, Some(N::to_usize()))?;
+
+        Self::new(vec).map_err(|e| ssz::DecodeError::BytesInvalid(format!("{:?}", e)))
+    }
 }
 
 #[cfg(test)]
@@ -129,6 +237,16 @@ mod ssz_tests {
 
     fn round_trip<T: Encode + Decode + std::fmt::Debug + PartialEq>(item: T) {
         let encoded = &item.as_ssz_bytes();
+
+        let mut written = vec![];
+        item.ssz_write(&mut written).unwrap();
+        assert_eq!(&written, encoded);
+        assert_eq!(item.ssz_bytes_len(), encoded.len());
+
+        assert_eq!(
+            T::from_ssz_reader(&mut &encoded[..], encoded.len()).as_ref(),
+            Ok(&item)
+        );
         assert_eq!(T::from_ssz_bytes(&encoded), Ok(item));
     }
 
@@ -137,4 +255,45 @@ mod ssz_tests {
         round_trip::<FixedLenVec<u16, U8>>(vec![42; 8].into());
         round_trip::<FixedLenVec<u16, U8>>(vec![0; 8].into());
     }
+    #[test]
+    fn variable_list_round_trip() {
+        round_trip::<VariableList<u16, U8>>(vec![].into());
+        round_trip::<VariableList<u16, U8>>(vec![42; 8].into());
+        round_trip::<VariableList<Vec<u16>, U2>>(vec![vec![], vec![1, 2]].into());
+    }
+
+    #[test]
+    fn variable_list_encodes_as_vec() {
+        let vec = vec![vec![1_u16], vec![2, 3]];
+        let list: VariableList<Vec<u16>, U4> = vec.clone().into();
+
+        assert_eq!(list.as_ssz_bytes(), vec.as_ssz_bytes());
+    }
+
+    #[test]
+    fn variable_list_exceeds_max_len() {
+        let bytes = vec![42_u16; 3].as_ssz_bytes();
+        assert!(VariableList::<u16, U2>::from_ssz_bytes(&bytes).is_err());
+
+        let bytes = vec![vec![1_u16], vec![2], vec![3]].as_ssz_bytes();
+        assert!(VariableList::<Vec<u16>, U2>::from_ssz_bytes(&bytes).is_err());
+        assert!(
+            VariableList::<Vec<u16>, U2>::from_ssz_reader(&mut &bytes[..], bytes.len()).is_err()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tree_hash_tests {
+    use super::*;
+    use tree_hash::TreeHash;
+    use typenum::*;
+
+    #[test]
+    fn variable_list_hashes_as_vec() {
+        let vec = vec![1_u64, 2, 3];
+        let list: VariableList<u64, U8> = vec.clone().into();
+
+        assert_eq!(list.tree_hash_root(), vec.tree_hash_root());
+    }
 }
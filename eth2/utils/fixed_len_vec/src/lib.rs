@@ -1,12 +1,20 @@
 use serde_derive::{Deserialize, Serialize};
 use std::marker::PhantomData;
-use std::ops::{Deref, Index, IndexMut};
+use std::ops::{Deref, DerefMut, Index, IndexMut};
 use std::slice::SliceIndex;
 use typenum::Unsigned;
 
 pub use typenum;
+pub use variable_list::VariableList;
 
 mod impls;
+mod variable_list;
+
+/// Returned when an operation would cause a `VariableList` to exceed its maximum length.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    TooLong { len: usize, max_len: usize },
+}
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -79,6 +87,12 @@ impl<T, N: Unsigned> Deref for FixedLenVec<T, N> {
     }
 }
 
+impl<T, N: Unsigned> DerefMut for FixedLenVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.vec[..]
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,214 @@
+use crate::Error;
+use serde::de::{Deserialize, Deserializer, Error as SerdeError};
+use serde::ser::{Serialize, Serializer};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::slice::SliceIndex;
+use typenum::Unsigned;
+
+/// A list of at most `N` items.
+///
+/// Unlike `FixedLenVec`, the list may be any length between `0` and `N`. Attempting to exceed `N`
+/// items (by pushing or decoding) results in an error, rather than an allocation.
+///
+/// Encoded and tree-hashed identically to a `Vec<T>`, with the length mixed-in to the root.
+#[derive(Debug, PartialEq, Clone)]
+pub struct VariableList<T, N> {
+    vec: Vec<T>,
+    _phantom: PhantomData<N>,
+}
+
+impl<T, N: Unsigned> VariableList<T, N> {
+    /// Returns a new list containing `vec`, or an error if `vec` is longer than `N`.
+    pub fn new(vec: Vec<T>) -> Result<Self, Error> {
+        if vec.len() <= Self::max_len() {
+            Ok(Self {
+                vec,
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(Error::TooLong {
+                len: vec.len(),
+                max_len: Self::max_len(),
+            })
+        }
+    }
+
+    /// Returns an empty list.
+    pub fn empty() -> Self {
+        Self {
+            vec: vec![],
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The maximum number of items in the list, `N`.
+    pub fn max_len() -> usize {
+        N::to_usize()
+    }
+
+    /// Appends `value` to the list, returning an error if the list is already full.
+    pub fn push(&mut self, value: T) -> Result<(), Error> {
+        if self.len() < Self::max_len() {
+            self.vec.push(value);
+            Ok(())
+        } else {
+            Err(Error::TooLong {
+                len: self.len() + 1,
+                max_len: Self::max_len(),
+            })
+        }
+    }
+}
+
+/// Truncates `vec` to `N` items.
+///
+/// Use `VariableList::new` to detect lists which are too long.
+impl<T, N: Unsigned> From<Vec<T>> for VariableList<T, N> {
+    fn from(mut vec: Vec<T>) -> Self {
+        vec.truncate(N::to_usize());
+
+        Self {
+            vec,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, N: Unsigned> Into<Vec<T>> for VariableList<T, N> {
+    fn into(self) -> Vec<T> {
+        self.vec
+    }
+}
+
+impl<T, N: Unsigned> Default for VariableList<T, N> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T, N: Unsigned, I: SliceIndex<[T]>> Index<I> for VariableList<T, N> {
+    type Output = I::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(&self.vec, index)
+    }
+}
+
+impl<T, N: Unsigned, I: SliceIndex<[T]>> IndexMut<I> for VariableList<T, N> {
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(&mut self.vec, index)
+    }
+}
+
+impl<T, N: Unsigned> Deref for VariableList<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.vec[..]
+    }
+}
+
+impl<T, N: Unsigned> DerefMut for VariableList<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.vec[..]
+    }
+}
+
+impl<'a, T, N: Unsigned> IntoIterator for &'a VariableList<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.iter()
+    }
+}
+
+impl<'a, T, N: Unsigned> IntoIterator for &'a mut VariableList<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.iter_mut()
+    }
+}
+
+impl<T: Serialize, N: Unsigned> Serialize for VariableList<T, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.vec.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, N: Unsigned> Deserialize<'de> for VariableList<T, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let vec = Vec::deserialize(deserializer)?;
+        Self::new(vec).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use typenum::*;
+
+    #[test]
+    fn new() {
+        let vec = vec![42; 4];
+        let list: VariableList<u64, U4> = VariableList::new(vec.clone()).unwrap();
+        assert_eq!(&list[..], &vec[..]);
+
+        assert_eq!(
+            VariableList::<u64, U4>::new(vec![42; 5]),
+            Err(Error::TooLong { len: 5, max_len: 4 })
+        );
+
+        let list: VariableList<u64, U4> = VariableList::new(vec![]).unwrap();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn from_truncates() {
+        let list: VariableList<u64, U4> = VariableList::from(vec![42; 5]);
+        assert_eq!(&list[..], &vec![42; 4][..]);
+
+        let list: VariableList<u64, U4> = VariableList::from(vec![42; 3]);
+        assert_eq!(&list[..], &vec![42; 3][..]);
+    }
+
+    #[test]
+    fn push() {
+        let mut list: VariableList<u64, U2> = VariableList::empty();
+
+        assert_eq!(list.push(1), Ok(()));
+        assert_eq!(list.push(2), Ok(()));
+        assert_eq!(list.push(3), Err(Error::TooLong { len: 3, max_len: 2 }));
+        assert_eq!(&list[..], &[1, 2][..]);
+    }
+
+    #[test]
+    fn indexing() {
+        let mut list: VariableList<u64, U8> = vec![1, 2].into();
+
+        assert_eq!(list[0], 1);
+        assert_eq!(list.get(2), None);
+
+        list[1] = 3;
+        assert_eq!(list[1], 3);
+    }
+}
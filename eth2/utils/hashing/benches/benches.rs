@@ -0,0 +1,45 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::black_box;
+use criterion::{Benchmark, Criterion};
+use hashing::{batch_hash_64_with, hash, Backend, BATCH_INPUT_LEN, BATCH_OUTPUT_LEN};
+
+fn bench_batch(c: &mut Criterion, n: usize) {
+    let input = vec![42; n * BATCH_INPUT_LEN];
+
+    let input_clone = input.clone();
+    c.bench(
+        &format!("{}_inputs", n),
+        Benchmark::new("individual", move |b| {
+            b.iter(|| {
+                for chunk in input_clone.chunks(BATCH_INPUT_LEN) {
+                    black_box(hash(chunk));
+                }
+            })
+        }),
+    );
+
+    for &backend in [Backend::Portable, Backend::ShaNi, Backend::Avx2].iter() {
+        if !backend.is_supported() {
+            continue;
+        }
+
+        let input_clone = input.clone();
+        let mut output = vec![0; n * BATCH_OUTPUT_LEN];
+        c.bench(
+            &format!("{}_inputs", n),
+            Benchmark::new(format!("{:?}", backend), move |b| {
+                b.iter(|| batch_hash_64_with(backend, &input_clone, black_box(&mut output)))
+            }),
+        );
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_batch(c, 8);
+    bench_batch(c, 1 << 16);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,227 @@
+//! Hashes many independent 64-byte inputs in a single call.
+//!
+//! Merkleization hashes the concatenation of two 32-byte nodes for every internal node of a tree,
+//! so hashing a whole layer of a tree at once allows the use of CPU features which are not
+//! available when hashing one buffer at a time:
+//!
+//! - `Backend::ShaNi` uses the x86 SHA extensions.
+//! - `Backend::Avx2` hashes eight inputs at a time, one in each 32-bit lane of the AVX2
+//!   registers.
+//! - `Backend::Portable` hashes each input with `hashing::hash` and is available on every
+//!   platform.
+//!
+//! Since every input is exactly 64 bytes, the second (padding) block of each SHA-256 message is
+//! identical and its message schedule is pre-computed in `PADDING_WK`.
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod portable;
+#[cfg(target_arch = "x86_64")]
+mod sha_ni;
+
+/// The length of each input to the batch hashing functions.
+pub const BATCH_INPUT_LEN: usize = 64;
+/// The length of each output of the batch hashing functions.
+pub const BATCH_OUTPUT_LEN: usize = 32;
+
+/// An implementation of batch hashing.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Backend {
+    Portable,
+    ShaNi,
+    Avx2,
+}
+
+impl Backend {
+    /// Returns the fastest backend supported by the current CPU.
+    pub fn detect() -> Self {
+        if Backend::ShaNi.is_supported() {
+            Backend::ShaNi
+        } else if Backend::Avx2.is_supported() {
+            Backend::Avx2
+        } else {
+            Backend::Portable
+        }
+    }
+
+    /// Returns `true` if the current CPU is able to use `self`.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Backend::Portable => true,
+            #[cfg(target_arch = "x86_64")]
+            Backend::ShaNi => {
+                is_x86_feature_detected!("sha")
+                    && is_x86_feature_detected!("sse2")
+                    && is_x86_feature_detected!("ssse3")
+                    && is_x86_feature_detected!("sse4.1")
+            }
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false,
+        }
+    }
+}
+
+/// Returns the concatenated SHA-256 digests of each consecutive 64-byte input in `input`.
+///
+/// # Panics
+///
+/// If `input.len()` is not a multiple of `BATCH_INPUT_LEN`.
+pub fn batch_hash_64(input: &[u8]) -> Vec<u8> {
+    let mut output = vec![0; input.len() / BATCH_INPUT_LEN * BATCH_OUTPUT_LEN];
+    batch_hash_64_into(input, &mut output);
+    output
+}
+
+/// Writes the SHA-256 digest of each consecutive 64-byte input in `input` to the corresponding
+/// 32-byte slot of `output`, using the fastest backend available.
+///
+/// # Panics
+///
+/// If `input.len()` is not a multiple of `BATCH_INPUT_LEN` or `output` does not have exactly
+/// one `BATCH_OUTPUT_LEN` slot for each input.
+pub fn batch_hash_64_into(input: &[u8], output: &mut [u8]) {
+    batch_hash_64_with(Backend::detect(), input, output)
+}
+
+/// As per `batch_hash_64_into`, but using the given `backend`.
+///
+/// # Panics
+///
+/// As per `batch_hash_64_into`, or if `backend` is not supported by the current CPU.
+pub fn batch_hash_64_with(backend: Backend, input: &[u8], output: &mut [u8]) {
+    assert_eq!(
+        input.len() % BATCH_INPUT_LEN,
+        0,
+        "Batch hash input must be a multiple of {} bytes",
+        BATCH_INPUT_LEN
+    );
+    assert_eq!(
+        output.len(),
+        input.len() / BATCH_INPUT_LEN * BATCH_OUTPUT_LEN,
+        "Batch hash output must have {} bytes for each input",
+        BATCH_OUTPUT_LEN
+    );
+    assert!(
+        backend.is_supported(),
+        "{:?} batch hashing is not supported by this CPU",
+        backend
+    );
+
+    match backend {
+        Backend::Portable => portable::batch_hash_64(input, output),
+        // Safe since the required CPU features were checked above.
+        #[cfg(target_arch = "x86_64")]
+        Backend::ShaNi => unsafe { sha_ni::batch_hash_64(input, output) },
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => unsafe { avx2::batch_hash_64(input, output) },
+        #[cfg(not(target_arch = "x86_64"))]
+        _ => unreachable!("Backend is not supported"),
+    }
+}
+
+/// The initial SHA-256 hash value.
+#[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The SHA-256 round constants.
+#[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// The message schedule of the padding block of a 64-byte message, with the round constants
+/// already added (i.e., `W[t] + K[t]`).
+#[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+const PADDING_WK: [u32; 64] = [
+    0xc28a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf374,
+    0x649b69c1, 0xf0fe4786, 0x0fe1edc6, 0x240cf254, 0x4fe9346f, 0x6cc984be, 0x61b9411e, 0x16f988fa,
+    0xf2c65152, 0xa88e5a6d, 0xb019fc65, 0xb9d99ec7, 0x9a1231c3, 0xe70eeaa0, 0xfdb1232b, 0xc7353eb0,
+    0x3069bad5, 0xcb976d5f, 0x5a0f118f, 0xdc1eeefd, 0x0a35b689, 0xde0b7a04, 0x58f4ca9d, 0xe15d5b16,
+    0x007f3e86, 0x37088980, 0xa507ea32, 0x6fab9537, 0x17406110, 0x0d8cd6f1, 0xcdaa3b6d, 0xc0bbbe37,
+    0x83613bda, 0xdb48a363, 0x0b02e931, 0x6fd15ca7, 0x521afaca, 0x31338431, 0x6ed41a95, 0x6d437890,
+    0xc39c91f2, 0x9eccabbd, 0xb5c9a0e6, 0x532fb63c, 0xd2c741c6, 0x07237ea3, 0xa4954b68, 0x4c191d76,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash;
+
+    /// Returns `n` distinct, deterministic inputs.
+    fn inputs(n: usize) -> Vec<u8> {
+        (0..n * BATCH_INPUT_LEN)
+            .map(|i| {
+                (i as u64)
+                    .wrapping_mul(0x9e37_79b9_7f4a_7c15)
+                    .wrapping_shr(56) as u8
+            })
+            .collect()
+    }
+
+    fn expected(input: &[u8]) -> Vec<u8> {
+        input
+            .chunks(BATCH_INPUT_LEN)
+            .flat_map(|chunk| hash(chunk))
+            .collect()
+    }
+
+    #[test]
+    fn padding_schedule() {
+        let mut w = [0u32; 64];
+        w[0] = 0x8000_0000;
+        w[15] = (BATCH_INPUT_LEN * 8) as u32;
+
+        for t in 16..64 {
+            let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+            let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+            w[t] = w[t - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 7])
+                .wrapping_add(s1);
+        }
+
+        for t in 0..64 {
+            assert_eq!(PADDING_WK[t], w[t].wrapping_add(K[t]), "t = {}", t);
+        }
+    }
+
+    #[test]
+    fn all_backends_match_hash() {
+        let backends = [Backend::Portable, Backend::ShaNi, Backend::Avx2];
+
+        for &backend in backends.iter().filter(|b| b.is_supported()) {
+            // Covers the empty case and batches which are not a multiple of the AVX2 width.
+            for n in 0..20 {
+                let input = inputs(n);
+                let mut output = vec![0; n * BATCH_OUTPUT_LEN];
+                batch_hash_64_with(backend, &input, &mut output);
+
+                assert_eq!(output, expected(&input), "{:?} with {} inputs", backend, n);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_input() {
+        let input = vec![0; BATCH_INPUT_LEN * 3];
+        assert_eq!(batch_hash_64(&input), expected(&input));
+    }
+
+    #[test]
+    #[should_panic]
+    fn partial_input() {
+        batch_hash_64(&[0; BATCH_INPUT_LEN + 1]);
+    }
+}
@@ -0,0 +1,139 @@
+use super::{portable, BATCH_INPUT_LEN, BATCH_OUTPUT_LEN, H0, K, PADDING_WK};
+use std::arch::x86_64::*;
+
+/// The number of inputs hashed at once, one in each 32-bit lane.
+const LANES: usize = 8;
+
+/// Rotates each 32-bit lane of `$x` right by `$n` bits.
+macro_rules! rotr {
+    ($x: expr, $n: expr) => {{
+        let x = $x;
+        _mm256_or_si256(_mm256_srli_epi32(x, $n), _mm256_slli_epi32(x, 32 - $n))
+    }};
+}
+
+/// Hashes the inputs eight at a time, hashing any remaining inputs with the portable backend.
+///
+/// # Safety
+///
+/// The CPU must support the `avx2` feature.
+#[target_feature(enable = "avx2")]
+pub unsafe fn batch_hash_64(input: &[u8], output: &mut [u8]) {
+    let num_batched = input.len() / (BATCH_INPUT_LEN * LANES) * LANES;
+    let (input, input_remainder) = input.split_at(num_batched * BATCH_INPUT_LEN);
+    let (output, output_remainder) = output.split_at_mut(num_batched * BATCH_OUTPUT_LEN);
+
+    for (chunk, out) in input
+        .chunks_exact(BATCH_INPUT_LEN * LANES)
+        .zip(output.chunks_exact_mut(BATCH_OUTPUT_LEN * LANES))
+    {
+        hash_8(chunk, out);
+    }
+
+    portable::batch_hash_64(input_remainder, output_remainder);
+}
+
+/// Hashes eight consecutive 64-byte inputs.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn hash_8(input: &[u8], output: &mut [u8]) {
+    // Reverses the bytes of each 32-bit word.
+    let bswap = _mm256_setr_epi8(
+        3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12, 3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8,
+        15, 14, 13, 12,
+    );
+    // The offset of each input from the first.
+    let offsets = _mm256_setr_epi32(0, 64, 128, 192, 256, 320, 384, 448);
+
+    // Transpose the inputs, such that `w[t]` holds word `t` of each input.
+    let mut w = [_mm256_setzero_si256(); 16];
+    for (t, w) in w.iter_mut().enumerate() {
+        let ptr = input.as_ptr().add(4 * t) as *const i32;
+        *w = _mm256_shuffle_epi8(_mm256_i32gather_epi32(ptr, offsets, 1), bswap);
+    }
+
+    let mut state = [_mm256_setzero_si256(); 8];
+    for (s, h) in state.iter_mut().zip(H0.iter()) {
+        *s = _mm256_set1_epi32(*h as i32);
+    }
+    let initial = state;
+
+    // The first block is the input itself, using a rolling 16-word message schedule.
+    for t in 0..64 {
+        if t >= 16 {
+            let w15 = w[(t - 15) & 15];
+            let w2 = w[(t - 2) & 15];
+            let s0 = _mm256_xor_si256(
+                _mm256_xor_si256(rotr!(w15, 7), rotr!(w15, 18)),
+                _mm256_srli_epi32(w15, 3),
+            );
+            let s1 = _mm256_xor_si256(
+                _mm256_xor_si256(rotr!(w2, 17), rotr!(w2, 19)),
+                _mm256_srli_epi32(w2, 10),
+            );
+            w[t & 15] = _mm256_add_epi32(
+                _mm256_add_epi32(w[t & 15], s0),
+                _mm256_add_epi32(w[(t - 7) & 15], s1),
+            );
+        }
+
+        let wk = _mm256_add_epi32(w[t & 15], _mm256_set1_epi32(K[t] as i32));
+        round(&mut state, wk);
+    }
+
+    for (s, i) in state.iter_mut().zip(initial.iter()) {
+        *s = _mm256_add_epi32(*s, *i);
+    }
+    let first = state;
+
+    // The second block is the padding, which has a pre-computed message schedule.
+    for wk in PADDING_WK.iter() {
+        round(&mut state, _mm256_set1_epi32(*wk as i32));
+    }
+
+    for (s, f) in state.iter_mut().zip(first.iter()) {
+        *s = _mm256_add_epi32(*s, *f);
+    }
+
+    // Transpose the state back and write each digest as big-endian bytes.
+    let mut words = [[0u32; LANES]; 8];
+    for (words, s) in words.iter_mut().zip(state.iter()) {
+        _mm256_storeu_si256(words.as_mut_ptr() as *mut __m256i, *s);
+    }
+
+    for (lane, out) in output.chunks_exact_mut(BATCH_OUTPUT_LEN).enumerate() {
+        for (i, word) in words.iter().enumerate() {
+            out[i * 4..(i + 1) * 4].copy_from_slice(&word[lane].to_be_bytes());
+        }
+    }
+}
+
+/// Performs a single round of SHA-256 on `state` (i.e., `[a, b, c, d, e, f, g, h]`), where `wk`
+/// contains `W[t] + K[t]`.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn round(state: &mut [__m256i; 8], wk: __m256i) {
+    let [a, b, c, d, e, f, g, h] = *state;
+
+    let s1 = _mm256_xor_si256(_mm256_xor_si256(rotr!(e, 6), rotr!(e, 11)), rotr!(e, 25));
+    let ch = _mm256_xor_si256(_mm256_and_si256(e, f), _mm256_andnot_si256(e, g));
+    let t1 = _mm256_add_epi32(_mm256_add_epi32(h, s1), _mm256_add_epi32(ch, wk));
+
+    let s0 = _mm256_xor_si256(_mm256_xor_si256(rotr!(a, 2), rotr!(a, 13)), rotr!(a, 22));
+    let maj = _mm256_xor_si256(
+        _mm256_and_si256(a, b),
+        _mm256_and_si256(c, _mm256_xor_si256(a, b)),
+    );
+    let t2 = _mm256_add_epi32(s0, maj);
+
+    *state = [
+        _mm256_add_epi32(t1, t2),
+        a,
+        b,
+        c,
+        _mm256_add_epi32(d, t1),
+        e,
+        f,
+        g,
+    ];
+}
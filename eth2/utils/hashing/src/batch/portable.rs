@@ -0,0 +1,12 @@
+use super::{BATCH_INPUT_LEN, BATCH_OUTPUT_LEN};
+use crate::hash;
+
+/// Hashes each input individually.
+pub fn batch_hash_64(input: &[u8], output: &mut [u8]) {
+    for (chunk, out) in input
+        .chunks_exact(BATCH_INPUT_LEN)
+        .zip(output.chunks_exact_mut(BATCH_OUTPUT_LEN))
+    {
+        out.copy_from_slice(&hash(chunk));
+    }
+}
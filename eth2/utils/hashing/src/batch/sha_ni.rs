@@ -0,0 +1,95 @@
+use super::{BATCH_INPUT_LEN, BATCH_OUTPUT_LEN, H0, K, PADDING_WK};
+use std::arch::x86_64::*;
+
+/// Performs four rounds of SHA-256, where `$wk` contains `W[t] + K[t]` for each round.
+macro_rules! rounds4 {
+    ($abef: ident, $cdgh: ident, $wk: expr) => {{
+        let wk = $wk;
+        $cdgh = _mm_sha256rnds2_epu32($cdgh, $abef, wk);
+        $abef = _mm_sha256rnds2_epu32($abef, $cdgh, _mm_shuffle_epi32(wk, 0x0E));
+    }};
+}
+
+/// Hashes each input using the SHA extensions.
+///
+/// # Safety
+///
+/// The CPU must support the `sha`, `sse2`, `ssse3` and `sse4.1` features.
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+pub unsafe fn batch_hash_64(input: &[u8], output: &mut [u8]) {
+    // Reverses the bytes of each 32-bit word, converting between SHA-256's big-endian words and
+    // the CPU's little-endian words.
+    let bswap = _mm_set_epi64x(0x0c0d_0e0f_0809_0a0b, 0x0405_0607_0001_0203);
+
+    // Arrange the initial state as the SHA instructions require.
+    let dcba = _mm_loadu_si128(H0.as_ptr() as *const __m128i);
+    let hgfe = _mm_loadu_si128(H0.as_ptr().add(4) as *const __m128i);
+    let cdab = _mm_shuffle_epi32(dcba, 0xB1);
+    let efgh = _mm_shuffle_epi32(hgfe, 0x1B);
+    let initial_abef = _mm_alignr_epi8(cdab, efgh, 8);
+    let initial_cdgh = _mm_blend_epi16(efgh, cdab, 0xF0);
+
+    for (chunk, out) in input
+        .chunks_exact(BATCH_INPUT_LEN)
+        .zip(output.chunks_exact_mut(BATCH_OUTPUT_LEN))
+    {
+        let mut abef = initial_abef;
+        let mut cdgh = initial_cdgh;
+
+        // The first block is the input itself.
+        let ptr = chunk.as_ptr() as *const __m128i;
+        let mut w = [
+            _mm_shuffle_epi8(_mm_loadu_si128(ptr), bswap),
+            _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(1)), bswap),
+            _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(2)), bswap),
+            _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(3)), bswap),
+        ];
+
+        for (i, w) in w.iter().enumerate() {
+            rounds4!(abef, cdgh, _mm_add_epi32(*w, load(&K, i)));
+        }
+
+        for i in 4..16 {
+            let next = _mm_sha256msg2_epu32(
+                _mm_add_epi32(
+                    _mm_sha256msg1_epu32(w[0], w[1]),
+                    _mm_alignr_epi8(w[3], w[2], 4),
+                ),
+                w[3],
+            );
+            rounds4!(abef, cdgh, _mm_add_epi32(next, load(&K, i)));
+            w = [w[1], w[2], w[3], next];
+        }
+
+        abef = _mm_add_epi32(abef, initial_abef);
+        cdgh = _mm_add_epi32(cdgh, initial_cdgh);
+
+        // The second block is the padding, which has a pre-computed message schedule.
+        let first_abef = abef;
+        let first_cdgh = cdgh;
+
+        for i in 0..16 {
+            rounds4!(abef, cdgh, load(&PADDING_WK, i));
+        }
+
+        abef = _mm_add_epi32(abef, first_abef);
+        cdgh = _mm_add_epi32(cdgh, first_cdgh);
+
+        // Convert the state back to `a..h` order and write it out as big-endian bytes.
+        let feba = _mm_shuffle_epi32(abef, 0x1B);
+        let dchg = _mm_shuffle_epi32(cdgh, 0xB1);
+        let dcba = _mm_blend_epi16(feba, dchg, 0xF0);
+        let hgef = _mm_alignr_epi8(dchg, feba, 8);
+
+        let out_ptr = out.as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(out_ptr, _mm_shuffle_epi8(dcba, bswap));
+        _mm_storeu_si128(out_ptr.add(1), _mm_shuffle_epi8(hgef, bswap));
+    }
+}
+
+/// Loads `words[4 * i..4 * i + 4]`.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn load(words: &[u32; 64], i: usize) -> __m128i {
+    _mm_loadu_si128(words.as_ptr().add(4 * i) as *const __m128i)
+}
@@ -1,5 +1,12 @@
 use ring::digest::{digest, SHA256};
 
+mod batch;
+
+pub use batch::{
+    batch_hash_64, batch_hash_64_into, batch_hash_64_with, Backend, BATCH_INPUT_LEN,
+    BATCH_OUTPUT_LEN,
+};
+
 pub fn hash(input: &[u8]) -> Vec<u8> {
     digest(&SHA256, input).as_ref().into()
 }
@@ -1,5 +1,6 @@
 use ethereum_types::H256;
 use hashing::hash;
+use std::collections::{HashMap, HashSet};
 
 /// Verify a proof that `leaf` exists at `index` in a Merkle tree rooted at `root`.
 ///
@@ -40,6 +41,128 @@ fn merkle_root_from_branch(leaf: H256, branch: &[H256], depth: usize, index: usi
     H256::from_slice(&merkle_root)
 }
 
+/// Verify a proof that each of `leaves` exists at the corresponding generalized index of
+/// `gindices` in a Merkle tree rooted at `root`.
+///
+/// The `proof` argument should contain the nodes at `get_helper_indices(gindices)`, in that
+/// (descending) order.
+pub fn verify_merkle_multiproof(
+    leaves: &[H256],
+    proof: &[H256],
+    gindices: &[usize],
+    root: H256,
+) -> bool {
+    merkle_root_from_multiproof(leaves, proof, gindices) == Some(root)
+}
+
+/// Compute a root hash from some leaves and a Merkle multiproof, returning `None` if the proof is
+/// malformed.
+fn merkle_root_from_multiproof(
+    leaves: &[H256],
+    proof: &[H256],
+    gindices: &[usize],
+) -> Option<H256> {
+    if leaves.len() != gindices.len() || gindices.contains(&0) {
+        return None;
+    }
+
+    let helper_indices = get_helper_indices(gindices);
+    if proof.len() != helper_indices.len() {
+        return None;
+    }
+
+    let mut nodes: HashMap<usize, H256> = gindices
+        .iter()
+        .cloned()
+        .zip(leaves.iter().cloned())
+        .chain(helper_indices.into_iter().zip(proof.iter().cloned()))
+        .collect();
+
+    // Hash sibling pairs into their parents, from the deepest nodes upwards.
+    let mut keys: Vec<usize> = nodes.keys().cloned().collect();
+    keys.sort_by(|a, b| b.cmp(a));
+
+    let mut pos = 0;
+    while pos < keys.len() {
+        let k = keys[pos];
+
+        if k > 1 && !nodes.contains_key(&(k / 2)) {
+            if let (Some(left), Some(right)) = (nodes.get(&(k & !1)), nodes.get(&(k | 1))) {
+                let mut input = left.as_bytes().to_vec();
+                input.extend_from_slice(right.as_bytes());
+
+                nodes.insert(k / 2, H256::from_slice(&hash(&input)));
+                keys.push(k / 2);
+            }
+        }
+
+        pos += 1;
+    }
+
+    nodes.get(&1).cloned()
+}
+
+/// Returns the depth of the node at generalized index `gindex`, where the root (`gindex == 1`)
+/// has a depth of `0`.
+///
+/// The node is at `index = gindex - 2^depth` within its layer of the tree.
+///
+/// Returns `None` if `gindex == 0`, which is not the index of any node.
+pub fn generalized_index_depth(gindex: usize) -> Option<usize> {
+    if gindex == 0 {
+        None
+    } else {
+        Some((std::mem::size_of::<usize>() * 8) - 1 - gindex.leading_zeros() as usize)
+    }
+}
+
+/// Returns the generalized indices of the nodes required to prove the node at `gindex`, i.e., the
+/// siblings of each node on the path from `gindex` to the root, in bottom-up order.
+pub fn get_branch_indices(gindex: usize) -> Vec<usize> {
+    let mut indices = vec![];
+    let mut i = gindex;
+
+    while i > 1 {
+        indices.push(i ^ 1);
+        i /= 2;
+    }
+
+    indices
+}
+
+/// Returns the generalized indices of the nodes on the path from `gindex` to the root (exclusive
+/// of the root), in bottom-up order.
+pub fn get_path_indices(gindex: usize) -> Vec<usize> {
+    let mut indices = vec![];
+    let mut i = gindex;
+
+    while i > 1 {
+        indices.push(i);
+        i /= 2;
+    }
+
+    indices
+}
+
+/// Returns the generalized indices of the nodes required to prove all of the nodes at `gindices`
+/// at once, in descending order.
+///
+/// Nodes that can be computed from the other nodes of the proof are omitted.
+pub fn get_helper_indices(gindices: &[usize]) -> Vec<usize> {
+    let mut branch_indices = HashSet::new();
+    let mut path_indices = HashSet::new();
+
+    for &gindex in gindices {
+        branch_indices.extend(get_branch_indices(gindex));
+        path_indices.extend(get_path_indices(gindex));
+    }
+
+    let mut helpers: Vec<usize> = branch_indices.difference(&path_indices).cloned().collect();
+    helpers.sort_by(|a, b| b.cmp(a));
+
+    helpers
+}
+
 /// Concatenate two vectors.
 fn concat(mut vec1: Vec<u8>, mut vec2: Vec<u8>) -> Vec<u8> {
     vec1.append(&mut vec2);
@@ -138,6 +261,91 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn generalized_indices() {
+        assert_eq!(generalized_index_depth(0), None);
+        assert_eq!(generalized_index_depth(1), Some(0));
+        assert_eq!(generalized_index_depth(2), Some(1));
+        assert_eq!(generalized_index_depth(3), Some(1));
+        assert_eq!(generalized_index_depth(4), Some(2));
+        assert_eq!(generalized_index_depth(7), Some(2));
+
+        assert_eq!(get_branch_indices(1), Vec::<usize>::new());
+        assert_eq!(get_branch_indices(0b110), vec![0b111, 0b10]);
+        assert_eq!(get_path_indices(0b110), vec![0b110, 0b11]);
+
+        assert_eq!(get_helper_indices(&[0b100, 0b101]), vec![0b11]);
+        assert_eq!(get_helper_indices(&[0b100, 0b110]), vec![0b111, 0b101]);
+    }
+
+    #[test]
+    fn verify_multiproof() {
+        let leaf_b00 = H256::from([0xAA; 32]);
+        let leaf_b01 = H256::from([0xBB; 32]);
+        let leaf_b10 = H256::from([0xCC; 32]);
+        let leaf_b11 = H256::from([0xDD; 32]);
+
+        let node_b0x = hash_concat(leaf_b00, leaf_b01);
+        let node_b1x = hash_concat(leaf_b10, leaf_b11);
+
+        let root = hash_concat(node_b0x, node_b1x);
+
+        // Leaves `0b00` and `0b10` (generalized indices `4` and `6`).
+        assert!(verify_merkle_multiproof(
+            &[leaf_b00, leaf_b10],
+            &[leaf_b11, leaf_b01],
+            &[4, 6],
+            root
+        ));
+        // Sibling leaves require only the other half of the tree.
+        assert!(verify_merkle_multiproof(
+            &[leaf_b00, leaf_b01],
+            &[node_b1x],
+            &[4, 5],
+            root
+        ));
+        // Leaves at different depths.
+        assert!(verify_merkle_multiproof(
+            &[leaf_b00, node_b1x],
+            &[leaf_b01],
+            &[4, 3],
+            root
+        ));
+        // A single leaf is equivalent to a regular proof.
+        assert!(verify_merkle_multiproof(
+            &[leaf_b01],
+            &[leaf_b00, node_b1x],
+            &[5],
+            root
+        ));
+
+        // Helpers in the wrong order.
+        assert!(!verify_merkle_multiproof(
+            &[leaf_b00, leaf_b10],
+            &[leaf_b01, leaf_b11],
+            &[4, 6],
+            root
+        ));
+        // Missing helper.
+        assert!(!verify_merkle_multiproof(
+            &[leaf_b00, leaf_b10],
+            &[leaf_b11],
+            &[4, 6],
+            root
+        ));
+        // Wrong indices.
+        assert!(!verify_merkle_multiproof(
+            &[leaf_b00, leaf_b10],
+            &[leaf_b11, leaf_b01],
+            &[5, 6],
+            root
+        ));
+        // Mismatched leaves and indices.
+        assert!(!verify_merkle_multiproof(&[leaf_b00], &[], &[4, 6], root));
+        // Generalized index of zero.
+        assert!(!verify_merkle_multiproof(&[root], &[], &[0], root));
+    }
+
     #[test]
     fn verify_zero_depth() {
         let leaf = H256::from([0xD6; 32]);
@@ -0,0 +1,29 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate ssz;
+extern crate ssz_derive;
+
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Container {
+    a: u16,
+    b: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Union {
+    Empty,
+    Fixed(u64),
+    Variable(Vec<u16>),
+    Container(Container),
+}
+
+// Fuzz Union::from_ssz_bytes()
+fuzz_target!(|data: &[u8]| {
+    if let Ok(union) = Union::from_ssz_bytes(data) {
+        // Any successfully decoded union must re-encode to the same bytes.
+        assert_eq!(union.as_ssz_bytes(), data);
+    }
+});
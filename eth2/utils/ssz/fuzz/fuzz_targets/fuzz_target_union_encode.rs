@@ -0,0 +1,27 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate ssz;
+extern crate ssz_derive;
+
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Union {
+    Empty,
+    Fixed(u8),
+    Variable(Vec<u8>),
+}
+
+// Fuzz Union::as_ssz_bytes()
+fuzz_target!(|data: &[u8]| {
+    let union = match data.split_first() {
+        None => Union::Empty,
+        Some((&selector, rest)) if selector % 2 == 0 => Union::Variable(rest.to_vec()),
+        Some((&byte, _)) => Union::Fixed(byte),
+    };
+
+    let bytes = union.as_ssz_bytes();
+
+    assert_eq!(Union::from_ssz_bytes(&bytes), Ok(union));
+});
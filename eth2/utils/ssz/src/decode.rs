@@ -1,6 +1,8 @@
 use super::*;
 
 pub mod impls;
+pub mod stream;
+pub mod view;
 
 /// Returned when SSZ decoding fails.
 #[derive(Debug, PartialEq)]
@@ -20,6 +22,10 @@ pub enum DecodeError {
     OutOfBoundsByte { i: usize },
     /// The given bytes were invalid for some application-level reason.
     BytesInvalid(String),
+    /// The selector byte of a union did not match any of its variants.
+    UnionSelectorInvalid(u8),
+    /// An error occurred whilst reading bytes from a `std::io::Read`.
+    ReadError(String),
 }
 
 /// Provides SSZ decoding (de-serialization) via the `from_ssz_bytes(&bytes)` method.
@@ -46,6 +52,16 @@ pub trait Decode: Sized {
     /// The supplied bytes must be the exact length required to decode `Self`, excess bytes will
     /// result in an error.
     fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, DecodeError>;
+
+    /// Attempts to decode `Self` from the next `len` bytes of `reader`, returning a `DecodeError`
+    /// on failure.
+    ///
+    /// The default implementation reads all `len` bytes into memory and calls `from_ssz_bytes`.
+    /// Large objects should override this method to avoid holding their entire encoding in
+    /// memory, see `SszStreamDecoder`.
+    fn from_ssz_reader<R: std::io::Read>(reader: &mut R, len: usize) -> Result<Self, DecodeError> {
+        Self::from_ssz_bytes(&stream::read_bytes(reader, len)?)
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -225,9 +225,13 @@ impl<T: Decode> Decode for Vec<T> {
                 .map(|chunk| T::from_ssz_bytes(chunk))
                 .collect()
         } else {
-            decode_list_of_variable_length_items(bytes)
+            decode_list_of_variable_length_items(bytes, None)
         }
     }
+
+    fn from_ssz_reader<R: std::io::Read>(reader: &mut R, len: usize) -> Result<Self, DecodeError> {
+        stream::decode_list_from_reader(reader, len, None)
+    }
 }
 
 /// Decodes `bytes` as if it were a list of variable-length items.
@@ -235,8 +239,12 @@ impl<T: Decode> Decode for Vec<T> {
 /// The `ssz::SszDecoder` can also perform this functionality, however it it significantly faster
 /// as it is optimized to read same-typed items whilst `ssz::SszDecoder` supports reading items of
 /// differing types.
+///
+/// If `max_len` is `Some`, an error is returned (before any items are decoded) if the list
+/// contains more than `max_len` items.
 pub fn decode_list_of_variable_length_items<T: Decode>(
     bytes: &[u8],
+    max_len: Option<usize>,
 ) -> Result<Vec<T>, DecodeError> {
     let mut next_variable_byte = read_offset(bytes)?;
 
@@ -258,6 +266,15 @@ pub fn decode_list_of_variable_length_items<T: Decode>(
         });
     }
 
+    if let Some(max_len) = max_len {
+        if num_items > max_len {
+            return Err(DecodeError::BytesInvalid(format!(
+                "List of {} items exceeds maximum of {}",
+                num_items, max_len
+            )));
+        }
+    }
+
     let mut values = Vec::with_capacity(num_items);
     for i in 1..=num_items {
         let slice_option = if i == num_items {
@@ -382,6 +399,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn list_exceeds_max_len() {
+        let bytes = vec![vec![1_u16], vec![2], vec![3]].as_ssz_bytes();
+
+        assert_eq!(
+            decode_list_of_variable_length_items::<Vec<u16>>(&bytes, Some(3)),
+            Ok(vec![vec![1], vec![2], vec![3]])
+        );
+
+        if let Err(DecodeError::BytesInvalid(_)) =
+            decode_list_of_variable_length_items::<Vec<u16>>(&bytes, Some(2))
+        {
+            // Success.
+        } else {
+            panic!("Did not return error on list exceeding max_len")
+        }
+    }
+
     #[test]
     fn awkward_fixed_lenth_portion() {
         assert_eq!(
@@ -0,0 +1,400 @@
+//! Decoding of SSZ objects from a `std::io::Read`.
+//!
+//! Allows for decoding objects (e.g., a `BeaconState`) without first reading the entirety of
+//! their SSZ bytes into memory.
+use super::*;
+use std::io::Read;
+
+/// The maximum number of bytes read from a reader in a single call when decoding a list of
+/// fixed-length items.
+const MAX_CHUNK_BYTES: usize = 64 * 1024;
+
+impl From<std::io::Error> for DecodeError {
+    fn from(e: std::io::Error) -> DecodeError {
+        DecodeError::ReadError(format!("{:?}", e))
+    }
+}
+
+/// Reads exactly `len` bytes from `reader`.
+///
+/// Returns an `InvalidByteLength` error if `reader` is exhausted before `len` bytes are read.
+pub fn read_bytes<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, DecodeError> {
+    let mut bytes = Vec::with_capacity(len);
+
+    reader.by_ref().take(len as u64).read_to_end(&mut bytes)?;
+
+    if bytes.len() != len {
+        Err(DecodeError::InvalidByteLength {
+            len: bytes.len(),
+            expected: len,
+        })
+    } else {
+        Ok(bytes)
+    }
+}
+
+/// Decodes `len` bytes from `reader` as if they were a SSZ list.
+///
+/// Equivalent to `Vec::<T>::from_ssz_bytes` (or `decode_list_of_variable_length_items`) on the
+/// same bytes, except that only the bytes of a single item (or chunk of fixed-length items) are
+/// held in memory at any one time.
+///
+/// If `max_len` is `Some`, an error is returned (before any items are decoded) if the list
+/// contains more than `max_len` items.
+pub fn decode_list_from_reader<T: Decode, R: Read>(
+    reader: &mut R,
+    len: usize,
+    max_len: Option<usize>,
+) -> Result<Vec<T>, DecodeError> {
+    if len == 0 {
+        return Ok(vec![]);
+    }
+
+    let (num_items, offsets) = if T::is_ssz_fixed_len() {
+        let item_len = T::ssz_fixed_len();
+
+        if len % item_len != 0 {
+            return Err(DecodeError::InvalidByteLength {
+                len: len % item_len,
+                expected: item_len,
+            });
+        }
+
+        let num_items = len / item_len;
+
+        if let Some(max_len) = max_len {
+            if num_items > max_len {
+                return Err(DecodeError::BytesInvalid(format!(
+                    "List of {} items exceeds maximum of {}",
+                    num_items, max_len
+                )));
+            }
+        }
+
+        (num_items, vec![])
+    } else {
+        let first_offset = read_offset(&read_bytes(reader, BYTES_PER_LENGTH_OFFSET)?)?;
+
+        // The first offset must point past at least one offset and must align with the end of
+        // an offset.
+        if first_offset < BYTES_PER_LENGTH_OFFSET
+            || first_offset > len
+            || first_offset % BYTES_PER_LENGTH_OFFSET != 0
+        {
+            return Err(DecodeError::OutOfBoundsByte { i: first_offset });
+        }
+
+        let num_items = first_offset / BYTES_PER_LENGTH_OFFSET;
+
+        if let Some(max_len) = max_len {
+            if num_items > max_len {
+                return Err(DecodeError::BytesInvalid(format!(
+                    "List of {} items exceeds maximum of {}",
+                    num_items, max_len
+                )));
+            }
+        }
+
+        let remaining_offsets = read_bytes(reader, first_offset - BYTES_PER_LENGTH_OFFSET)?;
+
+        let mut offsets = Vec::with_capacity(num_items + 1);
+        offsets.push(first_offset);
+
+        for bytes in remaining_offsets.chunks(BYTES_PER_LENGTH_OFFSET) {
+            let offset = decode_offset(bytes)?;
+
+            if offset < *offsets.last().expect("offsets is not empty") || offset > len {
+                return Err(DecodeError::OutOfBoundsByte { i: offset });
+            }
+
+            offsets.push(offset)
+        }
+
+        // The last item extends to the end of the list.
+        offsets.push(len);
+
+        (num_items, offsets)
+    };
+
+    let mut values = Vec::with_capacity(num_items);
+
+    if T::is_ssz_fixed_len() {
+        let item_len = T::ssz_fixed_len();
+        let items_per_chunk = std::cmp::max(MAX_CHUNK_BYTES / item_len, 1);
+
+        while values.len() < num_items {
+            let chunk_items = std::cmp::min(items_per_chunk, num_items - values.len());
+            let chunk = read_bytes(reader, chunk_items * item_len)?;
+
+            for item_bytes in chunk.chunks(item_len) {
+                values.push(T::from_ssz_bytes(item_bytes)?);
+            }
+        }
+    } else {
+        for pair in offsets.windows(2) {
+            values.push(T::from_ssz_reader(reader, pair[1] - pair[0])?);
+        }
+    }
+
+    Ok(values)
+}
+
+/// The location of an item registered with a `SszStreamDecoderBuilder`.
+#[derive(Copy, Clone, Debug)]
+enum StreamItem {
+    /// A fixed-length item at the given range of the fixed-length bytes.
+    Fixed { start: usize, end: usize },
+    /// A variable-length item of `len` bytes, yet to be read from the reader.
+    Variable { len: usize },
+}
+
+/// Builds an `SszStreamDecoder`.
+///
+/// Reads the fixed-length portion of some SSZ bytes from a reader and validates the offsets it
+/// contains, without reading any of the variable-length portion. The builder is then converted
+/// into a `SszStreamDecoder` which decodes the items into object instances.
+///
+/// See [`SszStreamDecoder`](struct.SszStreamDecoder.html) for usage examples.
+pub struct SszStreamDecoderBuilder<'a, R> {
+    reader: &'a mut R,
+    len: usize,
+    fixed_bytes: Vec<u8>,
+    items: Vec<StreamItem>,
+    offsets: Vec<Offset>,
+    items_index: usize,
+}
+
+impl<'a, R: Read> SszStreamDecoderBuilder<'a, R> {
+    /// Instantiate a new builder that should build a `SszStreamDecoder` over the next `len` bytes
+    /// of `reader`, which are assumed to be the SSZ encoding of some object.
+    ///
+    /// Reads `num_fixed_bytes` (the sum of the `ssz_fixed_len()` of all items which will be
+    /// registered) from `reader`.
+    pub fn new(reader: &'a mut R, len: usize, num_fixed_bytes: usize) -> Result<Self, DecodeError> {
+        if num_fixed_bytes > len {
+            return Err(DecodeError::InvalidByteLength {
+                len,
+                expected: num_fixed_bytes,
+            });
+        }
+
+        let fixed_bytes = read_bytes(reader, num_fixed_bytes)?;
+
+        Ok(Self {
+            reader,
+            len,
+            fixed_bytes,
+            items: vec![],
+            offsets: vec![],
+            items_index: 0,
+        })
+    }
+
+    /// Declares that some type `T` is the next item in the bytes.
+    pub fn register_type<T: Decode>(&mut self) -> Result<(), DecodeError> {
+        if T::is_ssz_fixed_len() {
+            let start = self.items_index;
+            self.items_index += T::ssz_fixed_len();
+
+            if self.items_index > self.fixed_bytes.len() {
+                return Err(DecodeError::InvalidByteLength {
+                    len: self.fixed_bytes.len(),
+                    expected: self.items_index,
+                });
+            }
+
+            self.items.push(StreamItem::Fixed {
+                start,
+                end: self.items_index,
+            });
+        } else {
+            let offset = read_offset(self.fixed_bytes.get(self.items_index..).unwrap_or(&[]))?;
+
+            let previous_offset = self
+                .offsets
+                .last()
+                .map(|o| o.offset)
+                .unwrap_or_else(|| BYTES_PER_LENGTH_OFFSET);
+
+            if (previous_offset > offset) || (offset > self.len) {
+                return Err(DecodeError::OutOfBoundsByte { i: offset });
+            }
+
+            self.offsets.push(Offset {
+                position: self.items.len(),
+                offset,
+            });
+
+            // Push an empty item; its length will be set later.
+            self.items.push(StreamItem::Variable { len: 0 });
+
+            self.items_index += BYTES_PER_LENGTH_OFFSET;
+        }
+
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<(), DecodeError> {
+        // Ensure the registered items consumed exactly the fixed-length bytes that were read.
+        if self.items_index != self.fixed_bytes.len() {
+            return Err(DecodeError::InvalidByteLength {
+                len: self.fixed_bytes.len(),
+                expected: self.items_index,
+            });
+        }
+
+        if !self.offsets.is_empty() {
+            // Check to ensure the first offset points to the byte immediately following the
+            // fixed-length bytes.
+            if self.offsets[0].offset != self.items_index {
+                return Err(DecodeError::OutOfBoundsByte {
+                    i: self.offsets[0].offset,
+                });
+            }
+
+            // Each variable-length item extends to the following offset, or the end of the
+            // bytes for the last item.
+            let ends = self
+                .offsets
+                .iter()
+                .skip(1)
+                .map(|o| o.offset)
+                .chain(std::iter::once(self.len));
+
+            for (offset, end) in self.offsets.iter().zip(ends) {
+                self.items[offset.position] = StreamItem::Variable {
+                    len: end - offset.offset,
+                };
+            }
+        } else if self.items_index != self.len {
+            // If the container is fixed-length, ensure there are no excess bytes.
+            return Err(DecodeError::InvalidByteLength {
+                len: self.len,
+                expected: self.items_index,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Finalizes the builder, returning a `SszStreamDecoder` that may be used to instantiate
+    /// objects.
+    pub fn build(mut self) -> Result<SszStreamDecoder<'a, R>, DecodeError> {
+        self.finalize()?;
+
+        Ok(SszStreamDecoder {
+            reader: self.reader,
+            fixed_bytes: self.fixed_bytes,
+            items: self.items,
+            next: 0,
+        })
+    }
+}
+
+/// Decodes SSZ read from a `std::io::Read` into object instances. Should be instantiated using
+/// [`SszStreamDecoderBuilder`](struct.SszStreamDecoderBuilder.html).
+///
+/// Variable-length items are read from the reader as they are decoded, therefore all items
+/// must be decoded (in order) to consume all of the bytes of the object.
+///
+/// ## Example
+///
+/// ```rust
+/// use ssz_derive::{Encode, Decode};
+/// use ssz::{Decode, Encode, SszStreamDecoder, SszStreamDecoderBuilder};
+///
+/// #[derive(PartialEq, Debug, Encode, Decode)]
+/// struct Foo {
+///     a: u64,
+///     b: Vec<u16>,
+/// }
+///
+/// fn main() {
+///     let foo = Foo {
+///         a: 42,
+///         b: vec![1, 3, 3, 7]
+///     };
+///
+///     let bytes = foo.as_ssz_bytes();
+///     let mut reader = &bytes[..];
+///     let num_fixed_bytes = <u64 as Decode>::ssz_fixed_len() + <Vec<u16> as Decode>::ssz_fixed_len();
+///
+///     let mut builder = SszStreamDecoderBuilder::new(&mut reader, bytes.len(), num_fixed_bytes).unwrap();
+///
+///     builder.register_type::<u64>().unwrap();
+///     builder.register_type::<Vec<u16>>().unwrap();
+///
+///     let mut decoder = builder.build().unwrap();
+///
+///     let decoded_foo = Foo {
+///         a: decoder.decode_next().unwrap(),
+///         b: decoder.decode_next().unwrap(),
+///     };
+///
+///     assert_eq!(foo, decoded_foo);
+/// }
+///
+/// ```
+pub struct SszStreamDecoder<'a, R> {
+    reader: &'a mut R,
+    fixed_bytes: Vec<u8>,
+    items: Vec<StreamItem>,
+    next: usize,
+}
+
+impl<'a, R: Read> SszStreamDecoder<'a, R> {
+    /// Decodes the next item.
+    ///
+    /// # Panics
+    ///
+    /// Panics when attempting to decode more items than actually exist.
+    pub fn decode_next<T: Decode>(&mut self) -> Result<T, DecodeError> {
+        let item = self.items[self.next];
+        self.next += 1;
+
+        match item {
+            StreamItem::Fixed { start, end } => T::from_ssz_bytes(&self.fixed_bytes[start..end]),
+            StreamItem::Variable { len } => T::from_ssz_reader(self.reader, len),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_bytes_too_short() {
+        assert_eq!(
+            read_bytes(&mut &[1, 2, 3][..], 4),
+            Err(DecodeError::InvalidByteLength {
+                len: 3,
+                expected: 4
+            })
+        );
+    }
+
+    #[test]
+    fn list_offsets_not_aligned() {
+        // A first offset of 6 does not describe a whole number of offsets.
+        let bytes = vec![6, 0, 0, 0, 6, 0, 0, 0];
+
+        assert_eq!(
+            decode_list_from_reader::<Vec<u8>, _>(&mut &bytes[..], bytes.len(), None),
+            Err(DecodeError::OutOfBoundsByte { i: 6 })
+        );
+    }
+
+    #[test]
+    fn list_exceeds_max_len() {
+        let bytes = vec![1_u16, 2, 3].as_ssz_bytes();
+
+        if let Err(DecodeError::BytesInvalid(_)) =
+            decode_list_from_reader::<u16, _>(&mut &bytes[..], bytes.len(), Some(2))
+        {
+            // Pass.
+        } else {
+            panic!("Did not return error on list exceeding max_len")
+        }
+    }
+}
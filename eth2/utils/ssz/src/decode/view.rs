@@ -0,0 +1,101 @@
+use super::*;
+
+/// Provides access to the individual fields of some SSZ-encoded container, without decoding the
+/// entire container.
+///
+/// Fields are identified by their index in the container. Only the length of the fixed-length
+/// portion of the bytes is checked when the view is created; any other invalidity is only
+/// detected when the affected field is read.
+///
+/// Typically used via the `FooView` types generated by `#[derive(DecodeView)]`.
+///
+/// ## Example
+///
+/// ```rust
+/// use ssz::ContainerView;
+///
+/// // A container of `(u16, Vec<u8>, u8)`.
+/// let bytes = vec![1, 0, 7, 0, 0, 0, 3, 42, 43];
+///
+/// let view = ContainerView::new(&bytes, &[Some(2), None, Some(1)]).unwrap();
+///
+/// assert_eq!(view.decode_field::<u16>(0), Ok(1));
+/// assert_eq!(view.field_bytes(1), Ok(&[42, 43][..]));
+/// assert_eq!(view.decode_field::<u8>(2), Ok(3));
+/// ```
+pub struct ContainerView<'a> {
+    bytes: &'a [u8],
+    /// The position of each field in the fixed-length portion of `bytes`, and its length if it is
+    /// fixed-length.
+    fields: Vec<(usize, Option<usize>)>,
+    /// The length of the fixed-length portion of `bytes`.
+    fixed_bytes_len: usize,
+}
+
+impl<'a> ContainerView<'a> {
+    /// Instantiate a view over `bytes`, which are assumed to be the SSZ encoding of some
+    /// container.
+    ///
+    /// `fixed_lens` must contain one item for each field in the container, in the order they are
+    /// encoded. The item is `Some(T::ssz_fixed_len())` for a fixed-length field or `None` for a
+    /// variable-length field.
+    pub fn new(bytes: &'a [u8], fixed_lens: &[Option<usize>]) -> Result<Self, DecodeError> {
+        let mut fields = Vec::with_capacity(fixed_lens.len());
+        let mut position = 0;
+
+        for fixed_len in fixed_lens {
+            fields.push((position, *fixed_len));
+            position += fixed_len.unwrap_or(BYTES_PER_LENGTH_OFFSET);
+        }
+
+        if bytes.len() < position {
+            return Err(DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected: position,
+            });
+        }
+
+        Ok(Self {
+            bytes,
+            fields,
+            fixed_bytes_len: position,
+        })
+    }
+
+    /// Returns the SSZ bytes of the field at `index`.
+    pub fn field_bytes(&self, index: usize) -> Result<&'a [u8], DecodeError> {
+        let (position, fixed_len) = *self.fields.get(index).ok_or_else(|| {
+            DecodeError::BytesInvalid(format!("Container has no field at index {}", index))
+        })?;
+
+        match fixed_len {
+            Some(len) => Ok(&self.bytes[position..position + len]),
+            None => {
+                let start = read_offset(&self.bytes[position..])?;
+
+                // A variable-length field ends where the next one starts, or at the end of the
+                // bytes if it is the last.
+                let end = match self.fields[index + 1..]
+                    .iter()
+                    .find(|(_, fixed_len)| fixed_len.is_none())
+                {
+                    Some((next_position, _)) => read_offset(&self.bytes[*next_position..])?,
+                    None => self.bytes.len(),
+                };
+
+                if start < self.fixed_bytes_len {
+                    return Err(DecodeError::OutOfBoundsByte { i: start });
+                }
+
+                self.bytes
+                    .get(start..end)
+                    .ok_or_else(|| DecodeError::OutOfBoundsByte { i: start })
+            }
+        }
+    }
+
+    /// Decodes the field at `index` as some type `T`.
+    pub fn decode_field<T: Decode>(&self, index: usize) -> Result<T, DecodeError> {
+        T::from_ssz_bytes(self.field_bytes(index)?)
+    }
+}
@@ -1,6 +1,7 @@
 use super::*;
+use std::io::{self, Write};
 
-mod impls;
+pub mod impls;
 
 /// Provides SSZ encoding (serialization) via the `as_ssz_bytes(&self)` method.
 ///
@@ -37,6 +38,27 @@ pub trait Encode {
 
         buf
     }
+
+    /// Returns the length of the full-form encoding of this object, in bytes.
+    ///
+    /// The default implementation is only efficient for fixed-length objects, variable-length
+    /// objects are encoded to determine their length. Large variable-length objects should
+    /// override this method.
+    fn ssz_bytes_len(&self) -> usize {
+        if <Self as Encode>::is_ssz_fixed_len() {
+            <Self as Encode>::ssz_fixed_len()
+        } else {
+            self.as_ssz_bytes().len()
+        }
+    }
+
+    /// Writes the full-form encoding of this object to `writer`.
+    ///
+    /// The default implementation writes `self.as_ssz_bytes()`. Large objects should override
+    /// this method to avoid holding their entire encoding in memory, see `SszStreamEncoder`.
+    fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.as_ssz_bytes())
+    }
 }
 
 /// Allow for encoding an ordered series of distinct or indistinct objects as SSZ bytes.
@@ -126,6 +148,94 @@ impl<'a> SszEncoder<'a> {
     }
 }
 
+/// Allow for writing an ordered series of distinct or indistinct objects as SSZ bytes to some
+/// `std::io::Write`, without holding the entire encoding in memory.
+///
+/// Unlike `SszEncoder`, each item must be supplied twice: first to `write_fixed_part(..)`, then
+/// (once all items have been supplied to `write_fixed_part(..)`) to `write_variable_part(..)`.
+/// The offsets of variable-length items are computed up-front using `Encode::ssz_bytes_len`.
+///
+/// ## Example
+///
+/// Use `SszStreamEncoder` to produce identical output to `foo.as_ssz_bytes()`:
+///
+/// ```rust
+/// use ssz_derive::{Encode, Decode};
+/// use ssz::{Decode, Encode, SszStreamEncoder};
+///
+/// #[derive(PartialEq, Debug, Encode, Decode)]
+/// struct Foo {
+///     a: u64,
+///     b: Vec<u16>,
+/// }
+///
+/// fn main() {
+///     let foo = Foo {
+///         a: 42,
+///         b: vec![1, 3, 3, 7]
+///     };
+///
+///     let mut buf: Vec<u8> = vec![];
+///     let offset = <u64 as Encode>::ssz_fixed_len() + <Vec<u16> as Encode>::ssz_fixed_len();
+///
+///     let mut encoder = SszStreamEncoder::container(&mut buf, offset);
+///
+///     encoder.write_fixed_part(&foo.a).unwrap();
+///     encoder.write_fixed_part(&foo.b).unwrap();
+///
+///     encoder.write_variable_part(&foo.a).unwrap();
+///     encoder.write_variable_part(&foo.b).unwrap();
+///
+///     assert_eq!(foo.as_ssz_bytes(), buf);
+/// }
+///
+/// ```
+pub struct SszStreamEncoder<'a, W> {
+    offset: usize,
+    writer: &'a mut W,
+}
+
+impl<'a, W: Write> SszStreamEncoder<'a, W> {
+    /// Instantiate a new encoder for writing a SSZ list.
+    ///
+    /// Identical to `Self::container`.
+    pub fn list(writer: &'a mut W, num_fixed_bytes: usize) -> Self {
+        Self::container(writer, num_fixed_bytes)
+    }
+
+    /// Instantiate a new encoder for writing a SSZ container.
+    pub fn container(writer: &'a mut W, num_fixed_bytes: usize) -> Self {
+        Self {
+            offset: num_fixed_bytes,
+            writer,
+        }
+    }
+
+    /// Writes `item` if it is fixed-length, otherwise writes the offset to its variable-length
+    /// bytes.
+    pub fn write_fixed_part<T: Encode>(&mut self, item: &T) -> io::Result<()> {
+        if T::is_ssz_fixed_len() {
+            item.ssz_write(self.writer)
+        } else {
+            self.writer.write_all(&encode_length(self.offset))?;
+            self.offset += item.ssz_bytes_len();
+
+            Ok(())
+        }
+    }
+
+    /// Writes `item` if it is variable-length, otherwise does nothing.
+    ///
+    /// Must only be called once all items have been supplied to `write_fixed_part(..)`.
+    pub fn write_variable_part<T: Encode>(&mut self, item: &T) -> io::Result<()> {
+        if T::is_ssz_fixed_len() {
+            Ok(())
+        } else {
+            item.ssz_write(self.writer)
+        }
+    }
+}
+
 /// Encode `index` as a little-endian byte vec of `BYTES_PER_LENGTH_OFFSET` length.
 ///
 /// If `len` is larger than `2 ^ BYTES_PER_LENGTH_OFFSET`, a `debug_assert` is raised.
@@ -2,6 +2,10 @@ use super::*;
 use core::num::NonZeroUsize;
 use ethereum_types::{H256, U128, U256};
 
+/// The maximum number of bytes buffered before writing when encoding a list of fixed-length items
+/// to a writer.
+const MAX_CHUNK_BYTES: usize = 64 * 1024;
+
 macro_rules! impl_encodable_for_uint {
     ($type: ident, $bit_size: expr) => {
         impl Encode for $type {
@@ -16,6 +20,10 @@ macro_rules! impl_encodable_for_uint {
             fn ssz_append(&self, buf: &mut Vec<u8>) {
                 buf.extend_from_slice(&self.to_le_bytes());
             }
+
+            fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                writer.write_all(&self.to_le_bytes())
+            }
         }
     };
 }
@@ -41,6 +49,23 @@ impl<T: Encode> Encode for Option<T> {
             }
         }
     }
+
+    fn ssz_bytes_len(&self) -> usize {
+        match self {
+            None => BYTES_PER_LENGTH_OFFSET,
+            Some(t) => BYTES_PER_LENGTH_OFFSET + t.ssz_bytes_len(),
+        }
+    }
+
+    fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        match self {
+            None => writer.write_all(&encode_union_index(0)),
+            Some(t) => {
+                writer.write_all(&encode_union_index(1))?;
+                t.ssz_write(writer)
+            }
+        }
+    }
 }
 
 impl<T: Encode> Encode for Vec<T> {
@@ -65,6 +90,83 @@ impl<T: Encode> Encode for Vec<T> {
             encoder.finalize();
         }
     }
+
+    fn ssz_bytes_len(&self) -> usize {
+        list_bytes_len(self)
+    }
+
+    fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        encode_list_to_writer(self, writer)
+    }
+}
+
+/// Returns the length of the SSZ encoding of `items` as a list, without encoding them.
+pub fn list_bytes_len<T: Encode>(items: &[T]) -> usize {
+    if T::is_ssz_fixed_len() {
+        T::ssz_fixed_len() * items.len()
+    } else {
+        items
+            .iter()
+            .map(|item| BYTES_PER_LENGTH_OFFSET + item.ssz_bytes_len())
+            .sum()
+    }
+}
+
+/// Writes the SSZ encoding of `items` as a list to `writer`.
+///
+/// Fixed-length items are encoded in chunks to avoid many small writes, variable-length items
+/// are written one-by-one via `SszStreamEncoder`.
+pub fn encode_list_to_writer<T: Encode, W: Write>(items: &[T], writer: &mut W) -> io::Result<()> {
+    if T::is_ssz_fixed_len() {
+        let items_per_chunk = std::cmp::max(MAX_CHUNK_BYTES / T::ssz_fixed_len(), 1);
+        let mut buf = Vec::with_capacity(T::ssz_fixed_len() * items_per_chunk);
+
+        for chunk in items.chunks(items_per_chunk) {
+            buf.clear();
+
+            for item in chunk {
+                item.ssz_append(&mut buf);
+            }
+
+            writer.write_all(&buf)?;
+        }
+    } else {
+        let mut encoder = SszStreamEncoder::list(writer, items.len() * BYTES_PER_LENGTH_OFFSET);
+
+        for item in items {
+            encoder.write_fixed_part(item)?;
+        }
+
+        for item in items {
+            encoder.write_variable_part(item)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Allows for encoding borrowed objects (e.g., within a container of references) without
+/// cloning them.
+impl<'a, T: Encode> Encode for &'a T {
+    fn is_ssz_fixed_len() -> bool {
+        T::is_ssz_fixed_len()
+    }
+
+    fn ssz_fixed_len() -> usize {
+        T::ssz_fixed_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        T::ssz_append(self, buf)
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        T::ssz_bytes_len(self)
+    }
+
+    fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        T::ssz_write(self, writer)
+    }
 }
 
 impl Encode for bool {
@@ -107,6 +209,10 @@ impl Encode for H256 {
     fn ssz_append(&self, buf: &mut Vec<u8>) {
         buf.extend_from_slice(self.as_bytes());
     }
+
+    fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(self.as_bytes())
+    }
 }
 
 impl Encode for U256 {
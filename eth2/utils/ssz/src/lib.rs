@@ -39,9 +39,15 @@ mod encode;
 mod macros;
 
 pub use decode::{
-    impls::decode_list_of_variable_length_items, Decode, DecodeError, SszDecoder, SszDecoderBuilder,
+    impls::decode_list_of_variable_length_items,
+    stream::{decode_list_from_reader, SszStreamDecoder, SszStreamDecoderBuilder},
+    view::ContainerView,
+    Decode, DecodeError, SszDecoder, SszDecoderBuilder,
+};
+pub use encode::{
+    impls::{encode_list_to_writer, list_bytes_len},
+    Encode, SszEncoder, SszStreamEncoder,
 };
-pub use encode::{Encode, SszEncoder};
 
 /// The number of bytes used to represent an offset.
 pub const BYTES_PER_LENGTH_OFFSET: usize = 4;
@@ -1,6 +1,6 @@
 use ethereum_types::H256;
 use ssz::{Decode, DecodeError, Encode};
-use ssz_derive::{Decode, Encode};
+use ssz_derive::{Decode, DecodeView, Encode};
 
 mod round_trip {
     use super::*;
@@ -8,6 +8,17 @@ mod round_trip {
     fn round_trip<T: Encode + Decode + std::fmt::Debug + PartialEq>(items: Vec<T>) {
         for item in items {
             let encoded = &item.as_ssz_bytes();
+
+            // The streaming encoder/decoder must agree with the in-memory encoder/decoder.
+            let mut written = vec![];
+            item.ssz_write(&mut written).unwrap();
+            assert_eq!(&written, encoded);
+            assert_eq!(item.ssz_bytes_len(), encoded.len());
+            assert_eq!(
+                T::from_ssz_reader(&mut &encoded[..], encoded.len()).as_ref(),
+                Ok(&item)
+            );
+
             assert_eq!(T::from_ssz_bytes(&encoded), Ok(item));
         }
     }
@@ -284,6 +295,10 @@ mod round_trip {
             ThreeVariableLen::from_ssz_bytes(&bytes),
             Err(DecodeError::OutOfBoundsByte { i: 14 })
         );
+        assert_eq!(
+            ThreeVariableLen::from_ssz_reader(&mut &bytes[..], bytes.len()),
+            Err(DecodeError::OutOfBoundsByte { i: 14 })
+        );
     }
 
     #[derive(Debug, PartialEq, Encode, Decode)]
@@ -346,4 +361,333 @@ mod round_trip {
 
         round_trip(vec);
     }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    enum Union {
+        Empty,
+        Fixed(u16),
+        Variable(Vec<u16>),
+        Struct(VariableLen),
+    }
+
+    #[test]
+    fn union_encoding() {
+        assert_eq!(Union::Empty.as_ssz_bytes(), vec![0]);
+        assert_eq!(Union::Fixed(42).as_ssz_bytes(), vec![1, 42, 0]);
+        assert_eq!(
+            Union::Variable(vec![1, 2]).as_ssz_bytes(),
+            vec![2, 1, 0, 2, 0]
+        );
+    }
+
+    #[test]
+    fn union_round_trip() {
+        let vec: Vec<Union> = vec![
+            Union::Empty,
+            Union::Fixed(0),
+            Union::Fixed(42),
+            Union::Variable(vec![]),
+            Union::Variable(vec![0, 1, 2]),
+            Union::Struct(VariableLen {
+                a: 42,
+                b: vec![0, 1, 2],
+                c: 11,
+            }),
+        ];
+
+        round_trip(vec);
+    }
+
+    #[test]
+    fn vec_of_union() {
+        let vec: Vec<Vec<Union>> = vec![
+            vec![],
+            vec![Union::Empty, Union::Fixed(42), Union::Variable(vec![1])],
+        ];
+
+        round_trip(vec);
+    }
+
+    #[test]
+    fn union_invalid_bytes() {
+        assert_eq!(
+            Union::from_ssz_bytes(&[]),
+            Err(DecodeError::InvalidByteLength {
+                len: 0,
+                expected: 1
+            })
+        );
+        assert_eq!(
+            Union::from_ssz_bytes(&[4]),
+            Err(DecodeError::UnionSelectorInvalid(4))
+        );
+        assert_eq!(
+            Union::from_ssz_bytes(&[0, 0]),
+            Err(DecodeError::InvalidByteLength {
+                len: 1,
+                expected: 0
+            })
+        );
+        assert_eq!(
+            Union::from_ssz_bytes(&[1, 42]),
+            Err(DecodeError::InvalidByteLength {
+                len: 1,
+                expected: 2
+            })
+        );
+    }
+}
+
+mod view {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Encode, Decode, DecodeView)]
+    struct Foo {
+        a: u16,
+        b: Vec<u16>,
+        c: u32,
+        d: Vec<u8>,
+        #[ssz(skip_serializing)]
+        #[ssz(skip_deserializing)]
+        e: u64,
+    }
+
+    fn foo() -> Foo {
+        Foo {
+            a: 1,
+            b: vec![2, 3],
+            c: 4,
+            d: vec![5],
+            e: 0,
+        }
+    }
+
+    #[test]
+    fn read_fields() {
+        let bytes = foo().as_ssz_bytes();
+        let view = FooView::new(&bytes).unwrap();
+
+        assert_eq!(view.a(), Ok(1));
+        assert_eq!(view.b(), Ok(vec![2, 3]));
+        assert_eq!(view.c(), Ok(4));
+        assert_eq!(view.d(), Ok(vec![5]));
+    }
+
+    #[test]
+    fn read_field_bytes() {
+        let original = foo();
+        let bytes = original.as_ssz_bytes();
+        let view = FooView::new(&bytes).unwrap();
+
+        assert_eq!(view.a_bytes(), Ok(&original.a.as_ssz_bytes()[..]));
+        assert_eq!(view.b_bytes(), Ok(&original.b.as_ssz_bytes()[..]));
+        assert_eq!(view.c_bytes(), Ok(&original.c.as_ssz_bytes()[..]));
+        assert_eq!(view.d_bytes(), Ok(&original.d.as_ssz_bytes()[..]));
+    }
+
+    #[test]
+    fn nested_view() {
+        #[derive(Debug, PartialEq, Encode, Decode, DecodeView)]
+        struct Outer {
+            inner: Foo,
+            z: u8,
+        }
+
+        let bytes = Outer { inner: foo(), z: 6 }.as_ssz_bytes();
+        let view = OuterView::new(&bytes).unwrap();
+
+        assert_eq!(view.z(), Ok(6));
+
+        let inner = FooView::new(view.inner_bytes().unwrap()).unwrap();
+        assert_eq!(inner.b(), Ok(vec![2, 3]));
+        assert_eq!(inner.d(), Ok(vec![5]));
+    }
+
+    #[test]
+    fn generic_view() {
+        #[derive(Debug, PartialEq, Encode, Decode, DecodeView)]
+        struct Generic<T>
+        where
+            T: Encode + Decode,
+        {
+            a: T,
+            b: Vec<T>,
+        }
+
+        let bytes = Generic {
+            a: 1_u16,
+            b: vec![2_u16],
+        }
+        .as_ssz_bytes();
+        let view = GenericView::<u16>::new(&bytes).unwrap();
+
+        assert_eq!(view.a(), Ok(1));
+        assert_eq!(view.b(), Ok(vec![2]));
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(
+            FooView::new(&[0; 13]).err(),
+            Some(DecodeError::InvalidByteLength {
+                len: 13,
+                expected: 14
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_offsets() {
+        let mut bytes = foo().as_ssz_bytes();
+
+        // Point the offset of `b` into the fixed-length bytes.
+        bytes[2] = 13;
+        let view = FooView::new(&bytes).unwrap();
+        assert_eq!(view.a(), Ok(1));
+        assert_eq!(view.b(), Err(DecodeError::OutOfBoundsByte { i: 13 }));
+
+        // Point the offset of `d` past the end of the bytes.
+        bytes[2] = 14;
+        bytes[10] = 42;
+        let view = FooView::new(&bytes).unwrap();
+        assert_eq!(view.c(), Ok(4));
+        assert_eq!(view.b(), Err(DecodeError::OutOfBoundsByte { i: 14 }));
+        assert_eq!(view.d(), Err(DecodeError::OutOfBoundsByte { i: 42 }));
+    }
+}
+
+mod stream {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Inner {
+        a: u16,
+        b: Vec<u8>,
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Outer {
+        a: Vec<Inner>,
+        b: u64,
+        c: Inner,
+    }
+
+    fn outer() -> Outer {
+        Outer {
+            a: vec![
+                Inner { a: 1, b: vec![] },
+                Inner {
+                    a: 2,
+                    b: vec![3, 4, 5],
+                },
+            ],
+            b: 42,
+            c: Inner {
+                a: 6,
+                b: vec![7; 300],
+            },
+        }
+    }
+
+    #[test]
+    fn nested_round_trip() {
+        let outer = outer();
+        let bytes = outer.as_ssz_bytes();
+
+        let mut written = vec![];
+        outer.ssz_write(&mut written).unwrap();
+        assert_eq!(written, bytes);
+        assert_eq!(outer.ssz_bytes_len(), bytes.len());
+
+        let mut reader = &bytes[..];
+        assert_eq!(Outer::from_ssz_reader(&mut reader, bytes.len()), Ok(outer));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn reads_only_len_bytes() {
+        let mut bytes = vec![1_u16, 2].as_ssz_bytes();
+        let len = bytes.len();
+        bytes.extend_from_slice(&[42, 42]);
+
+        let mut reader = &bytes[..];
+        assert_eq!(
+            <Vec<u16>>::from_ssz_reader(&mut reader, len),
+            Ok(vec![1, 2])
+        );
+        assert_eq!(reader, &[42, 42]);
+    }
+
+    #[test]
+    fn reader_too_short() {
+        let bytes = outer().as_ssz_bytes();
+
+        assert_eq!(
+            Outer::from_ssz_reader(&mut &bytes[..bytes.len() - 1], bytes.len()),
+            Err(DecodeError::InvalidByteLength {
+                len: 299,
+                expected: 300
+            })
+        );
+    }
+
+    #[test]
+    fn excess_bytes() {
+        let bytes = vec![42, 0, 0, 0, 0, 0, 0, 0, 1];
+
+        assert_eq!(
+            u64::from_ssz_reader(&mut &bytes[..], bytes.len()),
+            Err(DecodeError::InvalidByteLength {
+                len: 9,
+                expected: 8
+            })
+        );
+    }
+
+    #[test]
+    fn first_offset_skips_byte() {
+        let mut bytes = outer().as_ssz_bytes();
+
+        // Point the offset of `a` one byte past the fixed-length bytes.
+        bytes[0] += 1;
+
+        assert_eq!(
+            Outer::from_ssz_reader(&mut &bytes[..], bytes.len()),
+            Outer::from_ssz_bytes(&bytes)
+        );
+        assert!(Outer::from_ssz_bytes(&bytes).is_err());
+    }
+
+    #[derive(Encode)]
+    struct OuterRef<'a> {
+        a: Vec<&'a Inner>,
+        b: &'a u64,
+        c: &'a Inner,
+    }
+
+    #[test]
+    fn borrowed_container() {
+        let outer = outer();
+        let outer_ref = OuterRef {
+            a: outer.a.iter().collect(),
+            b: &outer.b,
+            c: &outer.c,
+        };
+
+        let mut written = vec![];
+        outer_ref.ssz_write(&mut written).unwrap();
+        assert_eq!(written, outer.as_ssz_bytes());
+        assert_eq!(outer_ref.as_ssz_bytes(), outer.as_ssz_bytes());
+        assert_eq!(outer_ref.ssz_bytes_len(), outer.ssz_bytes_len());
+    }
+
+    #[test]
+    fn write_to_file_like() {
+        let outer = outer();
+        let mut writer = std::io::BufWriter::new(vec![]);
+
+        outer.ssz_write(&mut writer).unwrap();
+
+        assert_eq!(writer.into_inner().unwrap(), outer.as_ssz_bytes());
+    }
 }
@@ -58,25 +58,62 @@ fn should_skip_serializing(field: &syn::Field) -> bool {
     false
 }
 
-/// Implements `ssz::Encode` for some `struct`.
+/// Returns the type of the single field of each variant in the enum, or `None` for unit variants.
 ///
-/// Fields are encoded in the order they are defined.
+/// # Panics
+/// Any variant with named fields, or more than one unnamed field, will raise a panic at compile
+/// time. Enums with more variants than can be represented by a `u8` selector will also panic.
+fn get_union_variant_types<'a>(enum_data: &'a syn::DataEnum) -> Vec<Option<&'a syn::Type>> {
+    if enum_data.variants.len() > usize::from(std::u8::MAX) + 1 {
+        panic!("ssz_derive only supports enums with at most 256 variants.");
+    }
+
+    enum_data
+        .variants
+        .iter()
+        .map(|variant| match &variant.fields {
+            syn::Fields::Unit => None,
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Some(&fields.unnamed[0].ty)
+            }
+            _ => panic!(
+                "ssz_derive only supports enum variants with a single unnamed field or no fields."
+            ),
+        })
+        .collect()
+}
+
+/// Implements `ssz::Encode` for some `struct` or `enum`.
+///
+/// Struct fields are encoded in the order they are defined.
+///
+/// Enums are encoded as SSZ unions: a single selector byte (the index of the variant) followed by
+/// the encoding of the value of the variant, if any.
 #[proc_macro_derive(Encode, attributes(ssz))]
 pub fn ssz_encode_derive(input: TokenStream) -> TokenStream {
     let item = parse_macro_input!(input as DeriveInput);
 
+    match &item.data {
+        syn::Data::Struct(s) => ssz_encode_derive_struct(&item, s),
+        syn::Data::Enum(e) => ssz_encode_derive_enum(&item, e),
+        _ => panic!("ssz_derive only supports structs and enums."),
+    }
+}
+
+fn ssz_encode_derive_struct(item: &DeriveInput, struct_data: &syn::DataStruct) -> TokenStream {
     let name = &item.ident;
     let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
 
-    let struct_data = match &item.data {
-        syn::Data::Struct(s) => s,
-        _ => panic!("ssz_derive only supports structs."),
-    };
-
     let field_idents = get_serializable_named_field_idents(&struct_data);
     let field_types_a = get_serializable_field_types(&struct_data);
     let field_types_b = field_types_a.clone();
     let field_types_c = field_types_a.clone();
+    let field_types_d = field_types_a.clone();
+    let field_types_e = field_types_a.clone();
+    let field_types_f = field_types_a.clone();
+    let field_idents_b = field_idents.clone();
+    let field_idents_c = field_idents.clone();
+    let field_idents_d = field_idents.clone();
 
     let output = quote! {
         impl #impl_generics ssz::Encode for #name #ty_generics #where_clause {
@@ -112,6 +149,127 @@ pub fn ssz_encode_derive(input: TokenStream) -> TokenStream {
 
                 encoder.finalize();
             }
+
+            fn ssz_bytes_len(&self) -> usize {
+                if <Self as ssz::Encode>::is_ssz_fixed_len() {
+                    <Self as ssz::Encode>::ssz_fixed_len()
+                } else {
+                    let mut len = 0;
+                    #(
+                        if <#field_types_d as ssz::Encode>::is_ssz_fixed_len() {
+                            len += <#field_types_e as ssz::Encode>::ssz_fixed_len();
+                        } else {
+                            len += ssz::BYTES_PER_LENGTH_OFFSET;
+                            len += ssz::Encode::ssz_bytes_len(&self.#field_idents_b);
+                        }
+                    )*
+                    len
+                }
+            }
+
+            fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                let offset = #(
+                        <#field_types_f as ssz::Encode>::ssz_fixed_len() +
+                    )*
+                        0;
+
+                let mut encoder = ssz::SszStreamEncoder::container(writer, offset);
+
+                #(
+                    encoder.write_fixed_part(&self.#field_idents_c)?;
+                )*
+
+                #(
+                    encoder.write_variable_part(&self.#field_idents_d)?;
+                )*
+
+                Ok(())
+            }
+        }
+    };
+    output.into()
+}
+
+fn ssz_encode_derive_enum(item: &DeriveInput, enum_data: &syn::DataEnum) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let mut arms = vec![];
+    let mut len_arms = vec![];
+    let mut write_arms = vec![];
+
+    for (selector, (variant, ty)) in enum_data
+        .variants
+        .iter()
+        .zip(get_union_variant_types(enum_data))
+        .enumerate()
+    {
+        let variant_name = &variant.ident;
+        let selector = selector as u8;
+
+        match ty {
+            Some(_) => {
+                arms.push(quote! {
+                    #name::#variant_name(ref inner) => {
+                        buf.push(#selector);
+                        ssz::Encode::ssz_append(inner, buf);
+                    }
+                });
+                len_arms.push(quote! {
+                    #name::#variant_name(ref inner) => 1 + ssz::Encode::ssz_bytes_len(inner),
+                });
+                write_arms.push(quote! {
+                    #name::#variant_name(ref inner) => {
+                        writer.write_all(&[#selector])?;
+                        ssz::Encode::ssz_write(inner, writer)
+                    }
+                });
+            }
+            None => {
+                arms.push(quote! {
+                    #name::#variant_name => {
+                        buf.push(#selector);
+                    }
+                });
+                len_arms.push(quote! {
+                    #name::#variant_name => 1,
+                });
+                write_arms.push(quote! {
+                    #name::#variant_name => writer.write_all(&[#selector]),
+                });
+            }
+        }
+    }
+
+    let output = quote! {
+        impl #impl_generics ssz::Encode for #name #ty_generics #where_clause {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                match *self {
+                    #(
+                        #arms
+                    )*
+                }
+            }
+
+            fn ssz_bytes_len(&self) -> usize {
+                match *self {
+                    #(
+                        #len_arms
+                    )*
+                }
+            }
+
+            fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                match *self {
+                    #(
+                        #write_arms
+                    )*
+                }
+            }
         }
     };
     output.into()
@@ -129,21 +287,26 @@ fn should_skip_deserializing(field: &syn::Field) -> bool {
     false
 }
 
-/// Implements `ssz::Decode` for some `struct`.
+/// Implements `ssz::Decode` for some `struct` or `enum`.
+///
+/// Struct fields are decoded in the order they are defined.
 ///
-/// Fields are decoded in the order they are defined.
+/// Enums are decoded as SSZ unions, see `ssz_encode_derive`.
 #[proc_macro_derive(Decode)]
 pub fn ssz_decode_derive(input: TokenStream) -> TokenStream {
     let item = parse_macro_input!(input as DeriveInput);
 
+    match &item.data {
+        syn::Data::Struct(s) => ssz_decode_derive_struct(&item, s),
+        syn::Data::Enum(e) => ssz_decode_derive_enum(&item, e),
+        _ => panic!("ssz_derive only supports structs and enums."),
+    }
+}
+
+fn ssz_decode_derive_struct(item: &DeriveInput, struct_data: &syn::DataStruct) -> TokenStream {
     let name = &item.ident;
     let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
 
-    let struct_data = match &item.data {
-        syn::Data::Struct(s) => s,
-        _ => panic!("ssz_derive only supports structs."),
-    };
-
     let mut register_types = vec![];
     let mut decodes = vec![];
     let mut is_fixed_lens = vec![];
@@ -183,6 +346,10 @@ pub fn ssz_decode_derive(input: TokenStream) -> TokenStream {
         };
     }
 
+    let fixed_lens_b = fixed_lens.clone();
+    let register_types_b = register_types.clone();
+    let decodes_b = decodes.clone();
+
     let output = quote! {
         impl #impl_generics ssz::Decode for #name #ty_generics #where_clause {
             fn is_ssz_fixed_len() -> bool {
@@ -218,6 +385,191 @@ pub fn ssz_decode_derive(input: TokenStream) -> TokenStream {
                     )*
                 })
             }
+
+            fn from_ssz_reader<R: std::io::Read>(
+                reader: &mut R,
+                len: usize,
+            ) -> Result<Self, ssz::DecodeError> {
+                let num_fixed_bytes = #(
+                        #fixed_lens_b +
+                    )*
+                        0;
+
+                let mut builder = ssz::SszStreamDecoderBuilder::new(reader, len, num_fixed_bytes)?;
+
+                #(
+                    #register_types_b
+                )*
+
+                let mut decoder = builder.build()?;
+
+                Ok(Self {
+                    #(
+                        #decodes_b,
+                    )*
+                })
+            }
+        }
+    };
+    output.into()
+}
+
+fn ssz_decode_derive_enum(item: &DeriveInput, enum_data: &syn::DataEnum) -> TokenStream {
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let arms: Vec<_> = enum_data
+        .variants
+        .iter()
+        .zip(get_union_variant_types(enum_data))
+        .enumerate()
+        .map(|(selector, (variant, ty))| {
+            let variant_name = &variant.ident;
+            let selector = selector as u8;
+
+            match ty {
+                Some(ty) => quote! {
+                    #selector => Ok(#name::#variant_name(<#ty as ssz::Decode>::from_ssz_bytes(body)?)),
+                },
+                None => quote! {
+                    #selector => {
+                        if body.is_empty() {
+                            Ok(#name::#variant_name)
+                        } else {
+                            Err(ssz::DecodeError::InvalidByteLength {
+                                len: body.len(),
+                                expected: 0,
+                            })
+                        }
+                    }
+                },
+            }
+        })
+        .collect();
+
+    let output = quote! {
+        impl #impl_generics ssz::Decode for #name #ty_generics #where_clause {
+            fn is_ssz_fixed_len() -> bool {
+                false
+            }
+
+            fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+                let (selector, body) = bytes
+                    .split_first()
+                    .ok_or(ssz::DecodeError::InvalidByteLength {
+                        len: 0,
+                        expected: 1,
+                    })?;
+
+                match *selector {
+                    #(
+                        #arms
+                    )*
+                    other => Err(ssz::DecodeError::UnionSelectorInvalid(other)),
+                }
+            }
+        }
+    };
+    output.into()
+}
+
+/// Generates a `FooView` type for some `struct Foo`, which reads individual fields from the SSZ
+/// bytes of a `Foo` without decoding the entire struct (see `ssz::ContainerView`).
+///
+/// For each field `bar: Bar`, the view has the methods:
+///
+/// - `fn bar(&self) -> Result<Bar, ssz::DecodeError>`, which decodes the field.
+/// - `fn bar_bytes(&self) -> Result<&[u8], ssz::DecodeError>`, which returns the SSZ bytes of the
+/// field (e.g., to instantiate a view of the field).
+///
+/// Fields marked `#[ssz(skip_deserializing)]` are not present in the view.
+#[proc_macro_derive(DecodeView, attributes(ssz))]
+pub fn ssz_decode_view_derive(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as DeriveInput);
+
+    let struct_data = match &item.data {
+        syn::Data::Struct(s) => s,
+        _ => panic!("ssz_derive only supports views of structs."),
+    };
+
+    let name = &item.ident;
+    let vis = &item.vis;
+    let view_name = syn::Ident::new(&format!("{}View", name), name.span());
+    let (_, ty_generics, where_clause) = &item.generics.split_for_impl();
+
+    let mut view_generics = item.generics.clone();
+    view_generics.params.insert(0, syn::parse_quote!('a));
+    let (view_impl_generics, view_ty_generics, _) = view_generics.split_for_impl();
+
+    let mut fixed_lens = vec![];
+    let mut accessors = vec![];
+
+    for field in &struct_data.fields {
+        let ident = match &field.ident {
+            Some(ref ident) => ident,
+            _ => panic!("ssz_derive only supports named struct fields."),
+        };
+
+        if should_skip_deserializing(field) {
+            continue;
+        }
+
+        let ty = &field.ty;
+        let index = fixed_lens.len();
+        let bytes_ident = syn::Ident::new(&format!("{}_bytes", ident), ident.span());
+        let decode_doc = format!("Decodes the `{}` field.", ident);
+        let bytes_doc = format!("Returns the SSZ bytes of the `{}` field.", ident);
+
+        fixed_lens.push(quote! {
+            if <#ty as ssz::Decode>::is_ssz_fixed_len() {
+                Some(<#ty as ssz::Decode>::ssz_fixed_len())
+            } else {
+                None
+            }
+        });
+
+        accessors.push(quote! {
+            #[doc = #decode_doc]
+            pub fn #ident(&self) -> Result<#ty, ssz::DecodeError> {
+                self.view.decode_field(#index)
+            }
+
+            #[doc = #bytes_doc]
+            pub fn #bytes_ident(&self) -> Result<&'a [u8], ssz::DecodeError> {
+                self.view.field_bytes(#index)
+            }
+        });
+    }
+
+    let view_doc = format!(
+        "Reads individual fields from the SSZ bytes of a `{}`, without decoding the entire struct.",
+        name
+    );
+
+    let output = quote! {
+        #[doc = #view_doc]
+        #vis struct #view_name #view_generics #where_clause {
+            view: ssz::ContainerView<'a>,
+            _phantom: std::marker::PhantomData<#name #ty_generics>,
+        }
+
+        impl #view_impl_generics #view_name #view_ty_generics #where_clause {
+            /// Instantiate a view over `bytes`, returning an error if they are too short to
+            /// contain the fixed-length fields.
+            pub fn new(bytes: &'a [u8]) -> Result<Self, ssz::DecodeError> {
+                Ok(Self {
+                    view: ssz::ContainerView::new(bytes, &[
+                        #(
+                            #fixed_lens,
+                        )*
+                    ])?,
+                    _phantom: std::marker::PhantomData,
+                })
+            }
+
+            #(
+                #accessors
+            )*
         }
     };
     output.into()
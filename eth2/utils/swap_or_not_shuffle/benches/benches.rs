@@ -77,6 +77,22 @@ fn shuffles(c: &mut Criterion) {
         .sample_size(10),
     );
 
+    // The approximate number of validators expected on mainnet.
+    c.bench_function("single swap of 300k elements", move |b| {
+        let seed = vec![42; 32];
+        b.iter(|| black_box(get_permutated_index(0, 300_000, &seed, SHUFFLE_ROUND_COUNT)))
+    });
+
+    c.bench(
+        "_fast_ whole list shuffle",
+        Benchmark::new("300k elements", move |b| {
+            let seed = vec![42; 32];
+            let list: Vec<usize> = (0..300_000).collect();
+            b.iter(|| black_box(fast_shuffle(list.clone(), SHUFFLE_ROUND_COUNT, &seed, true)))
+        })
+        .sample_size(10),
+    );
+
     c.bench(
         "_fast_ whole list shuffle",
         Benchmark::new("4m elements", move |b| {
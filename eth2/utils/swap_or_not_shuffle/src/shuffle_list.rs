@@ -1,5 +1,4 @@
 use hashing::hash;
-use int_to_bytes::int_to_bytes4;
 
 const SEED_SIZE: usize = 32;
 const ROUND_SIZE: usize = 1;
@@ -19,6 +18,8 @@ const TOTAL_SIZE: usize = SEED_SIZE + ROUND_SIZE + POSITION_WINDOW_SIZE;
 /// It holds that: shuffle_list(shuffle_list(l, r, s, true), r, s, false) == l
 ///           and: shuffle_list(shuffle_list(l, r, s, false), r, s, true) == l
 ///
+/// Panics if `seed` is shorter than 32 bytes.
+///
 /// Returns `None` under any of the following conditions:
 ///  - `list_size == 0`
 ///  - `list_size > 2**24`
@@ -39,29 +40,27 @@ pub fn shuffle_list(
         return None;
     }
 
-    let mut buf: Vec<u8> = Vec::with_capacity(TOTAL_SIZE);
+    // A fixed buffer avoids allocating for each of the (many) hashes in a shuffle.
+    let mut buf = [0; TOTAL_SIZE];
+    buf[0..SEED_SIZE].copy_from_slice(&seed[0..SEED_SIZE]);
 
     let mut r = if forwards { 0 } else { rounds - 1 };
 
-    buf.extend_from_slice(seed);
-
     loop {
-        buf.splice(SEED_SIZE.., vec![r]);
+        buf[SEED_SIZE] = r;
 
         let pivot = bytes_to_int64(&hash(&buf[0..PIVOT_VIEW_SIZE])[0..8]) as usize % list_size;
 
         let mirror = (pivot + 1) >> 1;
 
-        buf.splice(PIVOT_VIEW_SIZE.., int_to_bytes4((pivot >> 8) as u32));
-        let mut source = hash(&buf[..]);
+        let mut source = hash_position(&mut buf, pivot >> 8);
         let mut byte_v = source[(pivot & 0xff) >> 3];
 
         for i in 0..mirror {
             let j = pivot - i;
 
             if j & 0xff == 0xff {
-                buf.splice(PIVOT_VIEW_SIZE.., int_to_bytes4((j >> 8) as u32));
-                source = hash(&buf[..]);
+                source = hash_position(&mut buf, j >> 8);
             }
 
             if j & 0x07 == 0x07 {
@@ -77,16 +76,14 @@ pub fn shuffle_list(
         let mirror = (pivot + list_size + 1) >> 1;
         let end = list_size - 1;
 
-        buf.splice(PIVOT_VIEW_SIZE.., int_to_bytes4((end >> 8) as u32));
-        let mut source = hash(&buf[..]);
+        let mut source = hash_position(&mut buf, end >> 8);
         let mut byte_v = source[(end & 0xff) >> 3];
 
         for (loop_iter, i) in ((pivot + 1)..mirror).enumerate() {
             let j = end - loop_iter;
 
             if j & 0xff == 0xff {
-                buf.splice(PIVOT_VIEW_SIZE.., int_to_bytes4((j >> 8) as u32));
-                source = hash(&buf[..]);
+                source = hash_position(&mut buf, j >> 8);
             }
 
             if j & 0x07 == 0x07 {
@@ -115,6 +112,12 @@ pub fn shuffle_list(
     Some(input)
 }
 
+/// Sets the position window of `buf` to `position` and returns the hash of `buf`.
+fn hash_position(buf: &mut [u8; TOTAL_SIZE], position: usize) -> Vec<u8> {
+    buf[PIVOT_VIEW_SIZE..].copy_from_slice(&(position as u32).to_le_bytes());
+    hash(&buf[..])
+}
+
 fn bytes_to_int64(slice: &[u8]) -> u64 {
     let mut bytes = [0; 8];
     bytes.copy_from_slice(&slice[0..8]);
@@ -124,9 +127,32 @@ fn bytes_to_int64(slice: &[u8]) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::get_permutated_index;
 
     #[test]
     fn returns_none_for_zero_length_list() {
         assert_eq!(None, shuffle_list(vec![], 90, &[42, 42], true));
     }
+
+    #[test]
+    fn matches_get_permutated_index() {
+        let seed = [42; SEED_SIZE];
+
+        for &list_size in &[1, 2, 255, 256, 257, 1_000] {
+            let input: Vec<usize> = (0..list_size).collect();
+            let shuffled = shuffle_list(input.clone(), 90, &seed, false).unwrap();
+
+            for (i, index) in shuffled.iter().enumerate() {
+                assert_eq!(
+                    *index,
+                    get_permutated_index(i, list_size, &seed, 90).unwrap(),
+                    "list_size {}, index {}",
+                    list_size,
+                    i
+                );
+            }
+
+            assert_eq!(shuffle_list(shuffled, 90, &seed, true), Some(input));
+        }
+    }
 }
@@ -119,6 +119,14 @@ macro_rules! impl_for_list {
 
                 hash(&root_and_len)
             }
+
+            fn tree_hash_proof(&self, gindex: usize) -> Result<Proof, Error> {
+                proof::list_proof(self, gindex)
+            }
+
+            fn tree_hash_gindex(&self, path: &[PathElement]) -> Result<usize, Error> {
+                proof::list_gindex(self, path)
+            }
         }
     };
 }
@@ -169,5 +177,4 @@ mod test {
         assert_eq!(true.tree_hash_root(), true_bytes);
         assert_eq!(false.tree_hash_root(), false_bytes);
     }
-
 }
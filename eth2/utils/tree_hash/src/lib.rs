@@ -1,5 +1,8 @@
 pub mod impls;
 pub mod merkleize;
+pub mod proof;
+
+pub use proof::{Error, MultiProof, PathElement, Proof};
 
 pub const BYTES_PER_CHUNK: usize = 32;
 pub const HASHSIZE: usize = 32;
@@ -21,6 +24,35 @@ pub trait TreeHash {
     fn tree_hash_packing_factor() -> usize;
 
     fn tree_hash_root(&self) -> Vec<u8>;
+
+    /// Returns a proof of the node at generalized index `gindex` in the tree of `self`, where the
+    /// root is at `1`.
+    ///
+    /// The default implementation is only able to prove the root. Types with children (e.g.,
+    /// containers and lists) should override this method.
+    fn tree_hash_proof(&self, gindex: usize) -> Result<Proof, Error> {
+        if gindex == 1 {
+            Ok(Proof {
+                gindex,
+                leaf: ethereum_types::H256::from_slice(&self.tree_hash_root()),
+                branch: vec![],
+            })
+        } else {
+            Err(Error::InvalidGeneralizedIndex(gindex))
+        }
+    }
+
+    /// Returns the generalized index of the node at `path` in the tree of `self`.
+    ///
+    /// The default implementation is only able to find the root (an empty `path`). Types with
+    /// children should override this method.
+    fn tree_hash_gindex(&self, path: &[PathElement]) -> Result<usize, Error> {
+        if path.is_empty() {
+            Ok(1)
+        } else {
+            Err(Error::InvalidPath(format!("{:?} has no children", path[0])))
+        }
+    }
 }
 
 pub trait SignedRoot: TreeHash {
@@ -1,11 +1,13 @@
 use super::*;
-use hashing::hash;
+use hashing::batch_hash_64_into;
 
 pub fn merkle_root(bytes: &[u8]) -> Vec<u8> {
     // TODO: replace this with a more memory efficient method.
     efficient_merkleize(&bytes)[0..32].to_vec()
 }
 
+/// Returns every node of the merkle tree of `bytes` (padded with zeros to a power-of-two number
+/// of chunks), with the root first and the leaves last.
 pub fn efficient_merkleize(bytes: &[u8]) -> Vec<u8> {
     // If the bytes are just one chunk (or less than one chunk) just return them.
     if bytes.len() <= HASHSIZE {
@@ -18,42 +20,24 @@ pub fn efficient_merkleize(bytes: &[u8]) -> Vec<u8> {
     let nodes = num_nodes(leaves);
     let internal_nodes = nodes - leaves;
 
-    let num_bytes = std::cmp::max(internal_nodes, 1) * HASHSIZE + bytes.len();
+    let mut o: Vec<u8> = vec![0; nodes * HASHSIZE];
+    o[internal_nodes * HASHSIZE..internal_nodes * HASHSIZE + bytes.len()].copy_from_slice(bytes);
 
-    let mut o: Vec<u8> = vec![0; internal_nodes * HASHSIZE];
+    // Hash each layer of the tree in a single call, from the leaves up to the root.
+    let mut layer_start = internal_nodes;
+    let mut layer_len = leaves;
 
-    o.append(&mut bytes.to_vec());
+    while layer_len > 1 {
+        let parents_start = layer_start - layer_len / 2;
+        let (parents, children) = o.split_at_mut(layer_start * HASHSIZE);
 
-    assert_eq!(o.len(), num_bytes);
+        batch_hash_64_into(
+            &children[0..layer_len * HASHSIZE],
+            &mut parents[parents_start * HASHSIZE..],
+        );
 
-    let empty_chunk_hash = hash(&[0; MERKLE_HASH_CHUNK]);
-
-    let mut i = nodes * HASHSIZE;
-    let mut j = internal_nodes * HASHSIZE;
-
-    while i >= MERKLE_HASH_CHUNK {
-        i -= MERKLE_HASH_CHUNK;
-
-        j -= HASHSIZE;
-        let hash = match o.get(i..i + MERKLE_HASH_CHUNK) {
-            // All bytes are available, hash as ususal.
-            Some(slice) => hash(slice),
-            // Unable to get all the bytes.
-            None => {
-                match o.get(i..) {
-                    // Able to get some of the bytes, pad them out.
-                    Some(slice) => {
-                        let mut bytes = slice.to_vec();
-                        bytes.resize(MERKLE_HASH_CHUNK, 0);
-                        hash(&bytes)
-                    }
-                    // Unable to get any bytes, use the empty-chunk hash.
-                    None => empty_chunk_hash.clone(),
-                }
-            }
-        };
-
-        o[j..j + HASHSIZE].copy_from_slice(&hash);
+        layer_start = parents_start;
+        layer_len /= 2;
     }
 
     o
@@ -67,3 +51,35 @@ fn num_sanitized_leaves(num_bytes: usize) -> usize {
 fn num_nodes(num_leaves: usize) -> usize {
     2 * num_leaves - 1
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hashing::hash;
+
+    /// Merkleizes by hashing one pair of nodes at a time.
+    fn naive_merkle_root(bytes: &[u8]) -> Vec<u8> {
+        let mut layer = bytes.to_vec();
+        layer.resize(num_sanitized_leaves(bytes.len()) * HASHSIZE, 0);
+
+        while layer.len() > HASHSIZE {
+            layer = layer.chunks(MERKLE_HASH_CHUNK).flat_map(hash).collect();
+        }
+
+        layer
+    }
+
+    #[test]
+    fn matches_naive_merkle_root() {
+        for len in 0..(70 * HASHSIZE) {
+            let bytes: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+            assert_eq!(
+                merkle_root(&bytes),
+                naive_merkle_root(&bytes),
+                "len {}",
+                len
+            );
+        }
+    }
+}
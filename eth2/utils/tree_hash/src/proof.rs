@@ -0,0 +1,315 @@
+//! Generation of Merkle proofs for the nodes of the tree of any `TreeHash` object.
+//!
+//! Nodes are identified by their generalized index: the root has index `1` and the children of
+//! the node at index `i` are at `2i` and `2i + 1`. See `TreeHash::tree_hash_gindex` for finding
+//! the generalized index of a field (e.g., `validator_registry[i].effective_balance`).
+use super::*;
+use ethereum_types::H256;
+use hashing::batch_hash_64;
+use int_to_bytes::int_to_bytes32;
+use merkle_proof::{
+    generalized_index_depth, get_helper_indices, verify_merkle_multiproof, verify_merkle_proof,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// The generalized index does not refer to a node in the tree.
+    InvalidGeneralizedIndex(usize),
+    /// The path does not refer to a node in the tree.
+    InvalidPath(String),
+}
+
+/// An element of the path to some node in the tree of a `TreeHash` object.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PathElement<'a> {
+    /// A named field of a container.
+    Field(&'a str),
+    /// An item of a list or vector.
+    Index(usize),
+}
+
+/// A proof that `leaf` is the node at generalized index `gindex` of some tree.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Proof {
+    pub gindex: usize,
+    pub leaf: H256,
+    /// The siblings of each node on the path from `leaf` to the root, in bottom-up order.
+    pub branch: Vec<H256>,
+}
+
+impl Proof {
+    /// Returns `true` if the proof is valid for a tree with the given `root`.
+    ///
+    /// Always returns `false` if `self.gindex == 0`.
+    pub fn verify(&self, root: H256) -> bool {
+        match generalized_index_depth(self.gindex) {
+            Some(depth) => verify_merkle_proof(
+                self.leaf,
+                &self.branch,
+                depth,
+                self.gindex - (1 << depth),
+                root,
+            ),
+            None => false,
+        }
+    }
+}
+
+/// A proof that each of `leaves` is the node at the corresponding generalized index of `gindices`
+/// of some tree.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultiProof {
+    pub gindices: Vec<usize>,
+    pub leaves: Vec<H256>,
+    /// The nodes at `merkle_proof::get_helper_indices(gindices)`.
+    pub helpers: Vec<H256>,
+}
+
+impl MultiProof {
+    /// Returns `true` if the proof is valid for a tree with the given `root`.
+    pub fn verify(&self, root: H256) -> bool {
+        verify_merkle_multiproof(&self.leaves, &self.helpers, &self.gindices, root)
+    }
+}
+
+/// Returns a proof of all of the nodes at `gindices` in the tree of `item`.
+pub fn multiproof<T: TreeHash>(item: &T, gindices: &[usize]) -> Result<MultiProof, Error> {
+    let mut nodes = HashMap::new();
+    let mut leaves = Vec::with_capacity(gindices.len());
+
+    // Every helper node is in the branch of at least one of the leaves.
+    for &gindex in gindices {
+        let proof = item.tree_hash_proof(gindex)?;
+
+        for (i, node) in proof.branch.iter().enumerate() {
+            nodes.insert((gindex >> i) ^ 1, *node);
+        }
+
+        leaves.push(proof.leaf);
+    }
+
+    let helpers = get_helper_indices(gindices)
+        .iter()
+        .map(|i| nodes[i])
+        .collect();
+
+    Ok(MultiProof {
+        gindices: gindices.to_vec(),
+        leaves,
+        helpers,
+    })
+}
+
+/// Returns the generalized index of the node at `child` in the tree rooted at the node at
+/// `parent`.
+pub fn concat_gindices(parent: usize, child: usize) -> Result<usize, Error> {
+    let depth =
+        generalized_index_depth(child).ok_or_else(|| Error::InvalidGeneralizedIndex(child))?;
+
+    Ok((parent << depth) | (child - (1 << depth)))
+}
+
+/// Returns the number of leaves in a tree of `num_chunks` chunks (i.e., padded to a power of two).
+pub fn num_leaves(num_chunks: usize) -> usize {
+    std::cmp::max(num_chunks, 1).next_power_of_two()
+}
+
+/// Returns a proof of the node at `gindex` in the tree with the given leaf `chunks`.
+///
+/// If `gindex` is below the leaves, `descend(i, sub_gindex)` is called to prove the node at
+/// `sub_gindex` in the tree of the object whose root is the `i`'th chunk.
+pub fn tree_proof<F>(chunks: &[H256], gindex: usize, descend: F) -> Result<Proof, Error>
+where
+    F: FnOnce(usize, usize) -> Result<Proof, Error>,
+{
+    let gindex_depth =
+        generalized_index_depth(gindex).ok_or_else(|| Error::InvalidGeneralizedIndex(gindex))?;
+
+    let num_leaves = num_leaves(chunks.len());
+    // `num_leaves` is a power of two.
+    let depth = num_leaves.trailing_zeros() as usize;
+
+    // Build each layer of the tree, from the leaves to the root.
+    let mut layers = Vec::with_capacity(depth + 1);
+    let mut layer = chunks.to_vec();
+    layer.resize(num_leaves, H256::zero());
+
+    while layer.len() > 1 {
+        let input: Vec<u8> = layer
+            .iter()
+            .flat_map(|node| node.as_bytes())
+            .cloned()
+            .collect();
+        let next = batch_hash_64(&input)
+            .chunks(HASHSIZE)
+            .map(H256::from_slice)
+            .collect();
+
+        layers.push(std::mem::replace(&mut layer, next));
+    }
+    layers.push(layer);
+
+    let (leaf, mut branch, mut index, level) = if gindex_depth <= depth {
+        let level = depth - gindex_depth;
+        let index = gindex - (1 << gindex_depth);
+
+        (layers[level][index], vec![], index, level)
+    } else {
+        let below = gindex_depth - depth;
+        let index = (gindex >> below) - num_leaves;
+
+        if index >= chunks.len() {
+            return Err(Error::InvalidGeneralizedIndex(gindex));
+        }
+
+        let sub_gindex = (gindex & ((1 << below) - 1)) | (1 << below);
+        let proof = descend(index, sub_gindex).map_err(|e| match e {
+            Error::InvalidGeneralizedIndex(_) => Error::InvalidGeneralizedIndex(gindex),
+            e => e,
+        })?;
+
+        (proof.leaf, proof.branch, index, 0)
+    };
+
+    for layer in &layers[level..depth] {
+        branch.push(layer[index ^ 1]);
+        index /= 2;
+    }
+
+    Ok(Proof {
+        gindex,
+        leaf,
+        branch,
+    })
+}
+
+/// Returns a proof of the node at `gindex` in the tree of a container with the given field
+/// roots.
+///
+/// See `tree_proof` for a description of `descend`.
+pub fn container_proof<F>(
+    field_roots: Vec<Vec<u8>>,
+    gindex: usize,
+    descend: F,
+) -> Result<Proof, Error>
+where
+    F: FnOnce(usize, usize) -> Result<Proof, Error>,
+{
+    let chunks: Vec<H256> = field_roots
+        .iter()
+        .map(|root| H256::from_slice(root))
+        .collect();
+
+    tree_proof(&chunks, gindex, descend)
+}
+
+/// Returns a proof of the node at `gindex` in the tree of `vec`, as hashed by
+/// `impls::vec_tree_hash_root` (i.e., without the length mixed-in).
+pub fn vec_proof<T: TreeHash>(vec: &[T], gindex: usize) -> Result<Proof, Error> {
+    match T::tree_hash_type() {
+        TreeHashType::Basic => {
+            let mut bytes =
+                Vec::with_capacity((HASHSIZE / T::tree_hash_packing_factor()) * vec.len());
+
+            for item in vec {
+                bytes.append(&mut item.tree_hash_packed_encoding());
+            }
+
+            let chunks: Vec<H256> = bytes
+                .chunks(HASHSIZE)
+                .map(|chunk| {
+                    let mut padded = [0; HASHSIZE];
+                    padded[..chunk.len()].copy_from_slice(chunk);
+                    H256::from(padded)
+                })
+                .collect();
+
+            // Packed items have no tree of their own.
+            tree_proof(&chunks, gindex, |_, sub_gindex| {
+                Err(Error::InvalidGeneralizedIndex(sub_gindex))
+            })
+        }
+        TreeHashType::Container | TreeHashType::List | TreeHashType::Vector => {
+            let chunks: Vec<H256> = vec
+                .iter()
+                .map(|item| H256::from_slice(&item.tree_hash_root()))
+                .collect();
+
+            tree_proof(&chunks, gindex, |i, sub_gindex| {
+                vec[i].tree_hash_proof(sub_gindex)
+            })
+        }
+    }
+}
+
+/// Returns a proof of the node at `gindex` in the tree of `vec` as a list, i.e., with the length
+/// mixed-in.
+///
+/// The list items are below the generalized index `2` and the length is at `3`.
+pub fn list_proof<T: TreeHash>(vec: &[T], gindex: usize) -> Result<Proof, Error> {
+    let chunks = [
+        H256::from_slice(&impls::vec_tree_hash_root(vec)),
+        H256::from_slice(&int_to_bytes32(vec.len() as u64)),
+    ];
+
+    tree_proof(&chunks, gindex, |i, sub_gindex| {
+        if i == 0 {
+            vec_proof(vec, sub_gindex)
+        } else {
+            Err(Error::InvalidGeneralizedIndex(sub_gindex))
+        }
+    })
+}
+
+/// Returns the generalized index of the node at `path` in the tree of `vec`, as hashed by
+/// `impls::vec_tree_hash_root`.
+///
+/// For lists of basic items, the path leads to the chunk containing the item.
+pub fn vec_gindex<T: TreeHash>(vec: &[T], path: &[PathElement]) -> Result<usize, Error> {
+    match path.split_first() {
+        None => Ok(1),
+        Some((PathElement::Index(i), rest)) => {
+            if *i >= vec.len() {
+                return Err(Error::InvalidPath(format!(
+                    "Index {} out-of-bounds for length {}",
+                    i,
+                    vec.len()
+                )));
+            }
+
+            match T::tree_hash_type() {
+                TreeHashType::Basic => {
+                    if !rest.is_empty() {
+                        return Err(Error::InvalidPath(format!(
+                            "Basic item {} has no fields",
+                            i
+                        )));
+                    }
+
+                    let packing_factor = T::tree_hash_packing_factor();
+                    let num_chunks = (vec.len() + packing_factor - 1) / packing_factor;
+
+                    Ok(num_leaves(num_chunks) + i / packing_factor)
+                }
+                TreeHashType::Container | TreeHashType::List | TreeHashType::Vector => {
+                    concat_gindices(num_leaves(vec.len()) + i, vec[*i].tree_hash_gindex(rest)?)
+                }
+            }
+        }
+        Some((element, _)) => Err(Error::InvalidPath(format!(
+            "Expected an index, found {:?}",
+            element
+        ))),
+    }
+}
+
+/// Returns the generalized index of the node at `path` in the tree of `vec` as a list, i.e.,
+/// with the length mixed-in.
+pub fn list_gindex<T: TreeHash>(vec: &[T], path: &[PathElement]) -> Result<usize, Error> {
+    if path.is_empty() {
+        Ok(1)
+    } else {
+        concat_gindices(2, vec_gindex(vec, path)?)
+    }
+}
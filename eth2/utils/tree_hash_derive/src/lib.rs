@@ -37,11 +37,67 @@ fn should_skip_hashing(field: &syn::Field) -> bool {
         .any(|attr| attr.into_token_stream().to_string() == "# [ tree_hash ( skip_hashing ) ]")
 }
 
-/// Implements `tree_hash::CachedTreeHash` for some `struct`.
+/// Returns the `cached_tree_hash` attributes of `attrs`, parsed as a list of metas.
+///
+/// E.g., `#[cached_tree_hash(cache = "FooCache")]` returns `[cache = "FooCache"]`.
+fn cached_tree_hash_metas(attrs: &[syn::Attribute]) -> Vec<syn::Meta> {
+    attrs
+        .iter()
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) if list.ident == "cached_tree_hash" => Some(list.nested),
+            _ => None,
+        })
+        .flat_map(|nested| nested.into_iter())
+        .map(|nested| match nested {
+            syn::NestedMeta::Meta(meta) => meta,
+            syn::NestedMeta::Literal(_) => panic!("Invalid cached_tree_hash attribute."),
+        })
+        .collect()
+}
+
+/// Returns the type named by a `#[cached_tree_hash(cache = "...")]` attribute on the struct, if
+/// any.
+fn get_cache_type(item: &DeriveInput) -> Option<syn::Type> {
+    cached_tree_hash_metas(&item.attrs)
+        .into_iter()
+        .map(|meta| match meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                ref ident,
+                lit: syn::Lit::Str(ref lit),
+                ..
+            }) if ident == "cache" => syn::parse_str(&lit.value())
+                .unwrap_or_else(|_| panic!("Invalid cache type: {}", lit.value())),
+            _ => panic!("Unknown cached_tree_hash attribute on struct."),
+        })
+        .next()
+}
+
+/// Returns true if some field has an attribute declaring it has its own cache.
+///
+/// The field attribute is: `#[cached_tree_hash(cached)]`
+fn has_own_cache(field: &syn::Field) -> bool {
+    cached_tree_hash_metas(&field.attrs)
+        .iter()
+        .any(|meta| match meta {
+            syn::Meta::Word(ident) if ident == "cached" => true,
+            _ => panic!("Unknown cached_tree_hash attribute on field."),
+        })
+}
+
+/// Implements `cached_tree_hash::CachedTreeHash` for some `struct`.
 ///
 /// Fields are hashed in the order they are defined.
-#[proc_macro_derive(CachedTreeHash, attributes(tree_hash))]
-pub fn subtree_derive(input: TokenStream) -> TokenStream {
+///
+/// By default the cache is a `TreeHashCache` of the roots of the fields, which avoids re-hashing
+/// when only some fields have changed. Fields which are themselves expensive to hash (e.g., a long
+/// `TrackedList`) may use their own cache by:
+///
+/// - Annotating the struct with `#[cached_tree_hash(cache = "FooCache")]`, where `FooCache` is a
+///   `Default` struct with a `fields: TreeHashCache` member.
+/// - Annotating each such field with `#[cached_tree_hash(cached)]` and adding a member with the
+///   same name to `FooCache`, holding the cache for that field.
+#[proc_macro_derive(CachedTreeHash, attributes(tree_hash, cached_tree_hash))]
+pub fn cached_tree_hash_derive(input: TokenStream) -> TokenStream {
     let item = parse_macro_input!(input as DeriveInput);
     let (impl_generics, ty_generics, where_clause) = &item.generics.split_for_impl();
 
@@ -52,60 +108,59 @@ pub fn subtree_derive(input: TokenStream) -> TokenStream {
         _ => panic!("tree_hash_derive only supports structs."),
     };
 
-    let idents_a = get_hashable_named_field_idents(&struct_data);
-    let idents_b = idents_a.clone();
-    let idents_c = idents_a.clone();
-
-    let output = quote! {
-        impl #impl_generics cached_tree_hash::CachedTreeHash for #name #ty_generics #where_clause {
-            fn new_tree_hash_cache(&self, depth: usize) -> Result<cached_tree_hash::TreeHashCache, cached_tree_hash::Error> {
-                let tree = cached_tree_hash::TreeHashCache::from_subtrees(
-                    self,
-                    vec![
-                        #(
-                            self.#idents_a.new_tree_hash_cache(depth)?,
-                        )*
-                    ],
-                    depth
-                )?;
-
-                Ok(tree)
-            }
-
-            fn num_tree_hash_cache_chunks(&self) -> usize {
-                cached_tree_hash::BTreeOverlay::new(self, 0, 0).num_chunks()
-            }
-
-            fn tree_hash_cache_schema(&self, depth: usize) -> cached_tree_hash::BTreeSchema {
-                let mut lengths = vec![];
-
-                #(
-                    lengths.push(self.#idents_b.num_tree_hash_cache_chunks());
-                )*
+    let cache_type = get_cache_type(&item);
 
-                cached_tree_hash::BTreeSchema::from_lengths(depth, lengths)
+    let leaves: Vec<_> = struct_data
+        .fields
+        .iter()
+        .filter(|f| !should_skip_hashing(f))
+        .map(|f| {
+            let ident = match &f.ident {
+                Some(ref ident) => ident,
+                _ => panic!("tree_hash_derive only supports named struct fields."),
+            };
+
+            if has_own_cache(f) {
+                if cache_type.is_none() {
+                    panic!("#[cached_tree_hash(cached)] requires a cache type on the struct.");
+                }
+
+                quote! {
+                    leaves.extend_from_slice(
+                        cached_tree_hash::CachedTreeHash::recalculate_tree_hash_root(
+                            &mut self.#ident,
+                            &mut cache.#ident,
+                        )?
+                        .as_bytes(),
+                    );
+                }
+            } else {
+                quote! {
+                    leaves.append(&mut tree_hash::TreeHash::tree_hash_root(&self.#ident));
+                }
             }
+        })
+        .collect();
+    let num_leaves = leaves.len();
 
-            fn update_tree_hash_cache(&self, cache: &mut cached_tree_hash::TreeHashCache) -> Result<(), cached_tree_hash::Error> {
-                let overlay = cached_tree_hash::BTreeOverlay::new(self, cache.chunk_index, 0);
-
+    let (cache_type, fields_cache) = match cache_type {
+        Some(cache_type) => (quote! { #cache_type }, quote! { cache.fields }),
+        None => (quote! { cached_tree_hash::TreeHashCache }, quote! { cache }),
+    };
 
-                // Skip the chunk index to the first leaf node of this struct.
-                cache.chunk_index = overlay.first_leaf_node();
-                // Skip the overlay index to the first leaf node of this struct.
-                // cache.overlay_index += 1;
+    let output = quote! {
+        impl #impl_generics cached_tree_hash::CachedTreeHash<#cache_type> for #name #ty_generics #where_clause {
+            fn recalculate_tree_hash_root(
+                &mut self,
+                cache: &mut #cache_type,
+            ) -> Result<cached_tree_hash::Hash256, cached_tree_hash::Error> {
+                let mut leaves = Vec::with_capacity(#num_leaves * tree_hash::HASHSIZE);
 
-                // Recurse into the struct items, updating their caches.
                 #(
-                    self.#idents_c.update_tree_hash_cache(cache)?;
+                    #leaves
                 )*
 
-                // Iterate through the internal nodes, updating them if their children have changed.
-                cache.update_internal_nodes(&overlay)?;
-
-                cache.chunk_index = overlay.next_node();
-
-                Ok(())
+                Ok(#fields_cache.recalculate_merkle_root(&leaves))
             }
         }
     };
@@ -128,6 +183,13 @@ pub fn tree_hash_derive(input: TokenStream) -> TokenStream {
     };
 
     let idents = get_hashable_named_field_idents(&struct_data);
+    let idents_b = idents.clone();
+    let idents_c = idents.clone();
+    let idents_d = idents.clone();
+    let indices: Vec<usize> = (0..idents.len()).collect();
+    let indices_b = indices.clone();
+    let field_names: Vec<String> = idents.iter().map(|ident| ident.to_string()).collect();
+    let num_fields = idents.len();
 
     let output = quote! {
         impl #impl_generics tree_hash::TreeHash for #name #ty_generics #where_clause {
@@ -152,6 +214,47 @@ pub fn tree_hash_derive(input: TokenStream) -> TokenStream {
 
                 tree_hash::merkleize::merkle_root(&leaves)
             }
+
+            fn tree_hash_proof(&self, gindex: usize) -> Result<tree_hash::Proof, tree_hash::Error> {
+                let field_roots = vec![
+                    #(
+                        self.#idents_b.tree_hash_root(),
+                    )*
+                ];
+
+                tree_hash::proof::container_proof(field_roots, gindex, |i, sub_gindex| match i {
+                    #(
+                        #indices => self.#idents_c.tree_hash_proof(sub_gindex),
+                    )*
+                    _ => Err(tree_hash::Error::InvalidGeneralizedIndex(sub_gindex)),
+                })
+            }
+
+            fn tree_hash_gindex(&self, path: &[tree_hash::PathElement]) -> Result<usize, tree_hash::Error> {
+                let num_leaves = tree_hash::proof::num_leaves(#num_fields);
+
+                match path.split_first() {
+                    None => Ok(1),
+                    Some((tree_hash::PathElement::Field(field), rest)) => match *field {
+                        #(
+                            #field_names => tree_hash::proof::concat_gindices(
+                                num_leaves + #indices_b,
+                                self.#idents_d.tree_hash_gindex(rest)?,
+                            ),
+                        )*
+                        other => Err(tree_hash::Error::InvalidPath(format!(
+                            "{} has no field named {}",
+                            stringify!(#name),
+                            other
+                        ))),
+                    },
+                    Some((element, _)) => Err(tree_hash::Error::InvalidPath(format!(
+                        "Expected a field of {}, found {:?}",
+                        stringify!(#name),
+                        element
+                    ))),
+                }
+            }
         }
     };
     output.into()
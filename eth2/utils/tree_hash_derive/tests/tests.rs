@@ -1,8 +1,10 @@
-use cached_tree_hash::{CachedTreeHash, TreeHashCache};
-use tree_hash::{merkleize::merkle_root, SignedRoot, TreeHash};
+use cached_tree_hash::{CachedTreeHash, ListTreeHashCache, TrackedList, TreeHashCache};
+use ethereum_types::H256;
+use fixed_len_vec::{typenum::U8, VariableList};
+use tree_hash::{merkleize::merkle_root, Error, PathElement, SignedRoot, TreeHash};
 use tree_hash_derive::{CachedTreeHash, SignedRoot, TreeHash};
 
-#[derive(Clone, Debug, TreeHash, CachedTreeHash)]
+#[derive(Clone, Debug, PartialEq, TreeHash, CachedTreeHash)]
 pub struct Inner {
     pub a: u64,
     pub b: u64,
@@ -10,19 +12,17 @@ pub struct Inner {
     pub d: u64,
 }
 
-fn test_standard_and_cached<T: CachedTreeHash>(original: &T, modified: &T) {
-    // let mut cache = original.new_tree_hash_cache().unwrap();
-    let mut cache = TreeHashCache::new(original).unwrap();
+fn test_standard_and_cached<T: CachedTreeHash<TreeHashCache>>(mut original: T, mut modified: T) {
+    let mut cache = TreeHashCache::default();
 
     let standard_root = original.tree_hash_root();
-    let cached_root = cache.tree_hash_root().unwrap();
-    assert_eq!(standard_root, cached_root);
+    let cached_root = original.recalculate_tree_hash_root(&mut cache).unwrap();
+    assert_eq!(standard_root, cached_root.as_bytes());
 
     // Test after a modification
-    cache.update(modified).unwrap();
     let standard_root = modified.tree_hash_root();
-    let cached_root = cache.tree_hash_root().unwrap();
-    assert_eq!(standard_root, cached_root);
+    let cached_root = modified.recalculate_tree_hash_root(&mut cache).unwrap();
+    assert_eq!(standard_root, cached_root.as_bytes());
 }
 
 #[test]
@@ -38,7 +38,7 @@ fn inner_standard_vs_cached() {
         ..original.clone()
     };
 
-    test_standard_and_cached(&original, &modified);
+    test_standard_and_cached(original, modified);
 }
 
 #[derive(Clone, Debug, TreeHash, CachedTreeHash)]
@@ -64,7 +64,63 @@ fn uneven_standard_vs_cached() {
         ..original.clone()
     };
 
-    test_standard_and_cached(&original, &modified);
+    test_standard_and_cached(original, modified);
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct OuterCache {
+    fields: TreeHashCache,
+    inners: ListTreeHashCache,
+    values: ListTreeHashCache,
+}
+
+#[derive(Clone, Debug, TreeHash, CachedTreeHash)]
+#[cached_tree_hash(cache = "OuterCache")]
+pub struct Outer {
+    pub a: u64,
+    #[cached_tree_hash(cached)]
+    pub inners: TrackedList<VariableList<Inner, U8>>,
+    #[cached_tree_hash(cached)]
+    pub values: TrackedList<VariableList<u64, U8>>,
+    #[tree_hash(skip_hashing)]
+    pub skipped: u64,
+}
+
+#[test]
+fn outer_standard_vs_cached() {
+    let inner = Inner {
+        a: 1,
+        b: 2,
+        c: 3,
+        d: 4,
+    };
+    let mut outer = Outer {
+        a: 0,
+        inners: vec![inner.clone(); 3].into(),
+        values: vec![1, 2, 3].into(),
+        skipped: 42,
+    };
+
+    let mut cache = OuterCache::default();
+
+    let mut check = |outer: &mut Outer| {
+        let root = outer.recalculate_tree_hash_root(&mut cache).unwrap();
+        assert_eq!(root.as_bytes(), &outer.tree_hash_root()[..]);
+    };
+
+    check(&mut outer);
+
+    outer.inners[1].c = 42;
+    check(&mut outer);
+
+    outer.inners.push(inner).unwrap();
+    outer.values.push(4).unwrap();
+    check(&mut outer);
+
+    outer.a = 7;
+    outer.skipped = 0;
+    outer.values = vec![].into();
+    check(&mut outer);
 }
 
 #[derive(Clone, Debug, TreeHash, SignedRoot)]
@@ -177,3 +233,195 @@ fn test_annotated_signed_root_derive() {
     let casper = Casper::new();
     assert_eq!(casper.signed_root(), casper.expected_signed_hash());
 }
+
+#[derive(Clone, Debug, TreeHash)]
+pub struct Validator {
+    pub pubkey: u64,
+    pub effective_balance: u64,
+    pub slashed: bool,
+}
+
+#[derive(Clone, Debug, TreeHash)]
+pub struct State {
+    pub slot: u64,
+    pub finalized_root: Vec<u8>,
+    pub validators: Vec<Validator>,
+    pub balances: Vec<u64>,
+    pub casper: Inner,
+}
+
+fn state() -> State {
+    State {
+        slot: 42,
+        finalized_root: vec![7; 32],
+        validators: (0..5)
+            .map(|i| Validator {
+                pubkey: i,
+                effective_balance: 32 + i,
+                slashed: i % 2 == 0,
+            })
+            .collect(),
+        balances: (0..9).collect(),
+        casper: Inner {
+            a: 1,
+            b: 2,
+            c: 3,
+            d: 4,
+        },
+    }
+}
+
+fn root<T: TreeHash>(item: &T) -> H256 {
+    H256::from_slice(&item.tree_hash_root())
+}
+
+#[test]
+fn field_proofs() {
+    let state = state();
+    let state_root = root(&state);
+
+    // Five fields are padded to eight leaves.
+    assert_eq!(state.tree_hash_gindex(&[PathElement::Field("slot")]), Ok(8));
+    assert_eq!(
+        state.tree_hash_gindex(&[PathElement::Field("casper")]),
+        Ok(12)
+    );
+
+    for (i, field) in ["slot", "finalized_root", "validators", "balances", "casper"]
+        .iter()
+        .enumerate()
+    {
+        let gindex = state
+            .tree_hash_gindex(&[PathElement::Field(field)])
+            .unwrap();
+        let proof = state.tree_hash_proof(gindex).unwrap();
+
+        assert_eq!(proof.gindex, 8 + i);
+        assert_eq!(proof.branch.len(), 3);
+        assert!(proof.verify(state_root));
+    }
+
+    let proof = state.tree_hash_proof(12).unwrap();
+    assert_eq!(proof.leaf, root(&state.casper));
+    assert!(!proof.verify(root(&state.casper)));
+}
+
+#[test]
+fn nested_proofs() {
+    let state = state();
+    let state_root = root(&state);
+
+    let path = [
+        PathElement::Field("validators"),
+        PathElement::Index(3),
+        PathElement::Field("effective_balance"),
+    ];
+    let gindex = state.tree_hash_gindex(&path).unwrap();
+    let proof = state.tree_hash_proof(gindex).unwrap();
+
+    assert_eq!(proof.leaf, root(&state.validators[3].effective_balance));
+    assert!(proof.verify(state_root));
+
+    // The proof of the validator is a suffix of the proof of its field.
+    let validator_gindex = state.tree_hash_gindex(&path[..2]).unwrap();
+    let validator_proof = state.tree_hash_proof(validator_gindex).unwrap();
+    assert_eq!(validator_proof.leaf, root(&state.validators[3]));
+    assert!(proof.branch.ends_with(&validator_proof.branch));
+
+    // Basic items are proven by the chunk containing them.
+    let path = [PathElement::Field("balances"), PathElement::Index(5)];
+    let proof = state
+        .tree_hash_proof(state.tree_hash_gindex(&path).unwrap())
+        .unwrap();
+    let mut chunk = [0; 32];
+    chunk[..8].copy_from_slice(&4_u64.to_le_bytes());
+    chunk[8..16].copy_from_slice(&5_u64.to_le_bytes());
+    chunk[16..24].copy_from_slice(&6_u64.to_le_bytes());
+    chunk[24..].copy_from_slice(&7_u64.to_le_bytes());
+    assert_eq!(proof.leaf, H256::from(chunk));
+    assert!(proof.verify(state_root));
+}
+
+#[test]
+fn list_length_proof() {
+    let state = state();
+
+    // The length of a list is the right child of its root.
+    let list_gindex = state
+        .tree_hash_gindex(&[PathElement::Field("validators")])
+        .unwrap();
+    let proof = state.tree_hash_proof(list_gindex * 2 + 1).unwrap();
+
+    assert_eq!(proof.leaf, root(&(state.validators.len() as u64)));
+    assert!(proof.verify(root(&state)));
+}
+
+#[test]
+fn multiproof() {
+    let state = state();
+    let state_root = root(&state);
+
+    let gindices: Vec<usize> = vec![
+        vec![PathElement::Field("slot")],
+        vec![PathElement::Field("finalized_root")],
+        vec![
+            PathElement::Field("validators"),
+            PathElement::Index(0),
+            PathElement::Field("slashed"),
+        ],
+        vec![PathElement::Field("casper"), PathElement::Field("d")],
+    ]
+    .iter()
+    .map(|path| state.tree_hash_gindex(path).unwrap())
+    .collect();
+
+    let proof = tree_hash::proof::multiproof(&state, &gindices).unwrap();
+    assert!(proof.verify(state_root));
+
+    let mut invalid = proof.clone();
+    invalid.leaves[0] = H256::zero();
+    assert!(!invalid.verify(state_root));
+}
+
+#[test]
+fn invalid_proofs() {
+    let state = state();
+
+    assert_eq!(
+        state.tree_hash_gindex(&[PathElement::Field("nonexistent")]),
+        Err(Error::InvalidPath(
+            "State has no field named nonexistent".to_string()
+        ))
+    );
+    assert!(state
+        .tree_hash_gindex(&[PathElement::Field("validators"), PathElement::Index(5)])
+        .is_err());
+    assert!(state
+        .tree_hash_gindex(&[PathElement::Field("slot"), PathElement::Index(0)])
+        .is_err());
+
+    // Padding leaves have no children.
+    assert_eq!(
+        state.tree_hash_proof(15 * 2),
+        Err(Error::InvalidGeneralizedIndex(30))
+    );
+    // Basic fields have no children.
+    assert_eq!(
+        state.tree_hash_proof(8 * 2),
+        Err(Error::InvalidGeneralizedIndex(16))
+    );
+    assert_eq!(
+        state.tree_hash_proof(0),
+        Err(Error::InvalidGeneralizedIndex(0))
+    );
+    assert_eq!(
+        tree_hash::proof::concat_gindices(2, 0),
+        Err(Error::InvalidGeneralizedIndex(0))
+    );
+
+    // The zero generalized index is not a node in any tree.
+    let mut proof = state.tree_hash_proof(1).unwrap();
+    assert!(proof.verify(root(&state)));
+    proof.gindex = 0;
+    assert!(!proof.verify(root(&state)));
+}
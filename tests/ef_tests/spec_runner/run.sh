@@ -0,0 +1,16 @@
+#!/usr/bin/env bash
+#
+# The `SPEC_RUNNER` for the differential tests. Requires `setup.sh` to have been run.
+#
+# Usage: run.sh <case_dir>
+
+set -euo pipefail
+
+DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+
+if [ ! -x "$DIR/venv/bin/python" ]; then
+	echo "The spec runner is not installed, run $DIR/setup.sh" >&2
+	exit 1
+fi
+
+exec "$DIR/venv/bin/python" "$DIR/run_case.py" "$@"
@@ -0,0 +1,90 @@
+"""
+Applies a single `sanity/blocks` or `sanity/slots` test case using the pyspec, as required by the
+`SpecRunner` of the Lighthouse differential tests (see `src/differential/spec_runner.rs`).
+
+Usage: run_case.py <case_dir>
+
+Reads `<case_dir>/config.yaml` and `<case_dir>/case.yaml`. If the transition is valid, the post
+state is written to `<case_dir>/post.yaml`, otherwise no file is written. Exits with a non-zero
+status only if the case could not be run.
+"""
+
+import sys
+from pathlib import Path
+
+import yaml
+
+from eth2spec.debug.decode import decode
+from eth2spec.debug.encode import encode
+from eth2spec.phase0 import spec
+
+
+def load_yaml(path):
+    with open(path) as f:
+        return yaml.safe_load(f)
+
+
+def apply_config(config):
+    """
+    Replaces the constants of the spec with those of `config`, a Lighthouse `YamlConfig`.
+    """
+    preset = {}
+    for key, value in config.items():
+        # Byte constants (e.g., `BLS_WITHDRAWAL_PREFIX_BYTE`) are 0x-prefixed hex strings.
+        if isinstance(value, str) and value.startswith('0x'):
+            value = bytes.fromhex(value[2:])
+        preset[key] = value
+    spec.apply_constants_preset(preset)
+
+
+def apply_bls_setting(bls_setting):
+    """
+    Enables or disables signature verification, as per the `bls_setting` of the test case.
+
+    Only possible if the pyspec verifies signatures at all.
+    """
+    try:
+        from eth2spec.utils import bls
+    except ImportError:
+        return
+    if hasattr(bls, 'bls_active'):
+        bls.bls_active = bls_setting != 2
+
+
+def run(case):
+    """
+    Returns the post state of the case, or `None` if the transition is invalid.
+    """
+    state = decode(case['pre'], spec.BeaconState)
+
+    try:
+        if 'blocks' in case:
+            for block in case['blocks']:
+                spec.state_transition(state, decode(block, spec.BeaconBlock))
+        else:
+            spec.process_slots(state, state.slot + case['slots'])
+    except (AssertionError, IndexError):
+        # The spec signals an invalid transition with a failed assertion or an out-of-bounds
+        # list access.
+        return None
+
+    return state
+
+
+def main():
+    case_dir = Path(sys.argv[1])
+
+    apply_config(load_yaml(case_dir / 'config.yaml'))
+
+    case = load_yaml(case_dir / 'case.yaml')['test_cases'][0]
+    apply_bls_setting(case['bls_setting'])
+
+    post = run(case)
+
+    if post is not None:
+        with open(case_dir / 'post.yaml', 'w') as f:
+            yaml.safe_dump(encode(post, spec.BeaconState), f)
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,36 @@
+#!/usr/bin/env bash
+#
+# Builds the executable spec (the "pyspec") from the pinned `eth2.0-specs` submodule and installs
+# it, along with the dependencies of `run_case.py`, into a virtualenv beside this script.
+#
+# Safe to run repeatedly; the existing virtualenv is reused.
+
+set -euo pipefail
+
+# Must match the version of the spec implemented by Lighthouse (see the `Spec v0.6.3` comments).
+SPEC_VERSION="v0.6.3"
+
+DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+SPECS_DIR="$DIR/eth2.0-specs"
+VENV_DIR="$DIR/venv"
+
+if [ ! -f "$SPECS_DIR/Makefile" ]; then
+	echo "The eth2.0-specs submodule is not checked out, run: git submodule update --init $SPECS_DIR" >&2
+	exit 1
+fi
+
+if [ "$(git -C "$SPECS_DIR" describe --tags --exact-match HEAD 2>/dev/null)" != "$SPEC_VERSION" ]; then
+	echo "The eth2.0-specs submodule is not at $SPEC_VERSION, run: git submodule update $SPECS_DIR" >&2
+	exit 1
+fi
+
+# The pyspec is generated from the markdown spec.
+make -C "$SPECS_DIR" pyspec
+
+if [ ! -d "$VENV_DIR" ]; then
+	python3 -m venv "$VENV_DIR"
+fi
+
+"$VENV_DIR/bin/pip" install -r "$SPECS_DIR/test_libs/pyspec/requirements.txt"
+"$VENV_DIR/bin/pip" install -e "$SPECS_DIR/test_libs/pyspec"
+"$VENV_DIR/bin/pip" install -r "$DIR/requirements.txt"
@@ -72,6 +72,24 @@ where
     }
 }
 
+/// Returns the first field of `expected` which differs from `result`, retaining only the first
+/// differing element if that field is a list.
+pub fn first_diverging_field<T: CompareFields>(expected: &T, result: &T) -> Option<Comparison> {
+    let mut field = expected
+        .compare_fields(result)
+        .into_iter()
+        .find(Comparison::not_equal)?;
+
+    let mut found = false;
+    field.retain_children(|child| {
+        let first = !found && child.not_equal();
+        found |= first;
+        first
+    });
+
+    Some(field)
+}
+
 /// Compares `result` with `expected`.
 ///
 /// If `expected.is_none()` then `result` is expected to be `Err`. Otherwise, `T` in `result` and
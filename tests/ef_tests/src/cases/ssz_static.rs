@@ -1,6 +1,6 @@
 use super::*;
 use crate::case_result::compare_result;
-use cached_tree_hash::{CachedTreeHash, TreeHashCache};
+use cached_tree_hash::CachedTreeHash;
 use serde_derive::Deserialize;
 use ssz::{Decode, Encode};
 use std::fmt::Debug;
@@ -90,7 +90,7 @@ impl<E: EthSpec> Case for SszStatic<E> {
     }
 }
 
-fn ssz_static_test<T, E: EthSpec>(tc: &SszStaticInner<T, E>) -> Result<(), Error>
+fn ssz_static_test<T, C, E: EthSpec>(tc: &SszStaticInner<T, E>) -> Result<(), Error>
 where
     T: Clone
         + Decode
@@ -99,8 +99,9 @@ where
         + PartialEq<T>
         + serde::de::DeserializeOwned
         + TreeHash
-        + CachedTreeHash
+        + CachedTreeHash<C>
         + TestRandom,
+    C: Default,
 {
     // Verify we can decode SSZ in the same way we can decode YAML.
     let ssz = hex::decode(&tc.serialized[2..])
@@ -110,29 +111,35 @@ where
     compare_result(&decode_result, &Some(expected))?;
 
     // Verify we can encode the result back into original ssz bytes
-    let decoded = decode_result.unwrap();
+    let mut decoded = decode_result.unwrap();
     let encoded_result = decoded.as_ssz_bytes();
     compare_result::<Vec<u8>, Error>(&Ok(encoded_result), &Some(ssz))?;
 
     // Verify the TreeHash root of the decoded struct matches the test.
-    let expected_root =
-        &hex::decode(&tc.root[2..]).map_err(|e| Error::FailedToParseTest(format!("{:?}", e)))?;
-    let expected_root = Hash256::from_slice(&expected_root);
+    let expected_root = expected_root(tc)?;
     let tree_hash_root = Hash256::from_slice(&decoded.tree_hash_root());
     compare_result::<Hash256, Error>(&Ok(tree_hash_root), &Some(expected_root))?;
 
     // Verify a _new_ CachedTreeHash root of the decoded struct matches the test.
-    let cache = TreeHashCache::new(&decoded).unwrap();
-    let cached_tree_hash_root = Hash256::from_slice(cache.tree_hash_root().unwrap());
+    let mut cache = C::default();
+    let cached_tree_hash_root = decoded.recalculate_tree_hash_root(&mut cache).unwrap();
     compare_result::<Hash256, Error>(&Ok(cached_tree_hash_root), &Some(expected_root))?;
 
     // Verify the root after an update from a random CachedTreeHash to the decoded struct.
     let mut rng = XorShiftRng::from_seed([42; 16]);
-    let random_instance = T::random_for_test(&mut rng);
-    let mut cache = TreeHashCache::new(&random_instance).unwrap();
-    cache.update(&decoded).unwrap();
-    let updated_root = Hash256::from_slice(cache.tree_hash_root().unwrap());
+    let mut random_instance = T::random_for_test(&mut rng);
+    let mut cache = C::default();
+    random_instance
+        .recalculate_tree_hash_root(&mut cache)
+        .unwrap();
+    let updated_root = decoded.recalculate_tree_hash_root(&mut cache).unwrap();
     compare_result::<Hash256, Error>(&Ok(updated_root), &Some(expected_root))?;
 
     Ok(())
 }
+
+fn expected_root<T, E: EthSpec>(tc: &SszStaticInner<T, E>) -> Result<Hash256, Error> {
+    let root =
+        hex::decode(&tc.root[2..]).map_err(|e| Error::FailedToParseTest(format!("{:?}", e)))?;
+    Ok(Hash256::from_slice(&root))
+}
@@ -0,0 +1,117 @@
+use crate::case_result::{compare_result, first_diverging_field};
+use crate::error::Error;
+use state_processing::{per_block_processing, per_slot_processing};
+use std::path::PathBuf;
+use types::{BeaconBlock, BeaconState, ChainSpec, EthSpec};
+
+pub use random_transition::RandomTransition;
+pub use spec_runner::{SpecRunner, SPEC_RUNNER_ENV};
+
+mod random_transition;
+mod spec_runner;
+
+/// A transition to apply to a `BeaconState`, as per the `sanity` tests of `eth2.0-spec-tests`.
+#[derive(Debug, Clone)]
+pub enum Transition {
+    /// Process each block, processing any slots in between.
+    Blocks(Vec<BeaconBlock>),
+    /// Process the given number of slots (including any epoch transitions).
+    Slots(u64),
+}
+
+/// A randomly generated transition which is applied by both Lighthouse and a `SpecRunner`, such
+/// that any difference in the results can be reported.
+#[derive(Debug, Clone)]
+pub struct DifferentialCase<E: EthSpec> {
+    pub description: String,
+    pub pre: BeaconState<E>,
+    pub transition: Transition,
+}
+
+impl<E: EthSpec> DifferentialCase<E> {
+    /// A single random block, see `RandomTransition::block`.
+    pub fn random_block(seed: u64, validator_count: usize, spec: &ChainSpec) -> Self {
+        let (pre, block) = RandomTransition::new(seed, validator_count).block(spec);
+
+        Self {
+            description: format!("random_block_{}", seed),
+            pre,
+            transition: Transition::Blocks(vec![block]),
+        }
+    }
+
+    /// A single slot which ends an epoch, see `RandomTransition::epoch_transition_state`.
+    pub fn random_epoch_transition(seed: u64, validator_count: usize, spec: &ChainSpec) -> Self {
+        let pre = RandomTransition::new(seed, validator_count).epoch_transition_state(spec);
+
+        Self {
+            description: format!("random_epoch_transition_{}", seed),
+            pre,
+            transition: Transition::Slots(1),
+        }
+    }
+
+    /// Applies the transition to `self.pre` using `per_block_processing` and
+    /// `per_slot_processing`.
+    pub fn lighthouse_result(&self, spec: &ChainSpec) -> Result<BeaconState<E>, String> {
+        let mut state = self.pre.clone();
+
+        // Processing requires the epoch cache.
+        state
+            .build_all_caches(spec)
+            .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+
+        match &self.transition {
+            Transition::Blocks(blocks) => blocks.iter().try_for_each(|block| {
+                while state.slot < block.slot {
+                    per_slot_processing(&mut state, spec).map_err(|e| format!("{:?}", e))?;
+                }
+                per_block_processing(&mut state, block, spec).map_err(|e| format!("{:?}", e))
+            }),
+            Transition::Slots(slots) => (0..*slots).try_for_each(|_| {
+                per_slot_processing(&mut state, spec).map_err(|e| format!("{:?}", e))
+            }),
+        }
+        .map(|_| state)
+    }
+
+    /// Applies the transition with both Lighthouse and `runner`, returning an error if their
+    /// results differ.
+    ///
+    /// If both produce a state, the error describes the first field of the state which differs.
+    pub fn result(&self, runner: &SpecRunner, spec: &ChainSpec) -> Result<(), Error> {
+        let case_dir = self.case_dir();
+
+        let mut expected = runner.run(
+            &self.description,
+            &self.pre,
+            &self.transition,
+            &case_dir,
+            spec,
+        )?;
+        let mut result = self.lighthouse_result(spec);
+
+        match (result.as_mut(), expected.as_mut()) {
+            (Ok(result), Some(expected)) => {
+                result.drop_all_caches();
+                expected.drop_all_caches();
+
+                match first_diverging_field(expected, result) {
+                    Some(field) => Err(Error::NotEqual(format!(
+                        "First diverging field in {:?} (a = reference, b = lighthouse): {:#?}",
+                        case_dir, field
+                    ))),
+                    None => Ok(()),
+                }
+            }
+            _ => compare_result(&result, &expected),
+        }
+    }
+
+    /// The directory in which the files for the `SpecRunner` are written.
+    fn case_dir(&self) -> PathBuf {
+        std::env::temp_dir()
+            .join("lighthouse_differential")
+            .join(&self.description)
+    }
+}
@@ -0,0 +1,180 @@
+use tree_hash::SignedRoot;
+use types::test_utils::{
+    RngCore, SeedableRng, TestingBeaconBlockBuilder, TestingBeaconStateBuilder, XorShiftRng,
+};
+use types::*;
+
+/// Generates random, valid transitions from random `BeaconState`s.
+///
+/// The states are produced by `TestingBeaconStateBuilder` and teleported past the
+/// `persistent_committee_period`, so that every validator is eligible to exit.
+///
+/// Deposits are never produced: `TestingDepositBuilder` does not build a merkle proof against
+/// the `latest_eth1_data`.
+pub struct RandomTransition {
+    rng: XorShiftRng,
+    validator_count: usize,
+}
+
+impl RandomTransition {
+    /// Instantiates a generator which will always produce the same transitions for `seed`.
+    pub fn new(seed: u64, validator_count: usize) -> Self {
+        let mut seed_bytes = [42; 16];
+        seed_bytes[0..8].copy_from_slice(&seed.to_le_bytes());
+
+        Self {
+            rng: XorShiftRng::from_seed(seed_bytes),
+            validator_count,
+        }
+    }
+
+    /// Returns a state and a signed block for the slot of that state, containing a random
+    /// selection of proposer slashings, attester slashings, attestations, exits and transfers.
+    ///
+    /// Each validator is the subject of at most one slashing, exit or transfer.
+    pub fn block<E: EthSpec>(&mut self, spec: &ChainSpec) -> (BeaconState<E>, BeaconBlock) {
+        // Any slot after the first of the epoch, leaving room for attestations.
+        let slot_in_epoch = 1 + self.below(E::slots_per_epoch() - 1);
+        let (mut state, keypairs) = self.state::<E>(slot_in_epoch, spec);
+
+        let mut builder = TestingBeaconBlockBuilder::new(spec);
+        builder.set_slot(state.slot);
+        builder.set_previous_block_root(Hash256::from_slice(
+            &state.latest_block_header.signed_root(),
+        ));
+
+        let proposer_index = state
+            .get_beacon_proposer_index(state.slot, RelativeEpoch::Current, spec)
+            .expect("Should get proposer index");
+        let proposer_sk = &keypairs[proposer_index].sk;
+        builder.set_randao_reveal::<E>(proposer_sk, &state.fork, spec);
+
+        // A random stream of distinct validator indices for use in slashings, exits, etc.
+        let mut validators = self.shuffled_indices(keypairs.len()).into_iter();
+
+        for _ in 0..self.below(spec.max_proposer_slashings + 1) {
+            if let Some(i) = validators.next() {
+                builder.insert_proposer_slashing::<E>(
+                    i,
+                    &keypairs[i as usize].sk,
+                    &state.fork,
+                    spec,
+                );
+            }
+        }
+
+        for _ in 0..self.below(spec.max_attester_slashings + 1) {
+            let mut attesters: Vec<u64> = (0..1 + self.below(4))
+                .filter_map(|_| validators.next())
+                .collect();
+            attesters.sort();
+
+            if !attesters.is_empty() {
+                let secret_keys: Vec<&SecretKey> = attesters
+                    .iter()
+                    .map(|&i| &keypairs[i as usize].sk)
+                    .collect();
+                builder.insert_attester_slashing(&attesters, &secret_keys, &state.fork, spec);
+            }
+        }
+
+        // Ask for roughly one attestation per committee in range. Asking for many more would make
+        // the builder split committees until some attestations have no signers.
+        let num_attestations =
+            std::cmp::min(self.below(E::slots_per_epoch() + 1), spec.max_attestations);
+        let all_secret_keys: Vec<&SecretKey> = keypairs.iter().map(|keypair| &keypair.sk).collect();
+        builder
+            .insert_attestations(&state, &all_secret_keys, num_attestations as usize, spec)
+            .expect("Should insert attestations");
+
+        for _ in 0..self.below(spec.max_voluntary_exits + 1) {
+            if let Some(i) = validators.next() {
+                builder.insert_exit(&state, i, &keypairs[i as usize].sk, spec);
+            }
+        }
+
+        for _ in 0..self.below(spec.max_transfers + 1) {
+            if let Some(i) = validators.next() {
+                // Transfers are only possible from withdrawable validators.
+                state.validator_registry[i as usize].withdrawable_epoch = state.previous_epoch();
+
+                builder.insert_transfer(&state, i, i, 1, keypairs[i as usize].clone(), spec);
+            }
+        }
+
+        let block = builder.build::<E>(proposer_sk, &state.fork, spec);
+
+        (state, block)
+    }
+
+    /// Returns a state in the last slot of an epoch with random balances and a random subset of
+    /// its pending attestations, such that processing one slot runs `per_epoch_processing`.
+    pub fn epoch_transition_state<E: EthSpec>(&mut self, spec: &ChainSpec) -> BeaconState<E> {
+        let (mut state, _keypairs) = self.state::<E>(E::slots_per_epoch() - 1, spec);
+
+        // Drop pending attestations so that justification, finalization and rewards vary.
+        state.previous_epoch_attestations = self.subset(&state.previous_epoch_attestations).into();
+        state.current_epoch_attestations = self.subset(&state.current_epoch_attestations).into();
+
+        // Move some balances around the ejection and effective balance thresholds.
+        for i in 0..state.balances.len() {
+            if self.below(8) == 0 {
+                let balance = spec.ejection_balance - spec.effective_balance_increment
+                    + self.below(spec.max_effective_balance);
+                state.balances[i] = balance;
+            }
+        }
+
+        state.drop_all_caches();
+        state
+    }
+
+    /// Returns a state at `slot_in_epoch` of some epoch after the `persistent_committee_period`,
+    /// with all caches built.
+    ///
+    /// If `slot_in_epoch` is non-zero, the state randomly contains a full set of pending
+    /// attestations for the previous and current epochs.
+    fn state<E: EthSpec>(
+        &mut self,
+        slot_in_epoch: u64,
+        spec: &ChainSpec,
+    ) -> (BeaconState<E>, Vec<Keypair>) {
+        let epoch = E::genesis_epoch() + spec.persistent_committee_period + 1 + self.below(4);
+
+        let mut builder =
+            TestingBeaconStateBuilder::from_deterministic_keypairs(self.validator_count, spec);
+        builder.teleport_to_slot(epoch.start_slot(E::slots_per_epoch()) + slot_in_epoch);
+        if slot_in_epoch > 0 && self.below(2) == 0 {
+            builder.insert_attestations(spec);
+        }
+        builder.build_caches(spec).expect("Should build caches");
+
+        builder.build()
+    }
+
+    /// Returns the validator indices `0..count` in a random order.
+    fn shuffled_indices(&mut self, count: usize) -> Vec<u64> {
+        let mut indices: Vec<u64> = (0..count as u64).collect();
+
+        for i in (1..indices.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            indices.swap(i, j);
+        }
+
+        indices
+    }
+
+    /// Returns a random subset of `items`, keeping roughly three quarters of them.
+    fn subset<T: Clone>(&mut self, items: &[T]) -> Vec<T> {
+        items
+            .iter()
+            .filter(|_| self.below(4) != 0)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns a random integer in `0..n`.
+    fn below(&mut self, n: u64) -> u64 {
+        self.rng.next_u64() % n
+    }
+}
@@ -0,0 +1,156 @@
+use super::Transition;
+use crate::error::Error;
+use serde_derive::Serialize;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use types::{BeaconBlock, BeaconState, ChainSpec, EthSpec, YamlConfig};
+
+/// The environment variable which points to the reference runner executable.
+pub const SPEC_RUNNER_ENV: &str = "SPEC_RUNNER";
+
+/// An executable which applies transitions using the executable spec, such as
+/// `tests/ef_tests/spec_runner/run.sh` (a wrapper around a pinned copy of the pyspec).
+///
+/// The runner is invoked as `<runner> <case_dir>`, where `case_dir` contains:
+///
+/// - `config.yaml`: the constants to use, as a `YamlConfig`.
+/// - `case.yaml`: a single document in the `eth2.0-spec-tests` format with one test case, which
+///   has a `pre` state but no `post` state (i.e., a `sanity/blocks` or `sanity/slots` test).
+///
+/// If the transition is valid, the runner must write the resulting state to `case_dir/post.yaml`.
+/// If the transition is invalid, it must not write `post.yaml`. In both cases it must exit
+/// successfully; any other exit status is treated as a failure of the runner itself.
+#[derive(Debug, Clone)]
+pub struct SpecRunner {
+    program: PathBuf,
+}
+
+#[derive(Serialize)]
+struct CaseFile<'a, E: EthSpec> {
+    title: &'static str,
+    summary: &'static str,
+    forks_timeline: &'static str,
+    forks: Vec<&'static str>,
+    config: &'static str,
+    runner: &'static str,
+    handler: &'static str,
+    #[serde(bound = "E: EthSpec")]
+    test_cases: Vec<CaseYaml<'a, E>>,
+}
+
+#[derive(Serialize)]
+struct CaseYaml<'a, E: EthSpec> {
+    description: String,
+    bls_setting: u8,
+    #[serde(bound = "E: EthSpec")]
+    pre: &'a BeaconState<E>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocks: Option<&'a [BeaconBlock]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slots: Option<u64>,
+}
+
+impl SpecRunner {
+    /// Returns a runner for the executable at `program`.
+    pub fn new(program: PathBuf) -> Self {
+        Self { program }
+    }
+
+    /// Returns the runner named by the `SPEC_RUNNER` environment variable, if it is set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(SPEC_RUNNER_ENV).map(|program| Self::new(program.into()))
+    }
+
+    /// Applies `transition` to `pre`, returning `None` if the runner considered the transition
+    /// invalid.
+    ///
+    /// The case files are written to `case_dir`, which is created if required and left in place
+    /// for debugging.
+    pub fn run<E: EthSpec>(
+        &self,
+        description: &str,
+        pre: &BeaconState<E>,
+        transition: &Transition,
+        case_dir: &Path,
+        spec: &ChainSpec,
+    ) -> Result<Option<BeaconState<E>>, Error> {
+        let (handler, blocks, slots) = match transition {
+            Transition::Blocks(blocks) => ("blocks", Some(&blocks[..]), None),
+            Transition::Slots(slots) => ("slots", None, Some(*slots)),
+        };
+
+        let case = CaseFile {
+            title: "Differential test",
+            summary: "Generated by the Lighthouse differential harness",
+            forks_timeline: "testing",
+            forks: vec!["phase0"],
+            config: config_name(spec),
+            runner: "sanity",
+            handler,
+            test_cases: vec![CaseYaml {
+                description: description.to_string(),
+                // Fake crypto produces invalid signatures.
+                bls_setting: if cfg!(feature = "fake_crypto") { 2 } else { 1 },
+                pre,
+                blocks,
+                slots,
+            }],
+        };
+
+        let post_path = case_dir.join("post.yaml");
+        fs::create_dir_all(case_dir).map_err(runner_error)?;
+        if post_path.exists() {
+            fs::remove_file(&post_path).map_err(runner_error)?;
+        }
+
+        write_yaml(
+            &case_dir.join("config.yaml"),
+            &YamlConfig::from_spec::<E>(spec),
+        )?;
+        write_yaml(&case_dir.join("case.yaml"), &case)?;
+
+        let output = Command::new(&self.program)
+            .arg(case_dir)
+            .output()
+            .map_err(|e| {
+                Error::SpecRunnerFailed(format!("Unable to run {:?}: {:?}", self.program, e))
+            })?;
+
+        if !output.status.success() {
+            return Err(Error::SpecRunnerFailed(format!(
+                "{:?} exited with {}: {}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        if post_path.exists() {
+            let file = File::open(&post_path).map_err(runner_error)?;
+            serde_yaml::from_reader(file).map(Some).map_err(|e| {
+                Error::SpecRunnerFailed(format!("Unable to decode post state: {:?}", e))
+            })
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the name of the spec test config which matches `spec`.
+fn config_name(spec: &ChainSpec) -> &'static str {
+    if *spec == ChainSpec::minimal() {
+        "minimal"
+    } else {
+        "mainnet"
+    }
+}
+
+fn write_yaml<T: serde::Serialize>(path: &Path, value: &T) -> Result<(), Error> {
+    let file = File::create(path).map_err(runner_error)?;
+    serde_yaml::to_writer(file, value).map_err(runner_error)
+}
+
+fn runner_error<E: std::fmt::Debug>(e: E) -> Error {
+    Error::SpecRunnerFailed(format!("{:?}", e))
+}
@@ -10,6 +10,8 @@ pub enum Error {
     SkippedBls,
     /// Skipped the test because it's known to fail.
     SkippedKnownFailure,
+    /// The reference implementation failed to run the test (internal error).
+    SpecRunnerFailed(String),
 }
 
 impl Error {
@@ -20,6 +22,7 @@ impl Error {
             Error::FailedToParseTest(_) => "FailedToParseTest",
             Error::SkippedBls => "SkippedBls",
             Error::SkippedKnownFailure => "SkippedKnownFailure",
+            Error::SpecRunnerFailed(_) => "SpecRunnerFailed",
         }
     }
 
@@ -28,6 +31,7 @@ impl Error {
             Error::NotEqual(m) => m.as_str(),
             Error::DidntFail(m) => m.as_str(),
             Error::FailedToParseTest(m) => m.as_str(),
+            Error::SpecRunnerFailed(m) => m.as_str(),
             _ => self.name(),
         }
     }
@@ -2,6 +2,9 @@ use types::EthSpec;
 
 pub use case_result::CaseResult;
 pub use cases::Case;
+pub use differential::{
+    DifferentialCase, RandomTransition, SpecRunner, Transition, SPEC_RUNNER_ENV,
+};
 pub use doc::Doc;
 pub use error::Error;
 pub use yaml_decode::YamlDecode;
@@ -9,6 +12,7 @@ pub use yaml_decode::YamlDecode;
 mod bls_setting;
 mod case_result;
 mod cases;
+mod differential;
 mod doc;
 mod doc_header;
 mod error;
@@ -1,8 +1,16 @@
 use ef_tests::*;
 use rayon::prelude::*;
+use std::fs::File;
 use std::path::{Path, PathBuf};
+use types::{ChainSpec, EthSpec, MainnetEthSpec, MinimalEthSpec, YamlConfig};
 use walkdir::WalkDir;
 
+/// The number of random transitions of each kind to check against the `SPEC_RUNNER`.
+const DIFFERENTIAL_CASES: u64 = 32;
+
+/// The number of validators in each random state.
+const DIFFERENTIAL_VALIDATOR_COUNT: usize = 64;
+
 fn yaml_files_in_test_dir(dir: &Path) -> Vec<PathBuf> {
     let base_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("eth2.0-spec-tests")
@@ -40,6 +48,31 @@ fn yaml_files_in_test_dir(dir: &Path) -> Vec<PathBuf> {
     paths
 }
 
+/// Generates `DIFFERENTIAL_CASES` random cases and asserts that Lighthouse agrees with the
+/// reference implementation on each.
+///
+/// Panics if the `SPEC_RUNNER` environment variable is not set, see `spec_runner/README.md`.
+fn assert_differential_cases_pass<F>(generate: F)
+where
+    F: Fn(u64, &ChainSpec) -> DifferentialCase<MinimalEthSpec> + Sync,
+{
+    let runner = SpecRunner::from_env().unwrap_or_else(|| {
+        panic!(
+            "{} is not set. Run spec_runner/setup.sh and set {} to spec_runner/run.sh.",
+            SPEC_RUNNER_ENV, SPEC_RUNNER_ENV
+        )
+    });
+    let spec = MinimalEthSpec::default_spec();
+
+    (0..DIFFERENTIAL_CASES).into_par_iter().for_each(|seed| {
+        let case = generate(seed, &spec);
+
+        if let Err(e) = case.result(&runner, &spec) {
+            panic!("{} failed: {}: {}", case.description, e.name(), e.message());
+        }
+    });
+}
+
 #[test]
 #[cfg(feature = "fake_crypto")]
 fn ssz_generic() {
@@ -178,3 +211,53 @@ fn epoch_processing_registry_updates() {
             Doc::assert_tests_pass(file);
         });
 }
+
+#[test]
+#[ignore]
+fn differential_blocks() {
+    assert_differential_cases_pass(|seed, spec| {
+        DifferentialCase::random_block(seed, DIFFERENTIAL_VALIDATOR_COUNT, spec)
+    });
+}
+
+#[test]
+#[ignore]
+fn differential_epoch_transitions() {
+    assert_differential_cases_pass(|seed, spec| {
+        DifferentialCase::random_epoch_transition(seed, DIFFERENTIAL_VALIDATOR_COUNT, spec)
+    });
+}
+
+/// Asserts that `spec` (and the compile-time constants of `E`) match the constant preset `name`
+/// of the specification.
+///
+/// Skipped if the preset is not present (e.g., the submodule is not initialized).
+fn assert_preset_matches<E: EthSpec>(name: &str, spec: ChainSpec) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("eth2.0-spec-tests")
+        .join("configs")
+        .join("constant_presets")
+        .join(format!("{}.yaml", name));
+
+    if !path.exists() {
+        eprintln!("Skipping {} preset, unable to locate {:?}", name, path);
+        return;
+    }
+
+    let file = File::open(&path).unwrap_or_else(|e| panic!("Unable to open {:?}: {:?}", path, e));
+    let config: YamlConfig = serde_yaml::from_reader(file)
+        .unwrap_or_else(|e| panic!("Unable to parse {:?}: {:?}", path, e));
+
+    assert_eq!(config, YamlConfig::from_spec::<E>(&spec));
+    assert_eq!(config.apply_to_chain_spec::<E>(&spec), Ok(spec));
+}
+
+#[test]
+fn minimal_preset() {
+    assert_preset_matches::<MinimalEthSpec>("minimal", ChainSpec::minimal());
+}
+
+#[test]
+fn mainnet_preset() {
+    assert_preset_matches::<MainnetEthSpec>("mainnet", ChainSpec::mainnet());
+}
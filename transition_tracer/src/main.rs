@@ -0,0 +1,103 @@
+mod trace;
+
+use clap::{App, Arg};
+use slog::{crit, info, o, Drain};
+use std::path::PathBuf;
+use trace::Config;
+use types::{MainnetEthSpec, MinimalEthSpec};
+
+fn main() {
+    // Logging
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::CompactFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    let log = slog::Logger::root(drain, o!());
+
+    // CLI
+    let matches = App::new("Lighthouse Transition Tracer")
+        .version("0.0.1")
+        .author("Sigma Prime <contact@sigmaprime.io>")
+        .about("Replays a block atop a pre-state, step by step, to triage state transition bugs")
+        .arg(
+            Arg::with_name("pre-state")
+                .long("pre-state")
+                .value_name("SSZ_FILE")
+                .help("Path to the SSZ-encoded BeaconState upon which the block is applied.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("block")
+                .long("block")
+                .value_name("SSZ_FILE")
+                .help("Path to the SSZ-encoded BeaconBlock to apply.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("expected-state")
+                .long("expected-state")
+                .value_name("SSZ_FILE")
+                .help("Path to an SSZ-encoded BeaconState to diff against the post-state.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("output-dir")
+                .long("output-dir")
+                .short("o")
+                .value_name("DIR")
+                .help("Directory in which to write the SSZ-encoded state after each step.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("spec-constants")
+                .long("spec-constants")
+                .value_name("TITLE")
+                .short("s")
+                .help("The title of the spec constants for chain config.")
+                .takes_value(true)
+                .possible_values(&["mainnet", "minimal"])
+                .default_value("minimal"),
+        )
+        .arg(
+            Arg::with_name("no-signature-verification")
+                .long("no-signature-verification")
+                .help("Skip verification of all signatures in the block.")
+                .takes_value(false),
+        )
+        .get_matches();
+
+    let config = Config {
+        pre_state_path: PathBuf::from(matches.value_of("pre-state").expect("Required arg")),
+        block_path: PathBuf::from(matches.value_of("block").expect("Required arg")),
+        expected_state_path: matches.value_of("expected-state").map(PathBuf::from),
+        output_dir: matches.value_of("output-dir").map(PathBuf::from),
+        verify_signatures: !matches.is_present("no-signature-verification"),
+    };
+
+    let spec_constants = matches
+        .value_of("spec-constants")
+        .expect("Has default value");
+
+    info!(
+        log,
+        "Starting transition tracer";
+        "pre_state" => format!("{:?}", config.pre_state_path),
+        "block" => format!("{:?}", config.block_path),
+        "spec_constants" => spec_constants,
+    );
+
+    let result = match spec_constants {
+        "mainnet" => trace::run::<MainnetEthSpec>(&config, &log),
+        "minimal" => trace::run::<MinimalEthSpec>(&config, &log),
+        other => {
+            crit!(log, "Unknown spec constants"; "title" => other);
+            return;
+        }
+    };
+
+    match result {
+        Ok(()) => info!(log, "Trace complete"),
+        Err(e) => crit!(log, "Trace failed"; "error" => e),
+    }
+}
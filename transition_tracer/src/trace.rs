@@ -0,0 +1,251 @@
+use compare_fields::{CompareFields, Comparison, FieldComparison};
+use slog::{info, warn, Logger};
+use ssz::{Decode, Encode};
+use state_processing::per_block_processing::{
+    process_block_body_step, process_block_header, BlockBodyStep, VerifySignatures,
+    BLOCK_BODY_STEPS,
+};
+use state_processing::per_slot_processing;
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::io::{prelude::*, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use types::{BeaconBlock, BeaconState, EthSpec};
+
+/// The inputs to a single trace.
+pub struct Config {
+    pub pre_state_path: PathBuf,
+    pub block_path: PathBuf,
+    pub expected_state_path: Option<PathBuf>,
+    /// If `Some`, the state is written to this directory after each step.
+    pub output_dir: Option<PathBuf>,
+    pub verify_signatures: bool,
+}
+
+/// Runs each step of the transition in sequence, logging the state root and (optionally) writing
+/// the state to disk after each.
+struct Tracer<'a> {
+    output_dir: Option<&'a Path>,
+    log: &'a Logger,
+    steps: usize,
+}
+
+impl<'a> Tracer<'a> {
+    /// Apply `step` to `state`, then record the result under `name`.
+    fn step<E, F, T>(
+        &mut self,
+        name: &str,
+        state: &mut BeaconState<E>,
+        step: F,
+    ) -> Result<(), String>
+    where
+        E: EthSpec,
+        F: FnOnce(&mut BeaconState<E>) -> Result<(), T>,
+        T: Debug,
+    {
+        let result = step(state).map_err(|e| format!("{} failed: {:?}", name, e));
+
+        // The state is recorded even if the step failed, it may have been partially modified.
+        self.record(name, state)?;
+
+        result
+    }
+
+    /// Log the root of `state` and write it to `self.output_dir`, if any.
+    fn record<E: EthSpec>(&mut self, name: &str, state: &BeaconState<E>) -> Result<(), String> {
+        let file_name = format!("{:03}_{}.ssz", self.steps, name);
+        self.steps += 1;
+
+        info!(
+            self.log,
+            "Step complete";
+            "step" => name,
+            "slot" => state.slot.as_u64(),
+            "state_root" => format!("{:?}", state.canonical_root()),
+        );
+
+        if let Some(dir) = self.output_dir {
+            write_ssz(&dir.join(file_name), state)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Load the pre-state and block described by `config`, apply the block step by step and diff the
+/// resulting state against the expected state (if any).
+pub fn run<E: EthSpec>(config: &Config, log: &Logger) -> Result<(), String> {
+    let spec = &E::default_spec();
+
+    let mut state: BeaconState<E> = read_ssz(&config.pre_state_path)?;
+    let block: BeaconBlock = read_ssz(&config.block_path)?;
+
+    if let Some(dir) = &config.output_dir {
+        fs::create_dir_all(dir).map_err(|e| format!("Unable to create {:?}: {:?}", dir, e))?;
+    }
+
+    let verify_signatures = if config.verify_signatures {
+        VerifySignatures::True
+    } else {
+        VerifySignatures::False
+    };
+
+    let mut tracer = Tracer {
+        output_dir: config.output_dir.as_ref().map(PathBuf::as_path),
+        log,
+        steps: 0,
+    };
+
+    tracer.record("pre_state", &state)?;
+
+    state
+        .build_all_caches(spec)
+        .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+
+    while state.slot < block.slot {
+        let name = format!("per_slot_processing_{}", state.slot.as_u64());
+        tracer.step(&name, &mut state, |state| per_slot_processing(state, spec))?;
+    }
+
+    state
+        .build_all_caches(spec)
+        .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+
+    tracer.step("process_block_header", &mut state, |state| {
+        process_block_header(state, &block, spec, false)
+    })?;
+    for &step in BLOCK_BODY_STEPS.iter() {
+        if step == BlockBodyStep::BlockSignature && !config.verify_signatures {
+            continue;
+        }
+
+        tracer.step(step.name(), &mut state, |state| {
+            process_block_body_step(state, &block, step, verify_signatures, spec)
+        })?;
+    }
+
+    let state_root = state.canonical_root();
+    if state_root == block.state_root {
+        info!(log, "Post-state root matches block"; "state_root" => format!("{:?}", state_root));
+    } else {
+        warn!(
+            log,
+            "Post-state root does not match block";
+            "computed" => format!("{:?}", state_root),
+            "block" => format!("{:?}", block.state_root),
+        );
+    }
+
+    if let Some(path) = &config.expected_state_path {
+        let mut expected: BeaconState<E> = read_ssz(path)?;
+
+        let mismatches = diff_states(&mut expected, &mut state);
+
+        if mismatches.is_empty() {
+            info!(log, "Post-state matches expected state");
+        } else {
+            warn!(log, "Post-state differs from expected state"; "fields" => mismatches.len());
+
+            for mismatch in &mismatches {
+                warn!(
+                    log,
+                    "Field not equal (a = expected, b = result)";
+                    "field" => format!("{:#?}", mismatch),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the fields which differ between `a` and `b`, ignoring caches.
+fn diff_states<E: EthSpec>(a: &mut BeaconState<E>, b: &mut BeaconState<E>) -> Vec<Comparison> {
+    a.drop_all_caches();
+    b.drop_all_caches();
+
+    let mut mismatches: Vec<Comparison> = a
+        .compare_fields(b)
+        .into_iter()
+        .filter(Comparison::not_equal)
+        .collect();
+
+    mismatches
+        .iter_mut()
+        .for_each(|f| f.retain_children(FieldComparison::not_equal));
+
+    mismatches
+}
+
+fn read_ssz<T: Decode>(path: &Path) -> Result<T, String> {
+    let file = File::open(path).map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+    let len = file
+        .metadata()
+        .map_err(|e| format!("Unable to read metadata of {:?}: {:?}", path, e))?
+        .len() as usize;
+
+    T::from_ssz_reader(&mut BufReader::new(file), len)
+        .map_err(|e| format!("Unable to decode {:?}: {:?}", path, e))
+}
+
+fn write_ssz<T: Encode>(path: &Path, item: &T) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+    let mut writer = BufWriter::new(file);
+
+    item.ssz_write(&mut writer)
+        .and_then(|()| writer.flush())
+        .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slog::o;
+    use state_processing::per_block_processing;
+    use state_processing::per_block_processing::block_processing_builder::BlockProcessingBuilder;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 16;
+
+    #[test]
+    fn replays_block() {
+        let spec = E::default_spec();
+        let log = Logger::root(slog::Discard, o!());
+        let dir = tempfile::tempdir().unwrap();
+
+        let (block, pre_state) =
+            BlockProcessingBuilder::<E>::new(VALIDATOR_COUNT, &spec).build(None, None, &spec);
+
+        let mut expected = pre_state.clone();
+        per_block_processing(&mut expected, &block, &spec).expect("block should be valid");
+
+        let config = Config {
+            pre_state_path: dir.path().join("pre_state.ssz"),
+            block_path: dir.path().join("block.ssz"),
+            expected_state_path: Some(dir.path().join("expected.ssz")),
+            output_dir: Some(dir.path().join("steps")),
+            verify_signatures: true,
+        };
+
+        write_ssz(&config.pre_state_path, &pre_state).unwrap();
+        write_ssz(&config.block_path, &block).unwrap();
+        write_ssz(config.expected_state_path.as_ref().unwrap(), &expected).unwrap();
+
+        run::<E>(&config, &log).expect("replay should succeed");
+
+        // The pre-state, the block header and every step of the block body.
+        let output_dir = config.output_dir.as_ref().unwrap();
+        assert_eq!(
+            fs::read_dir(output_dir).unwrap().count(),
+            2 + BLOCK_BODY_STEPS.len()
+        );
+
+        let last_step = BLOCK_BODY_STEPS[BLOCK_BODY_STEPS.len() - 1];
+        let file_name = format!("{:03}_{}.ssz", 1 + BLOCK_BODY_STEPS.len(), last_step.name());
+        let mut post_state: BeaconState<E> = read_ssz(&output_dir.join(file_name)).unwrap();
+
+        assert!(diff_states(&mut expected, &mut post_state).is_empty());
+    }
+}
@@ -10,8 +10,8 @@ use beacon_node_attestation::BeaconNodeAttestation;
 use slog::{error, info, warn};
 use tree_hash::TreeHash;
 use types::{
-    AggregateSignature, Attestation, AttestationData, AttestationDataAndCustodyBit,
-    AttestationDuty, Bitfield,
+    AggregateSignature, Attestation, AttestationBitfield, AttestationData,
+    AttestationDataAndCustodyBit, AttestationDuty,
 };
 
 //TODO: Group these errors at a crate level
@@ -134,9 +134,12 @@ impl<'a, B: BeaconNodeAttestation, S: Signer> AttestationProducer<'a, B, S> {
             agg_sig
         };
 
-        let mut aggregation_bitfield = Bitfield::with_capacity(duties.committee_len);
-        let custody_bitfield = Bitfield::with_capacity(duties.committee_len);
-        aggregation_bitfield.set(duties.committee_index, true);
+        let mut aggregation_bitfield =
+            AttestationBitfield::with_capacity(duties.committee_len).ok()?;
+        let custody_bitfield = AttestationBitfield::with_capacity(duties.committee_len).ok()?;
+        aggregation_bitfield
+            .set(duties.committee_index, true)
+            .ok()?;
 
         Some(Attestation {
             aggregation_bitfield,
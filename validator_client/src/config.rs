@@ -1,16 +1,18 @@
 use bincode;
-use bls::Keypair;
+use bls::{Keypair, PublicKey};
 use clap::ArgMatches;
 use serde_derive::{Deserialize, Serialize};
 use slog::{debug, error, info};
 use std::fs;
 use std::fs::File;
 use std::io::{Error, ErrorKind};
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use types::{EthSpec, MainnetEthSpec};
 
 /// Stores the core configuration for this validator instance.
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     /// The data directory, which stores all validator databases
     pub data_dir: PathBuf,
@@ -18,9 +20,17 @@ pub struct Config {
     pub server: String,
     /// The number of slots per epoch.
     pub slots_per_epoch: u64,
+    /// The number of epochs to watch the chain for activity from our validators before they begin
+    /// signing. Doppelganger detection is disabled if this is zero.
+    pub doppelganger_detection_epochs: u64,
+    /// If `Some`, sign with the interop keypairs at these validator indices instead of the keys
+    /// in the data directory.
+    #[serde(skip)]
+    pub interop_keys: Option<Range<usize>>,
 }
 
-const DEFAULT_PRIVATE_KEY_FILENAME: &str = "private.key";
+pub const DEFAULT_PRIVATE_KEY_FILENAME: &str = "private.key";
+const DEFAULT_DOPPELGANGER_DETECTION_EPOCHS: u64 = 2;
 
 impl Default for Config {
     /// Build a new configuration from defaults.
@@ -29,6 +39,8 @@ impl Default for Config {
             data_dir: PathBuf::from(".lighthouse-validator"),
             server: "localhost:5051".to_string(),
             slots_per_epoch: MainnetEthSpec::slots_per_epoch(),
+            doppelganger_detection_epochs: DEFAULT_DOPPELGANGER_DETECTION_EPOCHS,
+            interop_keys: None,
         }
     }
 }
@@ -47,62 +59,73 @@ impl Config {
             self.server = srv.to_string();
         };
 
+        if let Some(epochs) = args.value_of("doppelganger-epochs") {
+            self.doppelganger_detection_epochs =
+                epochs.parse().map_err(|_| "Invalid doppelganger-epochs")?;
+        };
+
+        if args.is_present("disable-doppelganger-detection") {
+            self.doppelganger_detection_epochs = 0;
+        };
+
+        if let Some(range) = args.value_of("interop-keys") {
+            self.interop_keys = Some(parse_index_range(range).ok_or("Invalid interop-keys")?);
+        };
+
         Ok(())
     }
 
     /// Try to load keys from validator_dir, returning None if none are found or an error.
+    ///
+    /// If `self.interop_keys` is set, the interop keypairs are returned instead.
     #[allow(dead_code)]
     pub fn fetch_keys(&self, log: &slog::Logger) -> Option<Vec<Keypair>> {
-        let key_pairs: Vec<Keypair> = fs::read_dir(&self.data_dir)
-            .ok()?
-            .filter_map(|validator_dir| {
-                let validator_dir = validator_dir.ok()?;
+        if let Some(range) = &self.interop_keys {
+            info!(
+                log,
+                "Using interop keypairs";
+                "first_index" => range.start,
+                "count" => range.len(),
+            );
 
-                if !(validator_dir.file_type().ok()?.is_dir()) {
-                    // Skip non-directories (i.e. no files/symlinks)
-                    return None;
-                }
-
-                let key_filename = validator_dir.path().join(DEFAULT_PRIVATE_KEY_FILENAME);
+            let key_pairs = eth2_interop_keypairs::keypairs(range.clone());
+            return if key_pairs.is_empty() {
+                None
+            } else {
+                Some(key_pairs)
+            };
+        }
 
-                if !(key_filename.is_file()) {
-                    info!(
+        let key_pairs: Vec<Keypair> = self
+            .read_validator_dirs()
+            .ok()?
+            .into_iter()
+            .filter_map(|validator_dir| match validator_dir.key {
+                Ok(key) => {
+                    debug!(
                         log,
-                        "Private key is not a file: {:?}",
-                        key_filename.to_str()
+                        "Deserialized private key from file: {:?}",
+                        validator_dir.key_path().to_str()
                     );
-                    return None;
+                    Some(key)
                 }
-
-                debug!(
-                    log,
-                    "Deserializing private key from file: {:?}",
-                    key_filename.to_str()
-                );
-
-                let mut key_file = File::open(key_filename.clone()).ok()?;
-
-                let key: Keypair = if let Ok(key_ok) = bincode::deserialize_from(&mut key_file) {
-                    key_ok
-                } else {
-                    error!(
+                Err(KeyError::MissingKeyFile) => {
+                    info!(
                         log,
-                        "Unable to deserialize the private key file: {:?}", key_filename
+                        "Private key is not a file: {:?}",
+                        validator_dir.key_path().to_str()
                     );
-                    return None;
-                };
-
-                let ki = key.identifier();
-                if ki != validator_dir.file_name().into_string().ok()? {
+                    None
+                }
+                Err(e) => {
                     error!(
                         log,
-                        "The validator key ({:?}) did not match the directory filename {:?}.",
-                        ki,
-                        &validator_dir.path().to_string_lossy()
+                        "Unable to load validator key";
+                        "path" => validator_dir.path.to_string_lossy().to_string(),
+                        "error" => format!("{:?}", e)
                     );
-                    return None;
+                    None
                 }
-                Some(key)
             })
             .collect();
 
@@ -114,6 +137,39 @@ impl Config {
         }
     }
 
+    /// Attempt to load the key from every directory in the data directory.
+    ///
+    /// Unlike `fetch_keys`, directories whose key cannot be loaded are returned alongside the
+    /// reason, rather than being skipped.
+    pub fn read_validator_dirs(&self) -> Result<Vec<ValidatorDir>, Error> {
+        let mut validator_dirs = vec![];
+
+        for entry in fs::read_dir(&self.data_dir)? {
+            let entry = entry?;
+
+            if !entry.file_type()?.is_dir() {
+                // Skip non-directories (i.e. no files/symlinks)
+                continue;
+            }
+
+            let path = entry.path();
+            let key = read_keypair_file(&path.join(DEFAULT_PRIVATE_KEY_FILENAME)).and_then(|key| {
+                let identifier = key.identifier();
+                if entry.file_name().to_str() == Some(&identifier) {
+                    Ok(key)
+                } else {
+                    Err(KeyError::MismatchedDirectory { identifier })
+                }
+            });
+
+            validator_dirs.push(ValidatorDir { path, key });
+        }
+
+        validator_dirs.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(validator_dirs)
+    }
+
     /// Saves a keypair to a file inside the appropriate validator directory. Returns the saved path filename.
     #[allow(dead_code)]
     pub fn save_key(&self, key: &Keypair) -> Result<PathBuf, Error> {
@@ -129,3 +185,208 @@ impl Config {
         Ok(key_path)
     }
 }
+
+/// The reasons the key in a validator directory may fail to load.
+#[derive(Debug, PartialEq, Clone)]
+pub enum KeyError {
+    /// The directory does not contain a private key file.
+    MissingKeyFile,
+    /// The private key file could not be read or deserialized.
+    Unreadable(String),
+    /// The public key is not derived from the secret key.
+    InvalidKeypair,
+    /// The name of the directory does not match `Keypair::identifier()`.
+    MismatchedDirectory { identifier: String },
+}
+
+/// A directory in the validator data directory, and the result of loading its key.
+pub struct ValidatorDir {
+    pub path: PathBuf,
+    pub key: Result<Keypair, KeyError>,
+}
+
+impl ValidatorDir {
+    /// The path of the private key file within this directory.
+    pub fn key_path(&self) -> PathBuf {
+        self.path.join(DEFAULT_PRIVATE_KEY_FILENAME)
+    }
+}
+
+/// Parse a half-open range of validator indices of the form `START..END`.
+fn parse_index_range(range: &str) -> Option<Range<usize>> {
+    let mut split = range.splitn(2, "..");
+    let start = split.next()?.trim().parse().ok()?;
+    let end = split.next()?.trim().parse().ok()?;
+
+    if start <= end {
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
+/// Read a bincode-serialized `Keypair` from `path`, checking that its public key matches its
+/// secret key.
+pub fn read_keypair_file(path: &Path) -> Result<Keypair, KeyError> {
+    if !path.is_file() {
+        return Err(KeyError::MissingKeyFile);
+    }
+
+    let mut key_file = File::open(path).map_err(|e| KeyError::Unreadable(format!("{:?}", e)))?;
+    let key: Keypair = bincode::deserialize_from(&mut key_file)
+        .map_err(|e| KeyError::Unreadable(format!("{:?}", e)))?;
+
+    if PublicKey::from_secret_key(&key.sk) != key.pk {
+        return Err(KeyError::InvalidKeypair);
+    }
+
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_index_range_valid() {
+        assert_eq!(parse_index_range("0..4"), Some(0..4));
+        assert_eq!(parse_index_range(" 2 .. 8 "), Some(2..8));
+        assert_eq!(parse_index_range("3..3"), Some(3..3));
+    }
+
+    #[test]
+    fn parse_index_range_invalid() {
+        assert_eq!(parse_index_range("4..0"), None);
+        assert_eq!(parse_index_range("4"), None);
+        assert_eq!(parse_index_range("..4"), None);
+        assert_eq!(parse_index_range("0.."), None);
+        assert_eq!(parse_index_range("a..4"), None);
+        assert_eq!(parse_index_range("-1..4"), None);
+        assert_eq!(parse_index_range("0..4..8"), None);
+    }
+
+    fn config_in(dir: &Path) -> Config {
+        Config {
+            data_dir: dir.to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn save_and_read_keypair_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config_in(dir.path());
+        let keypair = Keypair::random();
+
+        let path = config.save_key(&keypair).unwrap();
+
+        assert_eq!(
+            path,
+            dir.path()
+                .join(keypair.identifier())
+                .join(DEFAULT_PRIVATE_KEY_FILENAME)
+        );
+        assert_eq!(read_keypair_file(&path).map(|key| key.pk), Ok(keypair.pk));
+    }
+
+    #[test]
+    fn read_keypair_file_errors() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let missing = dir.path().join("missing.key");
+        assert_eq!(
+            read_keypair_file(&missing).map(|key| key.pk),
+            Err(KeyError::MissingKeyFile)
+        );
+        assert_eq!(
+            read_keypair_file(dir.path()).map(|key| key.pk),
+            Err(KeyError::MissingKeyFile)
+        );
+
+        let garbage = dir.path().join("garbage.key");
+        fs::write(&garbage, [1, 2, 3]).unwrap();
+        match read_keypair_file(&garbage) {
+            Err(KeyError::Unreadable(_)) => {}
+            other => panic!(
+                "expected an unreadable key, got {:?}",
+                other.map(|key| key.pk)
+            ),
+        }
+
+        let mismatched = dir.path().join("mismatched.key");
+        let keypair = Keypair {
+            sk: Keypair::random().sk,
+            pk: Keypair::random().pk,
+        };
+        bincode::serialize_into(File::create(&mismatched).unwrap(), &keypair).unwrap();
+        assert_eq!(
+            read_keypair_file(&mismatched).map(|key| key.pk),
+            Err(KeyError::InvalidKeypair)
+        );
+    }
+
+    #[test]
+    fn read_validator_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config_in(dir.path());
+
+        let valid = Keypair::random();
+        config.save_key(&valid).unwrap();
+
+        // A key stored under the directory of a different key.
+        let moved = Keypair::random();
+        let moved_path = config.save_key(&moved).unwrap();
+        let renamed_dir = dir.path().join("renamed");
+        fs::rename(moved_path.parent().unwrap(), &renamed_dir).unwrap();
+
+        let empty_dir = dir.path().join("empty");
+        fs::create_dir(&empty_dir).unwrap();
+
+        // Files in the data directory are ignored.
+        fs::write(dir.path().join("not_a_dir"), [0]).unwrap();
+
+        let validator_dirs = config.read_validator_dirs().unwrap();
+
+        let mut expected_paths = vec![
+            dir.path().join(valid.identifier()),
+            renamed_dir.clone(),
+            empty_dir.clone(),
+        ];
+        expected_paths.sort();
+        assert_eq!(
+            validator_dirs
+                .iter()
+                .map(|validator_dir| validator_dir.path.clone())
+                .collect::<Vec<_>>(),
+            expected_paths
+        );
+
+        for validator_dir in &validator_dirs {
+            let expected = if validator_dir.path == renamed_dir {
+                Err(KeyError::MismatchedDirectory {
+                    identifier: moved.identifier(),
+                })
+            } else if validator_dir.path == empty_dir {
+                Err(KeyError::MissingKeyFile)
+            } else {
+                Ok(valid.pk.clone())
+            };
+
+            // Compare public keys, as `Keypair::eq` recurses infinitely.
+            assert_eq!(
+                validator_dir.key.as_ref().map(|key| key.pk.clone()),
+                expected
+            );
+        }
+
+        let keys = config.fetch_keys(&null_logger()).unwrap();
+        assert_eq!(
+            keys.into_iter().map(|key| key.pk).collect::<Vec<_>>(),
+            vec![valid.pk]
+        );
+    }
+
+    fn null_logger() -> slog::Logger {
+        slog::Logger::root(slog::Discard, slog::o!())
+    }
+}
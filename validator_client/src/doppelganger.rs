@@ -0,0 +1,198 @@
+/// Doppelganger detection.
+///
+/// If the same validator keys are loaded into two validator clients, both will sign messages and
+/// the validators will be slashed. To guard against this, the validator client watches the chain
+/// for a number of epochs after startup and refuses to sign anything if any attestations or blocks
+/// from its validators are seen.
+///
+/// Messages from the epoch in which the validator client started are ignored, as they may have
+/// been produced by this same validator client before it was restarted.
+use crate::duties::{BeaconNodeDuties, BeaconNodeDutiesError};
+use std::cmp;
+use std::sync::Arc;
+use types::{Epoch, PublicKey};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DoppelgangerStatus {
+    /// The chain is still being watched, validators must not sign any messages.
+    Watching { remaining_epochs: u64 },
+    /// No activity was seen during the detection period, validators may begin signing.
+    Complete,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// Some other instance has been seen signing messages with these validator keys.
+    DoppelgangerDetected(Vec<PublicKey>),
+    BeaconNodeDutiesError(BeaconNodeDutiesError),
+}
+
+/// Queries a Beacon Node for activity from a set of validators over a fixed range of epochs.
+pub struct DoppelgangerDetector<U: BeaconNodeDuties> {
+    beacon_node: Arc<U>,
+    pub_keys: Vec<PublicKey>,
+    /// The epoch in which the validator client started.
+    start_epoch: Epoch,
+    /// The number of epochs following `start_epoch` that must be free of activity.
+    detection_epochs: u64,
+}
+
+impl<U: BeaconNodeDuties> DoppelgangerDetector<U> {
+    /// Create a new detector which watches the `detection_epochs` epochs following `start_epoch`.
+    pub fn new(
+        beacon_node: Arc<U>,
+        pub_keys: Vec<PublicKey>,
+        start_epoch: Epoch,
+        detection_epochs: u64,
+    ) -> Self {
+        Self {
+            beacon_node,
+            pub_keys,
+            start_epoch,
+            detection_epochs,
+        }
+    }
+
+    /// Check the Beacon Node for activity during the previous and current epochs.
+    ///
+    /// Should be called at least once per epoch. Returns `DoppelgangerStatus::Complete` once the
+    /// last epoch of the detection period has ended without any activity being seen.
+    pub fn poll(&self, current_epoch: Epoch) -> Result<DoppelgangerStatus, Error> {
+        let first_epoch = self.start_epoch + 1;
+        let last_epoch = self.start_epoch + self.detection_epochs;
+
+        // Attestations may be included in the epoch following their target, so the previous epoch
+        // is checked again.
+        let from = cmp::max(first_epoch, current_epoch - 1).as_u64();
+        let to = cmp::min(last_epoch, current_epoch).as_u64();
+
+        for epoch in from..=to {
+            self.check_epoch(Epoch::new(epoch))?;
+        }
+
+        if current_epoch > last_epoch {
+            Ok(DoppelgangerStatus::Complete)
+        } else {
+            Ok(DoppelgangerStatus::Watching {
+                remaining_epochs: (last_epoch + 1 - current_epoch).as_u64(),
+            })
+        }
+    }
+
+    /// Returns an error if any of the validators were seen attesting or proposing during `epoch`.
+    fn check_epoch(&self, epoch: Epoch) -> Result<(), Error> {
+        let liveness = self.beacon_node.request_liveness(epoch, &self.pub_keys)?;
+
+        let live_pub_keys: Vec<PublicKey> = self
+            .pub_keys
+            .iter()
+            .zip(liveness.iter())
+            .filter(|(_, is_live)| **is_live)
+            .map(|(pub_key, _)| pub_key.clone())
+            .collect();
+
+        if live_pub_keys.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::DoppelgangerDetected(live_pub_keys))
+        }
+    }
+}
+
+impl From<BeaconNodeDutiesError> for Error {
+    fn from(e: BeaconNodeDutiesError) -> Error {
+        Error::BeaconNodeDutiesError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::duties::test_node::TestBeaconNode;
+    use bls::Keypair;
+
+    fn detector(
+        num_validators: usize,
+        start_epoch: u64,
+        detection_epochs: u64,
+    ) -> (DoppelgangerDetector<TestBeaconNode>, Arc<TestBeaconNode>) {
+        let beacon_node = Arc::new(TestBeaconNode::default());
+        let pub_keys = (0..num_validators).map(|_| Keypair::random().pk).collect();
+        let detector = DoppelgangerDetector::new(
+            beacon_node.clone(),
+            pub_keys,
+            Epoch::new(start_epoch),
+            detection_epochs,
+        );
+        (detector, beacon_node)
+    }
+
+    #[test]
+    fn completes_without_activity() {
+        let (detector, beacon_node) = detector(2, 10, 2);
+        beacon_node.set_next_liveness_result(Ok(vec![false, false]));
+
+        assert_eq!(
+            detector.poll(Epoch::new(10)),
+            Ok(DoppelgangerStatus::Watching {
+                remaining_epochs: 3
+            })
+        );
+        assert_eq!(
+            detector.poll(Epoch::new(11)),
+            Ok(DoppelgangerStatus::Watching {
+                remaining_epochs: 2
+            })
+        );
+        assert_eq!(
+            detector.poll(Epoch::new(12)),
+            Ok(DoppelgangerStatus::Watching {
+                remaining_epochs: 1
+            })
+        );
+        assert_eq!(
+            detector.poll(Epoch::new(13)),
+            Ok(DoppelgangerStatus::Complete)
+        );
+
+        // The start epoch is never checked and each epoch is checked again once it has ended.
+        assert_eq!(
+            beacon_node.liveness_epochs(),
+            vec![
+                Epoch::new(11),
+                Epoch::new(11),
+                Epoch::new(12),
+                Epoch::new(12)
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_activity() {
+        let (detector, beacon_node) = detector(3, 10, 2);
+        beacon_node.set_next_liveness_result(Ok(vec![false, false, false]));
+
+        assert!(detector.poll(Epoch::new(11)).is_ok());
+
+        beacon_node.set_next_liveness_result(Ok(vec![false, true, false]));
+
+        assert_eq!(
+            detector.poll(Epoch::new(12)),
+            Err(Error::DoppelgangerDetected(vec![
+                detector.pub_keys[1].clone()
+            ]))
+        );
+    }
+
+    #[test]
+    fn propagates_beacon_node_errors() {
+        let (detector, beacon_node) = detector(1, 10, 2);
+        let error = BeaconNodeDutiesError::RemoteFailure("unavailable".to_string());
+        beacon_node.set_next_liveness_result(Err(error.clone()));
+
+        assert_eq!(
+            detector.poll(Epoch::new(11)),
+            Err(Error::BeaconNodeDutiesError(error))
+        );
+    }
+}
@@ -17,4 +17,14 @@ pub trait BeaconNodeDuties: Send + Sync {
         epoch: Epoch,
         pub_keys: &[PublicKey],
     ) -> Result<EpochDuties, BeaconNodeDutiesError>;
+
+    /// Gets the liveness of all validators.
+    ///
+    /// Returns a vector with one entry for each validator public key, in the same order. The entry
+    /// will be `true` if the validator has been seen attesting or proposing during `epoch`.
+    fn request_liveness(
+        &self,
+        epoch: Epoch,
+        pub_keys: &[PublicKey],
+    ) -> Result<Vec<bool>, BeaconNodeDutiesError>;
 }
@@ -2,7 +2,7 @@ use super::beacon_node_duties::{BeaconNodeDuties, BeaconNodeDutiesError};
 use super::epoch_duties::{EpochDuties, EpochDuty};
 // to use if we manually specify a timeout
 //use grpcio::CallOption;
-use protos::services::{GetDutiesRequest, Validators};
+use protos::services::{GetDutiesRequest, ValidatorLivenessRequest, Validators};
 use protos::services_grpc::ValidatorServiceClient;
 use ssz::ssz_encode;
 use std::collections::HashMap;
@@ -64,4 +64,32 @@ impl BeaconNodeDuties for ValidatorServiceClient {
         }
         Ok(epoch_duties)
     }
+
+    /// Requests whether each validator has been seen attesting or proposing in `epoch` from the
+    /// Beacon Node (BN).
+    fn request_liveness(
+        &self,
+        epoch: Epoch,
+        pub_keys: &[PublicKey],
+    ) -> Result<Vec<bool>, BeaconNodeDutiesError> {
+        let mut req = ValidatorLivenessRequest::new();
+        req.set_epoch(epoch.as_u64());
+        let mut validators = Validators::new();
+        validators.set_public_keys(pub_keys.iter().map(|v| ssz_encode(v)).collect());
+        req.set_validators(validators);
+
+        let reply = self
+            .get_validator_liveness(&req)
+            .map_err(|err| BeaconNodeDutiesError::RemoteFailure(format!("{:?}", err)))?;
+
+        if reply.get_is_live().len() != pub_keys.len() {
+            return Err(BeaconNodeDutiesError::RemoteFailure(format!(
+                "Expected liveness for {} validators, got {}",
+                pub_keys.len(),
+                reply.get_is_live().len()
+            )));
+        }
+
+        Ok(reply.get_is_live().to_vec())
+    }
 }
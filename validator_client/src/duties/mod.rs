@@ -1,9 +1,8 @@
 mod beacon_node_duties;
 mod epoch_duties;
 mod grpc;
-// TODO: reintroduce tests
-//#[cfg(test)]
-//mod test_node;
+#[cfg(test)]
+pub mod test_node;
 
 pub use self::beacon_node_duties::{BeaconNodeDuties, BeaconNodeDutiesError};
 use self::epoch_duties::{EpochDuties, EpochDutiesMapError};
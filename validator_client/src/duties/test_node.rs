@@ -1,32 +1,61 @@
-use super::traits::{BeaconNode, BeaconNodeError};
+use super::beacon_node_duties::{BeaconNodeDuties, BeaconNodeDutiesError};
 use super::EpochDuties;
-use bls::PublicKey;
 use std::sync::RwLock;
-use types::Epoch;
+use types::{Epoch, PublicKey};
 
-type ShufflingResult = Result<Option<EpochDuties>, BeaconNodeError>;
+type DutiesResult = Result<EpochDuties, BeaconNodeDutiesError>;
+type LivenessResult = Result<Vec<bool>, BeaconNodeDutiesError>;
 
 /// A test-only struct used to simulate a Beacon Node.
 #[derive(Default)]
 pub struct TestBeaconNode {
-    pub request_shuffling_input: RwLock<Option<(Epoch, PublicKey)>>,
-    pub request_shuffling_result: RwLock<Option<ShufflingResult>>,
+    pub request_duties_input: RwLock<Option<(Epoch, Vec<PublicKey>)>>,
+    pub request_duties_result: RwLock<Option<DutiesResult>>,
+    pub request_liveness_inputs: RwLock<Vec<(Epoch, Vec<PublicKey>)>>,
+    pub request_liveness_result: RwLock<Option<LivenessResult>>,
 }
 
 impl TestBeaconNode {
-    /// Set the result to be returned when `request_shuffling` is called.
-    pub fn set_next_shuffling_result(&self, result: ShufflingResult) {
-        *self.request_shuffling_result.write().unwrap() = Some(result);
+    /// Set the result to be returned when `request_duties` is called.
+    pub fn set_next_duties_result(&self, result: DutiesResult) {
+        *self.request_duties_result.write().unwrap() = Some(result);
+    }
+
+    /// Set the result to be returned when `request_liveness` is called.
+    pub fn set_next_liveness_result(&self, result: LivenessResult) {
+        *self.request_liveness_result.write().unwrap() = Some(result);
+    }
+
+    /// Returns the epochs for which `request_liveness` has been called, in order.
+    pub fn liveness_epochs(&self) -> Vec<Epoch> {
+        self.request_liveness_inputs
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(epoch, _)| *epoch)
+            .collect()
     }
 }
 
-impl BeaconNode for TestBeaconNode {
-    /// Returns the value specified by the `set_next_shuffling_result`.
-    fn request_shuffling(&self, epoch: Epoch, public_key: &PublicKey) -> ShufflingResult {
-        *self.request_shuffling_input.write().unwrap() = Some((epoch, public_key.clone()));
-        match *self.request_shuffling_result.read().unwrap() {
+impl BeaconNodeDuties for TestBeaconNode {
+    /// Returns the value specified by the `set_next_duties_result`.
+    fn request_duties(&self, epoch: Epoch, pub_keys: &[PublicKey]) -> DutiesResult {
+        *self.request_duties_input.write().unwrap() = Some((epoch, pub_keys.to_vec()));
+        match *self.request_duties_result.read().unwrap() {
+            Some(ref r) => r.clone(),
+            None => panic!("TestBeaconNode: request_duties_result == None"),
+        }
+    }
+
+    /// Returns the value specified by the `set_next_liveness_result`.
+    fn request_liveness(&self, epoch: Epoch, pub_keys: &[PublicKey]) -> LivenessResult {
+        self.request_liveness_inputs
+            .write()
+            .unwrap()
+            .push((epoch, pub_keys.to_vec()));
+        match *self.request_liveness_result.read().unwrap() {
             Some(ref r) => r.clone(),
-            None => panic!("TestBeaconNode: produce_result == None"),
+            None => panic!("TestBeaconNode: request_liveness_result == None"),
         }
     }
 }
@@ -1,4 +1,5 @@
 use slot_clock;
+use types::PublicKey;
 
 use error_chain::error_chain;
 
@@ -15,5 +16,10 @@ error_chain! {
         description("Error reading system time"),
         display("SystemTimeError: '{}'", t)
     }
+
+    DoppelgangerDetected(pub_keys: Vec<PublicKey>) {
+        description("Validator keys are in use by another validator client"),
+        display("DoppelgangerDetected: '{:?}'", pub_keys)
+    }
    }
 }
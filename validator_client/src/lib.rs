@@ -1,3 +1,5 @@
 pub mod config;
 
-pub use crate::config::Config;
+pub use crate::config::{
+    read_keypair_file, Config, KeyError, ValidatorDir, DEFAULT_PRIVATE_KEY_FILENAME,
+};
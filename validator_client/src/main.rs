@@ -1,6 +1,7 @@
 mod attestation_producer;
 mod block_producer;
 mod config;
+mod doppelganger;
 mod duties;
 pub mod error;
 mod service;
@@ -64,6 +65,34 @@ fn main() {
                 .possible_values(&["mainnet", "minimal"])
                 .default_value("minimal"),
         )
+        .arg(
+            Arg::with_name("spec-yaml")
+                .long("spec-yaml")
+                .value_name("YAML_FILE")
+                .help("Path to a spec-compliant YAML config which replaces the values of the spec constants.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("doppelganger-epochs")
+                .long("doppelganger-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs to watch for other instances of our validators before signing.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("disable-doppelganger-detection")
+                .long("disable-doppelganger-detection")
+                .help("Begin signing immediately, without checking for other instances of our validators.")
+                .takes_value(false)
+                .conflicts_with("doppelganger-epochs"),
+        )
+        .arg(
+            Arg::with_name("interop-keys")
+                .long("interop-keys")
+                .value_name("START..END")
+                .help("Sign with the interop-standard deterministic keypairs for validator indices START (inclusive) to END (exclusive), instead of the keys in the datadir. Only for testing.")
+                .takes_value(true),
+        )
         .get_matches();
 
     let data_dir = match get_data_dir(&matches, PathBuf::from(DEFAULT_DATA_DIR)) {
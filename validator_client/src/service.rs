@@ -11,6 +11,7 @@
 use crate::attestation_producer::AttestationProducer;
 use crate::block_producer::{BeaconBlockGrpcClient, BlockProducer};
 use crate::config::Config as ValidatorConfig;
+use crate::doppelganger::{DoppelgangerDetector, DoppelgangerStatus, Error as DoppelgangerError};
 use crate::duties::{BeaconNodeDuties, DutiesManager, EpochDutiesMap};
 use crate::error as error_chain;
 use crate::error::ErrorKind;
@@ -23,7 +24,7 @@ use protos::services_grpc::{
     AttestationServiceClient, BeaconBlockServiceClient, BeaconNodeServiceClient,
     ValidatorServiceClient,
 };
-use slog::{error, info, warn};
+use slog::{crit, error, info, warn};
 use slot_clock::{SlotClock, SystemTimeSlotClock};
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -53,6 +54,9 @@ pub struct Service<B: BeaconNodeDuties + 'static, S: Signer + 'static> {
     spec: Arc<ChainSpec>,
     /// The duties manager which maintains the state of when to perform actions.
     duties_manager: Arc<DutiesManager<B, S>>,
+    /// Watches the chain for other instances of our validators. No duties are performed until
+    /// this is `None`.
+    doppelganger_detector: Option<DoppelgangerDetector<B>>,
     // GRPC Clients
     /// The beacon block GRPC client.
     beacon_block_client: Arc<BeaconBlockGrpcClient>,
@@ -191,6 +195,25 @@ impl<B: BeaconNodeDuties + 'static, S: Signer + 'static> Service<B, S> {
         // produce work on.
         let duties_map = RwLock::new(EpochDutiesMap::new(slots_per_epoch));
 
+        // builds a detector which ensures no other validator client is signing with our keys
+        // before we begin to sign.
+        let doppelganger_detector = if client_config.doppelganger_detection_epochs > 0 {
+            info!(
+                log,
+                "Doppelganger detection enabled";
+                "epochs" => client_config.doppelganger_detection_epochs
+            );
+            Some(DoppelgangerDetector::new(
+                validator_client.clone(),
+                keypairs.iter().map(|keypair| keypair.pk.clone()).collect(),
+                current_slot.epoch(slots_per_epoch),
+                client_config.doppelganger_detection_epochs,
+            ))
+        } else {
+            warn!(log, "Doppelganger detection disabled");
+            None
+        };
+
         // builds a manager which maintains the list of current duties for all known validators
         // and can check when a validator needs to perform a task.
         let duties_manager = Arc::new(DutiesManager {
@@ -209,6 +232,7 @@ impl<B: BeaconNodeDuties + 'static, S: Signer + 'static> Service<B, S> {
             slots_per_epoch,
             spec,
             duties_manager,
+            doppelganger_detector,
             beacon_block_client,
             attestation_client,
             log,
@@ -257,15 +281,21 @@ impl<B: BeaconNodeDuties + 'static, S: Signer + 'static> Service<B, S> {
         /* kick off the core service */
         runtime.block_on(
             interval
+                .map_err(|e| error_chain::Error::from(format!("Service thread failed: {:?}", e)))
                 .for_each(move |_| {
                     // wait for node to process
                     std::thread::sleep(TIME_DELAY_FROM_SLOT);
-                    // if a non-fatal error occurs, proceed to the next slot.
-                    let _ignore_error = service.per_slot_execution();
-                    // completed a slot process
-                    Ok(())
-                })
-                .map_err(|e| format!("Service thread failed: {:?}", e)),
+                    match service.per_slot_execution() {
+                        // another instance of our validators is fatal, stop before signing.
+                        Err(error_chain::Error(ErrorKind::DoppelgangerDetected(pub_keys), _)) => {
+                            Err(error_chain::Error::from(ErrorKind::DoppelgangerDetected(
+                                pub_keys,
+                            )))
+                        }
+                        // if a non-fatal error occurs, proceed to the next slot.
+                        _ => Ok(()),
+                    }
+                }),
         )?;
         // validator client exited
         Ok(())
@@ -280,6 +310,11 @@ impl<B: BeaconNodeDuties + 'static, S: Signer + 'static> Service<B, S> {
         /* check for new duties */
         self.check_for_duties();
 
+        /* refuse to sign anything until doppelganger detection has completed */
+        if self.check_for_doppelgangers()? {
+            return Ok(());
+        }
+
         /* process any required duties for validators */
         self.process_duties();
 
@@ -327,6 +362,45 @@ impl<B: BeaconNodeDuties + 'static, S: Signer + 'static> Service<B, S> {
         //});
     }
 
+    /// Polls the doppelganger detector, if any. Returns `true` whilst detection is in progress and
+    /// no duties should be performed.
+    ///
+    /// Returns an error if another instance of any of our validators has been seen.
+    fn check_for_doppelgangers(&mut self) -> error_chain::Result<bool> {
+        let current_epoch = self.current_slot.epoch(self.slots_per_epoch);
+
+        let status = match &self.doppelganger_detector {
+            Some(detector) => detector.poll(current_epoch),
+            None => return Ok(false),
+        };
+
+        match status {
+            Ok(DoppelgangerStatus::Watching { remaining_epochs }) => {
+                info!(self.log, "Checking for doppelgangers"; "remaining_epochs" => remaining_epochs);
+                Ok(true)
+            }
+            Ok(DoppelgangerStatus::Complete) => {
+                info!(
+                    self.log,
+                    "No doppelgangers detected, starting validator duties"
+                );
+                self.doppelganger_detector = None;
+                Ok(false)
+            }
+            Err(DoppelgangerError::DoppelgangerDetected(pub_keys)) => {
+                for pub_key in &pub_keys {
+                    crit!(self.log, "Doppelganger detected, another instance is signing for this validator"; "Validator" => format!("{}", pub_key));
+                }
+                Err(ErrorKind::DoppelgangerDetected(pub_keys).into())
+            }
+            Err(DoppelgangerError::BeaconNodeDutiesError(e)) => {
+                // fail safe, no duties are performed until the check succeeds.
+                error!(self.log, "Doppelganger detection poll error"; "error" => format!("{:?}", e));
+                Ok(true)
+            }
+        }
+    }
+
     /// If there are any duties to process, spawn a separate thread and perform required actions.
     fn process_duties(&mut self) {
         if let Some(work) = self.duties_manager.get_current_work(self.current_slot) {